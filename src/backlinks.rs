@@ -0,0 +1,290 @@
+//! Generated "Referenced by" blocks. Schemas opt in with
+//! `[schema.backlinks] enabled = true`; `cli-rag backlinks sync` keeps a fenced
+//! block at the end of each of their notes listing incoming edges by kind, and
+//! `validate` flags blocks that no longer match the graph.
+//!
+//! The block sits between HTML comment markers so it renders invisibly, and
+//! wikilink/Markdown link parsing skips it: backlinks never feed the graph.
+
+use anyhow::{anyhow, Result};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Component, Path};
+
+use crate::config::schema::SchemaBacklinksCfg;
+use crate::config::{build_schema_sets, Config};
+use crate::index::{index_entries, IndexEntry};
+use crate::mdlink::normalize_path;
+use crate::model::AdrDoc;
+use crate::wikilink::{track_fence, LinkResolver};
+
+pub const BLOCK_START: &str = "<!-- cli-rag:backlinks -->";
+pub const BLOCK_END: &str = "<!-- /cli-rag:backlinks -->";
+
+/// Label per incoming edge kind, in block order; other kinds follow by name.
+const KIND_LABELS: &[(&str, &str)] = &[
+    ("depends_on", "Dependents"),
+    ("supersedes", "Superseded by"),
+    ("superseded_by", "Supersedes"),
+    ("mentions", "Mentioned by"),
+    ("links", "Linked from"),
+];
+
+/// An opted-in note and the block it should carry (`None`: no referrers).
+pub struct PlannedBlock<'a> {
+    pub doc: &'a AdrDoc,
+    pub cfg: &'a SchemaBacklinksCfg,
+    pub block: Option<String>,
+}
+
+impl PlannedBlock<'_> {
+    /// `content` with this note's block brought up to date; an error when
+    /// the existing block is malformed and the note must be left alone.
+    pub fn apply(&self, content: &str) -> Result<String> {
+        apply_block(content, self.block.as_deref())
+    }
+}
+
+/// Blocks for every note whose schema enables backlinks. Incoming edges come
+/// from all `docs`, so pass the whole corpus even when checking a subset.
+pub fn plan_blocks<'a>(cfg: &'a Config, docs: &'a [AdrDoc]) -> Vec<PlannedBlock<'a>> {
+    plan_blocks_with(cfg, docs, || {
+        index_entries(cfg, docs, Path::new(""), &LinkResolver::new(docs))
+    })
+}
+
+/// [`plan_blocks`] with incoming edges taken from `entries`, the index
+/// entries of all `docs`; they are only asked for when some note opts in.
+pub fn plan_blocks_with<'a, E: AsRef<[IndexEntry]>>(
+    cfg: &'a Config,
+    docs: &'a [AdrDoc],
+    entries: impl FnOnce() -> E,
+) -> Vec<PlannedBlock<'a>> {
+    let schema_sets = build_schema_sets(cfg);
+    let opted: Vec<(&AdrDoc, &SchemaBacklinksCfg)> = docs
+        .iter()
+        .filter(|d| d.id.is_some())
+        .filter_map(|d| {
+            let fname = d.file.file_name().and_then(|s| s.to_str()).unwrap_or("");
+            let (matched, _) = schema_sets.iter().find(|(_, set)| set.is_match(fname))?;
+            let sc = cfg.schema.iter().find(|s| s.name == matched.name)?;
+            let bl = sc.backlinks.as_ref().filter(|b| b.enabled)?;
+            Some((d, bl))
+        })
+        .collect();
+    if opted.is_empty() {
+        return Vec::new();
+    }
+
+    // target id -> kind -> referrer ids
+    let mut incoming: HashMap<String, BTreeMap<String, BTreeSet<String>>> = HashMap::new();
+    for entry in entries().as_ref() {
+        for e in &entry.edges {
+            let (Some(to), Some(kind)) = (e["to"].as_str(), e["kind"].as_str()) else {
+                continue;
+            };
+            if to != entry.id {
+                incoming
+                    .entry(to.to_string())
+                    .or_default()
+                    .entry(kind.to_string())
+                    .or_default()
+                    .insert(entry.id.clone());
+            }
+        }
+    }
+    let mut by_id: HashMap<&str, &AdrDoc> = HashMap::new();
+    for d in docs {
+        if let Some(id) = d.id.as_deref() {
+            by_id.entry(id).or_insert(d);
+        }
+    }
+
+    opted
+        .into_iter()
+        .map(|(doc, bl)| {
+            let id = doc.id.as_deref().unwrap_or_default();
+            let kinds = incoming.remove(id).unwrap_or_default();
+            let mut groups: Vec<(String, Vec<&AdrDoc>)> = Vec::new();
+            for (kind, ids) in order_kinds(kinds, &bl.kinds) {
+                let refs: Vec<&AdrDoc> = ids
+                    .iter()
+                    .filter_map(|i| by_id.get(i.as_str()).copied())
+                    .collect();
+                if !refs.is_empty() {
+                    groups.push((kind, refs));
+                }
+            }
+            PlannedBlock {
+                doc,
+                cfg: bl,
+                block: render_block(doc, &groups),
+            }
+        })
+        .collect()
+}
+
+fn order_kinds(
+    mut kinds: BTreeMap<String, BTreeSet<String>>,
+    only: &[String],
+) -> Vec<(String, BTreeSet<String>)> {
+    if !only.is_empty() {
+        kinds.retain(|k, _| only.contains(k));
+    }
+    let mut out = Vec::new();
+    for (kind, _) in KIND_LABELS {
+        if let Some(ids) = kinds.remove(*kind) {
+            out.push((kind.to_string(), ids));
+        }
+    }
+    out.extend(kinds);
+    out
+}
+
+fn render_block(doc: &AdrDoc, groups: &[(String, Vec<&AdrDoc>)]) -> Option<String> {
+    if groups.is_empty() {
+        return None;
+    }
+    let dir = normalize_path(&doc.file);
+    let dir = dir.parent().unwrap_or(Path::new(""));
+    let mut out = format!("{}\n**Referenced by**\n", BLOCK_START);
+    for (kind, refs) in groups {
+        let label = KIND_LABELS
+            .iter()
+            .find(|(k, _)| k == kind)
+            .map(|(_, l)| l.to_string())
+            .unwrap_or_else(|| kind.clone());
+        out.push_str(&format!("\n{}:\n", label));
+        for r in refs {
+            let id = r.id.as_deref().unwrap_or_default();
+            let mut dest = relative_path(dir, &normalize_path(&r.file));
+            if dest.contains(' ') {
+                dest = format!("<{}>", dest);
+            }
+            out.push_str(&format!("- [{}]({})", id, dest));
+            let title = r.title.trim();
+            if !title.is_empty() && title != id {
+                out.push_str(&format!(": {}", title));
+            }
+            out.push('\n');
+        }
+    }
+    out.push_str(BLOCK_END);
+    Some(out)
+}
+
+/// `/`-separated path from directory `from` to `to`, both absolute.
+fn relative_path(from: &Path, to: &Path) -> String {
+    let a: Vec<Component> = from.components().collect();
+    let b: Vec<Component> = to.components().collect();
+    let common = a.iter().zip(&b).take_while(|(x, y)| x == y).count();
+    let mut parts: Vec<String> = vec!["..".to_string(); a.len() - common];
+    parts.extend(
+        b[common..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+    );
+    parts.join("/")
+}
+
+/// Line range of the existing block, start marker to end marker. Markers
+/// inside fenced code are text, not a block. A start marker without an end
+/// marker is an error: guessing where the block ends could delete prose.
+fn block_range(lines: &[&str]) -> Result<Option<(usize, usize)>> {
+    let mut fence = None;
+    let mut start = None;
+    for (i, line) in lines.iter().enumerate() {
+        if track_fence(&mut fence, line.trim_start()) || fence.is_some() {
+            continue;
+        }
+        match start {
+            None if line.trim() == BLOCK_START => start = Some(i),
+            Some(s) if line.trim() == BLOCK_END => return Ok(Some((s, i + 1))),
+            _ => {}
+        }
+    }
+    match start {
+        Some(s) => Err(anyhow!(
+            "backlinks block starting on line {} has no `{}` marker; fix it by hand",
+            s + 1,
+            BLOCK_END
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Replace, append or remove the block. The block always ends the note,
+/// after one blank line; line endings follow the note (LF or CRLF).
+pub fn apply_block(content: &str, block: Option<&str>) -> Result<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let range = block_range(&lines)?;
+    if range.is_none() && block.is_none() {
+        return Ok(content.to_string());
+    }
+    let eol = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut kept: Vec<&str> = match range {
+        Some((start, end)) => lines[..start]
+            .iter()
+            .chain(&lines[end..])
+            .copied()
+            .collect(),
+        None => lines,
+    };
+    while kept.last().is_some_and(|l| l.trim().is_empty()) {
+        kept.pop();
+    }
+    if let Some(block) = block {
+        if !kept.is_empty() {
+            kept.push("");
+        }
+        kept.extend(block.lines());
+    }
+    let mut out = kept.join(eol);
+    if !out.is_empty() {
+        out.push_str(eol);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLOCK: &str = "<!-- cli-rag:backlinks -->\n**Referenced by**\n\nDependents:\n- [B](B.md)\n<!-- /cli-rag:backlinks -->";
+
+    #[test]
+    fn apply_is_idempotent_and_removes_empty_blocks() {
+        let note = "---\nid: A\n---\n\n# A\n\nBody.\n\n";
+        let once = apply_block(note, Some(BLOCK)).unwrap();
+        assert_eq!(
+            once,
+            format!("---\nid: A\n---\n\n# A\n\nBody.\n\n{}\n", BLOCK)
+        );
+        assert_eq!(apply_block(&once, Some(BLOCK)).unwrap(), once);
+        assert_eq!(
+            apply_block(&once, None).unwrap(),
+            "---\nid: A\n---\n\n# A\n\nBody.\n"
+        );
+        assert_eq!(apply_block(note, None).unwrap(), note);
+        let crlf = apply_block("# A\r\n\r\nBody.\r\n", Some(BLOCK)).unwrap();
+        assert!(crlf.ends_with("Body.\r\n\r\n<!-- cli-rag:backlinks -->\r\n**Referenced by**\r\n\r\nDependents:\r\n- [B](B.md)\r\n<!-- /cli-rag:backlinks -->\r\n"));
+    }
+
+    #[test]
+    fn unterminated_blocks_are_errors_and_fenced_markers_text() {
+        let open = "# A\n\n<!-- cli-rag:backlinks -->\nProse.\n";
+        assert!(apply_block(open, None).is_err());
+        let fenced = "# A\n\n~~~\n<!-- cli-rag:backlinks -->\n~~~\n";
+        assert_eq!(apply_block(fenced, None).unwrap(), fenced);
+    }
+
+    #[test]
+    fn relative_paths_walk_up_and_down() {
+        let rel = relative_path(Path::new("/n/adr"), Path::new("/n/imp/IMP-1.md"));
+        assert_eq!(rel, "../imp/IMP-1.md");
+        assert_eq!(relative_path(Path::new("/n"), Path::new("/n/A.md")), "A.md");
+    }
+}
@@ -2,11 +2,12 @@ use anyhow::Result;
 use clap::{CommandFactory, Parser};
 
 use cli_rag::cli::{
-    AiCommands, AiIndexApplyArgs, AiIndexCommands, AiIndexPlanArgs, AiNewCommands, AiNewSubmitArgs,
-    Cli, Commands,
+    AiCommands, AiIndexCommands, AiNewCommands, AiNewSubmitArgs, Cli, Commands, ConfigCommands,
+    LuaCommands, SchemaCommands,
 };
 use cli_rag::commands::ai_new::{SubmitInput, SubmitRequest};
 use cli_rag::config::load_config;
+use cli_rag::config::provenance::ExplainInputs;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -55,16 +56,64 @@ fn main() -> Result<()> {
             let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
             cli_rag::commands::info::run(&cfg, &cfg_path, &cli.format)?;
         }
+        Commands::Config { command } => match command {
+            ConfigCommands::Explain(args) => {
+                let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
+                let inputs = ExplainInputs {
+                    explicit_config: &cli.config,
+                    base_override: &cli.base,
+                    no_lua: cli.no_lua,
+                };
+                cli_rag::commands::config_cmd::explain(
+                    &cfg,
+                    &cfg_path,
+                    &inputs,
+                    args.key,
+                    &cli.format,
+                )?;
+            }
+        },
+        Commands::Schema { command } => match command {
+            SchemaCommands::Export(args) => {
+                let (cfg, _cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
+                cli_rag::commands::schema_export::run(&cfg, args.name, args.out, &cli.format)?;
+            }
+        },
+        Commands::Lua { command } => match command {
+            LuaCommands::Test(args) => {
+                let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
+                cli_rag::commands::lua_integration::run_lua_tests(
+                    &cfg,
+                    &cfg_path,
+                    &cli.format,
+                    args.paths,
+                    args.filter,
+                )?;
+            }
+        },
         Commands::Completions { shell } => {
-            let cmd = Cli::command();
+            // View names come from the config when one is found; completions work without it.
+            let views = load_config(&cli.config, &cli.base, true)
+                .map(|(cfg, _)| cfg.view.into_iter().map(|v| v.name).collect())
+                .unwrap_or_default();
+            let cmd = cli_rag::commands::completions::with_view_names(Cli::command(), views);
             cli_rag::commands::completions::run_completions(cmd, shell);
         }
+        Commands::Agenda { days, overdue } => {
+            let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
+            cli_rag::commands::agenda::run(&cfg, &cfg_path, &cli.format, days, overdue)?;
+        }
+        Commands::View { name } => {
+            let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
+            cli_rag::commands::view::run(&cfg, &cfg_path, &cli.format, name)?;
+        }
         Commands::Search {
             query,
             kind,
             schema,
             status,
             tag,
+            render,
         } => {
             let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
             cli_rag::commands::search::run(
@@ -76,6 +125,7 @@ fn main() -> Result<()> {
                 schema,
                 status,
                 tag,
+                render,
             )?;
         }
 
@@ -85,6 +135,7 @@ fn main() -> Result<()> {
             neighbor_style,
             depth,
             max_fanout,
+            section,
         } => {
             let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
             cli_rag::commands::get::run(
@@ -96,6 +147,7 @@ fn main() -> Result<()> {
                 neighbor_style,
                 depth,
                 max_fanout,
+                section,
             )?;
         }
         Commands::Cluster {
@@ -126,6 +178,7 @@ fn main() -> Result<()> {
             depth,
             include_bidirectional,
             graph_format,
+            render,
         } => {
             let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
             cli_rag::commands::graph::run(
@@ -135,6 +188,7 @@ fn main() -> Result<()> {
                 id,
                 depth,
                 include_bidirectional,
+                render,
             )?;
         }
         Commands::Watch {
@@ -153,6 +207,36 @@ fn main() -> Result<()> {
                 json,
             )?;
         }
+        Commands::Daemon { debounce_ms } => {
+            let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
+            cli_rag::daemon::run(&cfg, &cfg_path, debounce_ms)?;
+        }
+        Commands::Run { name, args } => {
+            let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
+            cli_rag::commands::lua_integration::run_overlay_command(
+                &cfg,
+                &cfg_path,
+                &cli.format,
+                name,
+                &args,
+            )?;
+        }
+        Commands::Todo { command } => {
+            let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
+            cli_rag::commands::gtd_cmd::run_todo(&cfg, &cfg_path, &cli.format, command)?;
+        }
+        Commands::Kanban { command } => {
+            let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
+            cli_rag::commands::gtd_cmd::run_kanban(&cfg, &cfg_path, &cli.format, command)?;
+        }
+        Commands::Backlinks { command } => {
+            let (cfg, _cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
+            cli_rag::commands::backlinks_cmd::run(&cfg, &cli.format, command)?;
+        }
+        Commands::ReviewQueue { schema } => {
+            let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
+            cli_rag::commands::review_queue::run(&cfg, &cfg_path, &cli.format, schema)?;
+        }
         Commands::Validate(args) => {
             let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
             cli_rag::commands::validate_cmd::run(
@@ -161,6 +245,7 @@ fn main() -> Result<()> {
                 &args.format,
                 args.dry_run,
                 args.full_rescan,
+                args.fix,
             )?;
         }
         Commands::Ai { command } => match command {
@@ -210,37 +295,11 @@ fn main() -> Result<()> {
             AiCommands::Index { command } => match command {
                 AiIndexCommands::Plan(args) => {
                     let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
-                    let AiIndexPlanArgs {
-                        edges,
-                        min_cluster_size,
-                        schema,
-                        output,
-                    } = args;
-                    cli_rag::commands::ai_index_plan::run(
-                        &cfg,
-                        &cfg_path,
-                        edges,
-                        min_cluster_size,
-                        schema,
-                        output,
-                    )?;
+                    cli_rag::commands::ai_index_plan::run(&cfg, &cfg_path, args)?;
                 }
                 AiIndexCommands::Apply(args) => {
                     let (cfg, cfg_path) = load_config(&cli.config, &cli.base, cli.no_lua)?;
-                    let AiIndexApplyArgs {
-                        from,
-                        write_cache,
-                        write_frontmatter,
-                        dry_run,
-                    } = args;
-                    cli_rag::commands::ai_index_apply::run(
-                        &cfg,
-                        &cfg_path,
-                        from,
-                        write_cache,
-                        write_frontmatter,
-                        dry_run,
-                    )?;
+                    cli_rag::commands::ai_index_apply::run(&cfg, &cfg_path, args)?;
                 }
             },
         },
@@ -0,0 +1,168 @@
+use clap::{ArgGroup, Args, Subcommand, ValueEnum};
+
+#[derive(Subcommand, Debug)]
+pub enum AiCommands {
+    /// AI authoring helpers for managed drafts (start/submit/cancel/list)
+    New {
+        #[command(subcommand)]
+        command: AiNewCommands,
+    },
+    /// AI index workflows (plan/apply)
+    Index {
+        #[command(subcommand)]
+        command: AiIndexCommands,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AiNewCommands {
+    /// Start an AI draft, reserving ID/filename and returning constraints
+    Start(AiNewStartArgs),
+    /// Submit an AI draft with rendered sections/frontmatter to finalize the note
+    Submit(AiNewSubmitArgs),
+    /// Cancel an AI draft and release its reservation
+    Cancel(AiNewCancelArgs),
+    /// List active drafts (optionally filtering by staleness)
+    List(AiNewListArgs),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AiIndexCommands {
+    /// Compute AI index plan over the unified graph and write JSON
+    Plan(AiIndexPlanArgs),
+    /// Apply an AI index plan: write cache and optionally add tags
+    Apply(AiIndexApplyArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct AiNewStartArgs {
+    /// Schema name to use (e.g., ADR, IMP)
+    #[arg(long)]
+    pub schema: String,
+    /// Optional title to seed template and filename rendering
+    #[arg(long)]
+    pub title: Option<String>,
+    /// Optional explicit ID override; otherwise engine assigns via schema rules
+    #[arg(long)]
+    pub id: Option<String>,
+}
+
+#[derive(Args, Debug)]
+#[command(group = ArgGroup::new("submit_input").args(["stdin", "sections", "from_file"]).required(true))]
+pub struct AiNewSubmitArgs {
+    /// Draft identifier returned by `ai new start`
+    #[arg(long)]
+    pub draft: String,
+    /// Read structured JSON payload from stdin
+    #[arg(long, default_value_t = false)]
+    pub stdin: bool,
+    /// Path to structured JSON payload file ({frontmatter, sections})
+    #[arg(long, value_name = "PATH")]
+    pub sections: Option<std::path::PathBuf>,
+    /// Path to Markdown note to parse into sections
+    #[arg(long = "from-file", value_name = "PATH")]
+    pub from_file: Option<std::path::PathBuf>,
+    /// Allow writing even if line-count constraints fail (marks needs_attention)
+    #[arg(long, default_value_t = false)]
+    pub allow_oversize: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct AiNewCancelArgs {
+    /// Optional draft identifier to cancel (omit to auto-select when only one draft exists)
+    #[arg(long)]
+    pub draft: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct AiNewListArgs {
+    /// Optional staleness filter in days (show only drafts older than N days)
+    #[arg(long, value_name = "DAYS")]
+    pub stale_days: Option<u64>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClusterAlgorithm {
+    /// Connected components (the default)
+    Components,
+    /// Weighted label propagation
+    LabelPropagation,
+    /// Louvain modularity optimisation
+    Louvain,
+    /// Louvain with connected-community refinement
+    Leiden,
+    /// Connected parts of the k-cores
+    Kcore,
+}
+
+impl ClusterAlgorithm {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ClusterAlgorithm::Components => "components",
+            ClusterAlgorithm::LabelPropagation => "label-propagation",
+            ClusterAlgorithm::Louvain => "louvain",
+            ClusterAlgorithm::Leiden => "leiden",
+            ClusterAlgorithm::Kcore => "kcore",
+        }
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct AiIndexPlanArgs {
+    /// Comma-separated list of edge kinds to include (default: depends_on,mentions)
+    #[arg(long, value_delimiter = ',')]
+    pub edges: Option<Vec<String>>,
+    /// Minimum cluster size to include (default: 3)
+    #[arg(long, default_value_t = 3)]
+    pub min_cluster_size: usize,
+    /// Clustering algorithm
+    #[arg(long, value_enum, default_value_t = ClusterAlgorithm::Components)]
+    pub algorithm: ClusterAlgorithm,
+    /// Modularity resolution for louvain/leiden; higher values give smaller clusters
+    #[arg(long, default_value_t = 1.0)]
+    pub resolution: f64,
+    /// Smallest core number kept by kcore
+    #[arg(long, default_value_t = 2)]
+    pub min_core: usize,
+    /// Emit every level of the hierarchy (louvain/leiden passes, kcore depths) with parent links
+    #[arg(long, default_value_t = false)]
+    pub hierarchical: bool,
+    /// Optional schema filter (only include nodes of this schema)
+    #[arg(long)]
+    pub schema: Option<String>,
+    /// Output path for the plan JSON
+    #[arg(long, value_name = "PATH")]
+    pub output: std::path::PathBuf,
+    /// Leave label, summary and tags empty instead of proposing them from TF-IDF terms
+    #[arg(long, default_value_t = false)]
+    pub no_labels: bool,
+    /// Shell command that refines labels: reads the clusters as JSON on stdin and
+    /// prints `{"clusters": [{clusterId, label?, summary?, tags?}]}`
+    #[arg(long, value_name = "CMD")]
+    pub label_command: Option<String>,
+    /// AI index cache to match clusters against (default: .cli-rag/cache/ai-index.json)
+    #[arg(long, value_name = "PATH")]
+    pub previous: Option<std::path::PathBuf>,
+    /// Minimum Jaccard overlap for a cluster to continue a previous one
+    #[arg(long, default_value_t = 0.3)]
+    pub match_threshold: f64,
+}
+
+#[derive(Args, Debug)]
+pub struct AiIndexApplyArgs {
+    /// Path to the plan JSON generated by ai index plan
+    #[arg(long, value_name = "PATH")]
+    pub from: std::path::PathBuf,
+    /// Write authoritative cache (.cli-rag/cache/ai-index.json)
+    #[arg(long, default_value_t = true)]
+    pub write_cache: bool,
+    /// Reconcile the namespaced cluster/... tags in note frontmatter
+    #[arg(long, default_value_t = false)]
+    pub write_frontmatter: bool,
+    /// Dry run; do not write files
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+    /// Replace the whole cache instead of merging the plan into it
+    #[arg(long, default_value_t = false)]
+    pub replace: bool,
+}
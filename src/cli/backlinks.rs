@@ -0,0 +1,14 @@
+use clap::{Args, Subcommand};
+
+#[derive(Subcommand, Debug)]
+pub enum BacklinksCommands {
+    /// Rewrite the generated "Referenced by" block of notes whose schema enables backlinks
+    Sync(BacklinksSyncArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct BacklinksSyncArgs {
+    /// Show the changes as diffs without writing files
+    #[arg(long, default_value_t = false)]
+    pub dry_run: bool,
+}
@@ -0,0 +1,30 @@
+use clap::{Args, Subcommand};
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Show effective config keys with their source layer (file, import, env, cli, default, overlay)
+    Explain(ConfigExplainArgs),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SchemaCommands {
+    /// Export JSON Schemas for the config file and each note schema's front matter
+    Export(SchemaExportArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ConfigExplainArgs {
+    /// Optional key or key prefix to explain (e.g., scan.filepaths, schemas.ADR)
+    #[arg(value_name = "KEY")]
+    pub key: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct SchemaExportArgs {
+    /// Only export the front matter schema for this note schema (e.g., ADR)
+    #[arg(long, value_name = "NAME")]
+    pub name: Option<String>,
+    /// Write one `.schema.json` file per schema into this directory instead of printing
+    #[arg(long, value_name = "DIR")]
+    pub out: Option<std::path::PathBuf>,
+}
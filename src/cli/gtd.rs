@@ -0,0 +1,45 @@
+use clap::{Args, Subcommand};
+
+#[derive(Subcommand, Debug)]
+pub enum TodoCommands {
+    /// Mark a todo done (`done=<date>` on GTD boxes, `[x]` on checkboxes)
+    Done(TodoDoneArgs),
+    /// Set or clear attributes on a `[@TODO:...]` box
+    Set(TodoSetArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct TodoDoneArgs {
+    /// Todo id as emitted by `search` (`<NOTE-ID>#<hash>`)
+    #[arg(value_name = "TODO_ID")]
+    pub id: String,
+    /// Completion date (default: today)
+    #[arg(long, value_name = "YYYY-MM-DD")]
+    pub date: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct TodoSetArgs {
+    /// Todo id as emitted by `search` (`<NOTE-ID>#<hash>`)
+    #[arg(value_name = "TODO_ID")]
+    pub id: String,
+    /// Attributes as `key=value`; `key=` removes the attribute
+    #[arg(value_name = "KEY=VALUE", required = true)]
+    pub attrs: Vec<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum KanbanCommands {
+    /// Move a card to another column by rewriting `kanban_status`
+    Move(KanbanMoveArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct KanbanMoveArgs {
+    /// Note id, or the kanban item id emitted by `search`
+    #[arg(long)]
+    pub id: String,
+    /// Target status
+    #[arg(long)]
+    pub to: String,
+}
@@ -0,0 +1,17 @@
+use clap::{Args, Subcommand};
+
+#[derive(Subcommand, Debug)]
+pub enum LuaCommands {
+    /// Run `*_spec.lua` files against the loaded overlays
+    Test(LuaTestArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct LuaTestArgs {
+    /// Spec files or directories to search (default: the project root)
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<std::path::PathBuf>,
+    /// Only run tests whose full name contains this text
+    #[arg(long, value_name = "TEXT")]
+    pub filter: Option<String>,
+}
@@ -1,6 +1,18 @@
-use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
+mod ai;
+mod backlinks;
+mod config;
+mod gtd;
+mod lua;
+
+pub use ai::*;
+pub use backlinks::*;
+pub use config::*;
+pub use gtd::*;
+pub use lua::*;
+
 #[derive(ValueEnum, Debug, Clone, Copy)]
 pub enum OutputFormat {
     Plain,
@@ -85,8 +97,24 @@ pub enum Commands {
     },
     /// Inspect resolved config, cache entries, and capabilities
     Info {},
+    /// Inspect the effective configuration and where each value came from
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Export JSON Schemas for editor validation of config and note front matter
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommands,
+    },
+    /// Test Lua overlays with `*_spec.lua` files
+    Lua {
+        #[command(subcommand)]
+        command: LuaCommands,
+    },
     /// Search notes with fuzzy text matching and filters
     Search {
+        /// Query expression, e.g. `schema:ADR status:(accepted OR proposed) -tag:old "rate limit"`
         #[arg(long, short = 'q')]
         query: String,
         /// Filter by item kind (note,todo,kanban)
@@ -101,6 +129,9 @@ pub enum Commands {
         /// Filter by tag(s)
         #[arg(long, value_delimiter = ',')]
         tag: Option<Vec<String>>,
+        /// Render results with a Lua overlay renderer (`renderers.search.<NAME>`)
+        #[arg(long, value_name = "NAME")]
+        render: Option<String>,
     },
     /// Retrieve a note with its neighborhood for AI workflows
     Get {
@@ -117,6 +148,9 @@ pub enum Commands {
         /// Max neighbors to include (JSON)
         #[arg(long, value_name = "N")]
         max_fanout: Option<usize>,
+        /// Return only the section under this heading (text or anchor)
+        #[arg(long, value_name = "HEADING")]
+        section: Option<String>,
     },
     /// Explore dependency clusters around a given note
     Cluster {
@@ -147,9 +181,57 @@ pub enum Commands {
         /// Output format (json is the machine/AI surface)
         #[arg(long = "graph-format", value_enum, default_value_t = GraphFormat::Mermaid, help = "Output format (json is the machine/AI surface)")]
         graph_format: GraphFormat,
+        /// Render the graph with a Lua overlay renderer (`renderers.graph.<NAME>`)
+        #[arg(long, value_name = "NAME")]
+        render: Option<String>,
     },
     /// Build or print the unified index and run validation checks
     Validate(ValidateArgs),
+    /// List notes with passed review dates, expired dates, or stale status
+    ReviewQueue {
+        /// Filter by schema name(s)
+        #[arg(long, value_delimiter = ',')]
+        schema: Option<Vec<String>>,
+    },
+
+    /// Show dated todos and kanban cards grouped by day (overdue items land on today)
+    Agenda {
+        /// Number of days to show, starting today
+        #[arg(long, default_value_t = 7)]
+        days: u32,
+        /// Only show overdue items
+        #[arg(long, default_value_t = false)]
+        overdue: bool,
+    },
+
+    /// Complete or edit todo items found by `search`
+    Todo {
+        #[command(subcommand)]
+        command: TodoCommands,
+    },
+    /// Move kanban cards between statuses
+    Kanban {
+        #[command(subcommand)]
+        command: KanbanCommands,
+    },
+    /// Maintain generated "Referenced by" blocks
+    Backlinks {
+        #[command(subcommand)]
+        command: BacklinksCommands,
+    },
+    /// Run a command registered by a Lua overlay (`overlay.commands`); lists them without a name
+    Run {
+        name: Option<String>,
+        /// Arguments passed to the command (`--key value`, `--flag`, positionals)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+
+    /// Run a saved search from a `[[view]]` config block; lists views without a name
+    View {
+        #[arg(value_name = "NAME")]
+        name: Option<String>,
+    },
 
     /// Watch bases and incrementally validate + update indexes on changes
     Watch {
@@ -167,6 +249,13 @@ pub enum Commands {
         json: bool,
     },
 
+    /// Serve `search`/`get` from memory over a Unix socket, kept fresh by watching bases
+    Daemon {
+        /// Debounce milliseconds for coalescing FS events
+        #[arg(long, default_value_t = 400)]
+        debounce_ms: u64,
+    },
+
     /// Generate shell completions (bash|zsh|fish)
     Completions {
         #[arg(value_name = "SHELL")]
@@ -179,119 +268,6 @@ pub enum Commands {
     },
 }
 
-#[derive(Subcommand, Debug)]
-pub enum AiCommands {
-    /// AI authoring helpers for managed drafts (start/submit/cancel/list)
-    New {
-        #[command(subcommand)]
-        command: AiNewCommands,
-    },
-    /// AI index workflows (plan/apply)
-    Index {
-        #[command(subcommand)]
-        command: AiIndexCommands,
-    },
-}
-
-#[derive(Subcommand, Debug)]
-pub enum AiNewCommands {
-    /// Start an AI draft, reserving ID/filename and returning constraints
-    Start(AiNewStartArgs),
-    /// Submit an AI draft with rendered sections/frontmatter to finalize the note
-    Submit(AiNewSubmitArgs),
-    /// Cancel an AI draft and release its reservation
-    Cancel(AiNewCancelArgs),
-    /// List active drafts (optionally filtering by staleness)
-    List(AiNewListArgs),
-}
-
-#[derive(Subcommand, Debug)]
-pub enum AiIndexCommands {
-    /// Compute AI index plan over the unified graph and write JSON
-    Plan(AiIndexPlanArgs),
-    /// Apply an AI index plan: write cache and optionally add tags
-    Apply(AiIndexApplyArgs),
-}
-
-#[derive(Args, Debug)]
-pub struct AiNewStartArgs {
-    /// Schema name to use (e.g., ADR, IMP)
-    #[arg(long)]
-    pub schema: String,
-    /// Optional title to seed template and filename rendering
-    #[arg(long)]
-    pub title: Option<String>,
-    /// Optional explicit ID override; otherwise engine assigns via schema rules
-    #[arg(long)]
-    pub id: Option<String>,
-}
-
-#[derive(Args, Debug)]
-#[command(group = ArgGroup::new("submit_input").args(["stdin", "sections", "from_file"]).required(true))]
-pub struct AiNewSubmitArgs {
-    /// Draft identifier returned by `ai new start`
-    #[arg(long)]
-    pub draft: String,
-    /// Read structured JSON payload from stdin
-    #[arg(long, default_value_t = false)]
-    pub stdin: bool,
-    /// Path to structured JSON payload file ({frontmatter, sections})
-    #[arg(long, value_name = "PATH")]
-    pub sections: Option<std::path::PathBuf>,
-    /// Path to Markdown note to parse into sections
-    #[arg(long = "from-file", value_name = "PATH")]
-    pub from_file: Option<std::path::PathBuf>,
-    /// Allow writing even if line-count constraints fail (marks needs_attention)
-    #[arg(long, default_value_t = false)]
-    pub allow_oversize: bool,
-}
-
-#[derive(Args, Debug)]
-pub struct AiNewCancelArgs {
-    /// Optional draft identifier to cancel (omit to auto-select when only one draft exists)
-    #[arg(long)]
-    pub draft: Option<String>,
-}
-
-#[derive(Args, Debug)]
-pub struct AiNewListArgs {
-    /// Optional staleness filter in days (show only drafts older than N days)
-    #[arg(long, value_name = "DAYS")]
-    pub stale_days: Option<u64>,
-}
-
-#[derive(Args, Debug)]
-pub struct AiIndexPlanArgs {
-    /// Comma-separated list of edge kinds to include (default: depends_on,mentions)
-    #[arg(long, value_delimiter = ',')]
-    pub edges: Option<Vec<String>>,
-    /// Minimum cluster size to include (default: 3)
-    #[arg(long, default_value_t = 3)]
-    pub min_cluster_size: usize,
-    /// Optional schema filter (only include nodes of this schema)
-    #[arg(long)]
-    pub schema: Option<String>,
-    /// Output path for the plan JSON
-    #[arg(long, value_name = "PATH")]
-    pub output: std::path::PathBuf,
-}
-
-#[derive(Args, Debug)]
-pub struct AiIndexApplyArgs {
-    /// Path to the plan JSON generated by ai index plan
-    #[arg(long, value_name = "PATH")]
-    pub from: std::path::PathBuf,
-    /// Write authoritative cache (.cli-rag/cache/ai-index.json)
-    #[arg(long, default_value_t = true)]
-    pub write_cache: bool,
-    /// Write tags to frontmatter (additive)
-    #[arg(long, default_value_t = false)]
-    pub write_frontmatter: bool,
-    /// Dry run; do not write files
-    #[arg(long, default_value_t = false)]
-    pub dry_run: bool,
-}
-
 #[derive(Args, Debug)]
 pub struct ValidateArgs {
     #[arg(long, value_enum, default_value_t = OutputFormat::Plain)]
@@ -302,4 +278,7 @@ pub struct ValidateArgs {
     /// Force full rescan instead of incremental
     #[arg(long, default_value_t = false)]
     pub full_rescan: bool,
+    /// Apply safe front matter fixes in place and report what remains (honors --dry-run)
+    #[arg(long, default_value_t = false)]
+    pub fix: bool,
 }
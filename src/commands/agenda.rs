@@ -0,0 +1,256 @@
+//! `cli-rag agenda`: GTD items grouped by date, org-mode style.
+//!
+//! Items are `[@TODO:...]` boxes (`due=` is a deadline, `scheduled=` a start
+//! date, `repeat=` a recurrence, `rank=` the priority) and kanban cards with a
+//! `due_date` front matter value. An item is placed on its scheduled date, else
+//! its deadline; unfinished items from past days are carried to today as overdue.
+
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::cli::OutputFormat;
+use crate::commands::output::{print_json, print_ndjson_iter};
+use crate::commands::search_gtd::{
+    fnv1a_64, hex_u64, map_rank_to_priority_score, parse_gtd_box, parse_gtd_date, Repeat,
+};
+use crate::config::Config;
+use crate::discovery::docs_with_source;
+use crate::model::AdrDoc;
+
+struct Entry {
+    kind: &'static str,
+    id: String,
+    note_id: String,
+    path: String,
+    line: Option<usize>,
+    text: String,
+    priority: Option<i64>,
+    deadline: Option<NaiveDate>,
+    scheduled: Option<NaiveDate>,
+    repeat: Option<(String, Repeat)>,
+    kanban_status: Option<String>,
+}
+
+fn todo_entries(doc: &AdrDoc, note_id: &str, out: &mut Vec<Entry>) {
+    let Ok(content) = std::fs::read_to_string(&doc.file) else {
+        return;
+    };
+    let path = doc.display_path();
+    for (i, line) in content.lines().enumerate() {
+        let Some(g) = parse_gtd_box(line) else {
+            continue;
+        };
+        // `done` (e.g. `done=2025-01-03`) marks a finished item.
+        if !g.cmd.eq_ignore_ascii_case("todo") || g.attrs.contains_key("done") {
+            continue;
+        }
+        let key = format!("{}:{}:{}", path, i + 1, line.trim());
+        out.push(Entry {
+            kind: "todo",
+            id: format!("{}#{}", note_id, hex_u64(fnv1a_64(&key))),
+            note_id: note_id.to_string(),
+            path: path.clone(),
+            line: Some(i + 1),
+            text: g.remainder.clone(),
+            priority: g
+                .attrs
+                .get("rank")
+                .and_then(|r| map_rank_to_priority_score(r)),
+            deadline: g.attrs.get("due").and_then(|d| parse_gtd_date(d)),
+            scheduled: g.attrs.get("scheduled").and_then(|d| parse_gtd_date(d)),
+            repeat: g
+                .attrs
+                .get("repeat")
+                .and_then(|r| Repeat::parse(r).map(|p| (r.clone(), p))),
+            kanban_status: None,
+        });
+    }
+}
+
+fn kanban_entry(doc: &AdrDoc, note_id: &str) -> Option<Entry> {
+    let fm_str = |k: &str| doc.fm.get(k).and_then(|v| v.as_str());
+    let status = fm_str("kanban_status")?;
+    if status.eq_ignore_ascii_case("done") {
+        return None;
+    }
+    let path = doc.display_path();
+    let key = format!("{}:{}:{}", path, note_id, status);
+    Some(Entry {
+        kind: "kanban",
+        id: format!("{}#{}", note_id, hex_u64(fnv1a_64(&key))),
+        note_id: note_id.to_string(),
+        path,
+        line: None,
+        text: doc.title.clone(),
+        priority: fm_str("rank").and_then(map_rank_to_priority_score),
+        deadline: fm_str("due_date").and_then(parse_gtd_date),
+        scheduled: None,
+        repeat: None,
+        kanban_status: Some(status.to_string()),
+    })
+}
+
+fn item_json(e: &Entry, date: NaiveDate, days_overdue: i64) -> Value {
+    let mut v = json!({
+        "kind": e.kind,
+        "id": e.id,
+        "noteId": e.note_id,
+        "path": e.path,
+        "text": e.text,
+        "type": if e.scheduled.is_some() { "scheduled" } else { "deadline" },
+        "date": date.to_string(),
+        "daysOverdue": days_overdue,
+        "priorityScore": e.priority,
+        "dueDate": e.deadline.map(|d| d.to_string()),
+        "scheduledDate": e.scheduled.map(|d| d.to_string()),
+        "repeat": e.repeat.as_ref().map(|(raw, _)| raw.clone()),
+    });
+    if let Some(line) = e.line {
+        v["line"] = json!(line);
+    }
+    if let Some(status) = &e.kanban_status {
+        v["kanbanStatus"] = json!(status);
+    }
+    v
+}
+
+/// Group entries by day over `[today, end)`; overdue occurrences land on
+/// today. With `overdue_only`, only those are kept.
+fn agenda_groups(
+    entries: &[Entry],
+    today: NaiveDate,
+    end: NaiveDate,
+    overdue_only: bool,
+) -> BTreeMap<NaiveDate, Vec<Value>> {
+    let mut groups: BTreeMap<NaiveDate, Vec<(Option<i64>, i64, Value)>> = BTreeMap::new();
+    for e in entries {
+        let Some(base) = e.scheduled.or(e.deadline) else {
+            continue;
+        };
+        if base < today {
+            let overdue = (today - base).num_days();
+            let v = item_json(e, base, overdue);
+            groups
+                .entry(today)
+                .or_default()
+                .push((e.priority, overdue, v));
+        }
+        if overdue_only {
+            continue;
+        }
+        // Occurrences inside the window, starting at the first one on or after
+        // today; a non-repeating item has only `base`.
+        let mut k = match e.repeat {
+            Some((_, r)) => match r.first_on_or_after(base, today) {
+                Some(k) => k,
+                None => continue,
+            },
+            None => 0,
+        };
+        while let Some(date) = match e.repeat {
+            Some((_, r)) => r.nth(base, k),
+            None if k == 0 => Some(base),
+            None => None,
+        } {
+            if date >= end {
+                break;
+            }
+            // An overdue item already shows on today; its other occurrences are still listed.
+            if date > today || (date == today && base >= today) {
+                let v = item_json(e, date, 0);
+                groups.entry(date).or_default().push((e.priority, 0, v));
+            }
+            k += 1;
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(date, mut items)| {
+            // Priority first (unranked last), then most overdue, then location.
+            items.sort_by(|a, b| {
+                b.0.unwrap_or(0)
+                    .cmp(&a.0.unwrap_or(0))
+                    .then(b.1.cmp(&a.1))
+                    .then_with(|| a.2["path"].as_str().cmp(&b.2["path"].as_str()))
+                    .then_with(|| a.2["line"].as_u64().cmp(&b.2["line"].as_u64()))
+            });
+            (date, items.into_iter().map(|(_, _, v)| v).collect())
+        })
+        .collect()
+}
+
+fn plain_line(v: &Value) -> String {
+    let mut when = v["type"].as_str().unwrap_or("").to_string();
+    if let Some(n) = v["daysOverdue"].as_i64().filter(|n| *n > 0) {
+        when = format!("{} -{}d", when, n);
+    }
+    let prio = v["priorityScore"]
+        .as_i64()
+        .map(|p| format!("P{}", p))
+        .unwrap_or_else(|| "-".into());
+    let at = match v["line"].as_u64() {
+        Some(line) => format!("{}:{}", v["path"].as_str().unwrap_or(""), line),
+        None => v["path"].as_str().unwrap_or("").to_string(),
+    };
+    format!(
+        "  {:<14} {:<3} {:<10} {}  ({})",
+        when,
+        prio,
+        v["noteId"].as_str().unwrap_or(""),
+        v["text"].as_str().unwrap_or(""),
+        at
+    )
+}
+
+pub fn run(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    format: &OutputFormat,
+    days: u32,
+    overdue_only: bool,
+) -> Result<()> {
+    let (docs, used_unified) = docs_with_source(cfg, cfg_path)?;
+    if !used_unified {
+        eprintln!("Note: unified index not found; falling back to per-base/scan. Consider `cli-rag validate`.");
+    }
+    let mut entries = Vec::new();
+    for d in &docs {
+        let Some(id) = d.id.as_deref() else { continue };
+        todo_entries(d, id, &mut entries);
+        entries.extend(kanban_entry(d, id));
+    }
+    let today = chrono::Local::now().date_naive();
+    let end = today
+        .checked_add_days(chrono::Days::new(days.max(1) as u64))
+        .ok_or_else(|| anyhow!("--days {} reaches past the last supported date", days))?;
+    let groups = agenda_groups(&entries, today, end, overdue_only);
+    match format {
+        OutputFormat::Json | OutputFormat::Ai => {
+            let groups: Vec<Value> = groups
+                .into_iter()
+                .map(|(date, items)| json!({"date": date.to_string(), "items": items}))
+                .collect();
+            print_json(&json!({
+                "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+                "today": today.to_string(),
+                "days": days,
+                "groups": groups,
+            }))?;
+        }
+        OutputFormat::Ndjson => {
+            print_ndjson_iter::<Value, _>(groups.into_values().flatten())?;
+        }
+        OutputFormat::Plain => {
+            for (date, items) in &groups {
+                println!("{}", date.format("%Y-%m-%d %a"));
+                for v in items {
+                    println!("{}", plain_line(v));
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! Merging a plan into the AI index cache. Clusters the plan does not touch
+//! stay in the cache; the plan's clusters replace the cached ones they were
+//! matched to (stable, split, merged) and the ones it reports as dissolved.
+
+use serde_json::{json, Map, Value};
+use std::collections::BTreeSet;
+
+fn strings(v: Option<&Value>) -> Vec<String> {
+    v.and_then(|a| a.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|x| x.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Where the label of a planned cluster came from. A TF-IDF label that was
+/// edited in the plan (it no longer equals `proposed`) counts as `manual`.
+fn label_source(c: &Value) -> &'static str {
+    let label = c.get("label").and_then(|v| v.as_str()).unwrap_or("");
+    match c.get("labelSource").and_then(|v| v.as_str()) {
+        Some("command") => "command",
+        Some("tfidf") => {
+            let proposed = c.get("proposed");
+            let same = |key: &str| {
+                proposed.and_then(|p| p.get(key)).and_then(|v| v.as_str())
+                    == c.get(key).and_then(|v| v.as_str())
+            };
+            if proposed.is_none() || (same("label") && same("summary")) {
+                "tfidf"
+            } else {
+                "manual"
+            }
+        }
+        _ if label.is_empty() => "none",
+        _ => "manual",
+    }
+}
+
+/// The cache entry for a planned cluster.
+pub fn entry(c: &Value) -> Value {
+    let text = |key: &str| c.get(key).and_then(|v| v.as_str()).unwrap_or("");
+    let mut obj = Map::new();
+    obj.insert("clusterId".into(), json!(text("clusterId")));
+    obj.insert("label".into(), json!(text("label")));
+    obj.insert("summary".into(), json!(text("summary")));
+    obj.insert("members".into(), json!(strings(c.get("members"))));
+    obj.insert("tags".into(), json!(strings(c.get("tags"))));
+    obj.insert("labelSource".into(), json!(label_source(c)));
+    for key in ["level", "parentId"] {
+        if let Some(v) = c.get(key) {
+            obj.insert(key.into(), v.clone());
+        }
+    }
+    Value::Object(obj)
+}
+
+pub struct Merged {
+    pub clusters: Vec<Value>,
+    pub retained: usize,
+    pub replaced: usize,
+}
+
+/// Cache clusters after applying `plan` on top of `old`. With `replace` the
+/// old cache is dropped entirely, as before clusters had stable identities.
+pub fn merge(plan: &Value, old: Vec<Value>, replace: bool) -> Merged {
+    let planned: Vec<&Value> = plan
+        .get("clusters")
+        .and_then(|v| v.as_array())
+        .map(|a| a.iter().collect())
+        .unwrap_or_default();
+    let id = |v: &Value| {
+        v.get("clusterId")
+            .and_then(|s| s.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    let mut superseded: BTreeSet<String> = planned.iter().map(|c| id(c)).collect();
+    let mut gone: BTreeSet<String> = BTreeSet::new();
+    for d in plan
+        .get("dissolved")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+    {
+        gone.insert(id(d));
+    }
+    for c in &planned {
+        if matches!(
+            c.get("status").and_then(|s| s.as_str()),
+            Some("split" | "merged")
+        ) {
+            for p in c
+                .get("previous")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+            {
+                gone.insert(id(p));
+            }
+        }
+    }
+    superseded.extend(gone);
+
+    let old_count = old.len();
+    let kept: Vec<Value> = if replace {
+        Vec::new()
+    } else {
+        old.into_iter()
+            .filter(|c| !superseded.contains(&id(c)))
+            .collect()
+    };
+    let retained = kept.len();
+    let mut clusters = kept;
+    clusters.extend(planned.iter().map(|c| entry(c)));
+    Merged {
+        clusters,
+        retained,
+        replaced: old_count - retained,
+    }
+}
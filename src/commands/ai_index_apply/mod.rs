@@ -0,0 +1,129 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::path::PathBuf;
+
+use crate::cli::AiIndexApplyArgs;
+use crate::commands::ai_index_plan::{load_previous, CACHE_RELATIVE};
+use crate::config::Config;
+use crate::discovery::unified::load_docs_unified;
+use crate::util::{normalize_display_path, unified_diff};
+
+mod cache;
+mod tags;
+
+pub use tags::TAG_PREFIX;
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    let out = hasher.finalize();
+    let mut s = String::with_capacity(out.len() * 2);
+    for b in out {
+        s.push_str(&format!("{:02x}", b));
+    }
+    s
+}
+
+pub fn run(cfg: &Config, cfg_path: &Option<PathBuf>, args: AiIndexApplyArgs) -> Result<()> {
+    let AiIndexApplyArgs {
+        from: plan_path,
+        write_cache,
+        write_frontmatter,
+        dry_run,
+        replace,
+    } = args;
+    let cfg_dir = cfg_path.as_ref().and_then(|p| p.parent()).ok_or_else(|| {
+        anyhow!("Cannot locate config directory; pass --config and run `validate` first")
+    })?;
+
+    // Load plan
+    let plan_data = std::fs::read(&plan_path)
+        .with_context(|| format!("reading plan {}", plan_path.display()))?;
+    let plan: Value = serde_json::from_slice(&plan_data)
+        .with_context(|| format!("parsing plan {}", plan_path.display()))?;
+
+    // Check sourceIndexHash match by hashing current unified index
+    let unified_path = cfg_dir.join(&cfg.index_relative);
+    if !unified_path.exists() {
+        return Err(anyhow!(
+            "Unified index not found at {}. Run `cli-rag validate` first.",
+            unified_path.display()
+        ));
+    }
+    let idx_bytes = std::fs::read(&unified_path)
+        .with_context(|| format!("reading unified index {}", unified_path.display()))?;
+    let idx_hash = format!("sha256:{}", sha256_hex(&idx_bytes));
+    let plan_hash = plan
+        .get("sourceIndexHash")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    if !plan_hash.is_empty() && plan_hash != idx_hash {
+        eprintln!(
+            "Plan sourceIndexHash does not match current index (plan: {}, current: {})",
+            plan_hash, idx_hash
+        );
+        // Exit code 2 per conventions (validation/contract failure)
+        std::process::exit(2);
+    }
+
+    // Build cache JSON: merge into the existing cache unless --replace
+    let cache_path = cfg_dir.join(CACHE_RELATIVE);
+    let merged = cache::merge(&plan, load_previous(&cache_path), replace);
+    let clusters_applied = plan
+        .get("clusters")
+        .and_then(|v| v.as_array())
+        .map_or(0, |a| a.len());
+    let cache = serde_json::json!({
+        "version": 1,
+        "clusters": &merged.clusters,
+    });
+
+    // Write cache if requested
+    if write_cache && !dry_run {
+        if let Some(parent) = cache_path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        std::fs::write(&cache_path, serde_json::to_string_pretty(&cache)?)
+            .with_context(|| format!("writing cache {}", cache_path.display()))?;
+    }
+
+    // Frontmatter writes (optional): reconcile the namespaced cluster tags
+    let mut files: Vec<Value> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+    if write_frontmatter {
+        let docs = load_docs_unified(cfg, cfg_path)?.unwrap_or_default();
+        let wanted = tags::cluster_tags(&merged.clusters);
+        for edit in tags::plan_tag_edits(&docs, &wanted, &mut warnings)? {
+            let path = normalize_display_path(&edit.path);
+            let mut file = serde_json::json!({
+                "path": path,
+                "id": edit.id,
+                "added": edit.added,
+                "removed": edit.removed,
+            });
+            if dry_run {
+                file["diff"] = Value::String(unified_diff(&path, &edit.before, &edit.after));
+            } else {
+                std::fs::write(&edit.path, &edit.after)
+                    .with_context(|| format!("writing {}", edit.path.display()))?;
+            }
+            files.push(file);
+        }
+    }
+
+    // Emit apply report JSON
+    let report = serde_json::json!({
+        "ok": true,
+        "written": { "cache": write_cache, "frontmatter": write_frontmatter },
+        "clustersApplied": clusters_applied,
+        "clustersRetained": merged.retained,
+        "clustersReplaced": merged.replaced,
+        "membersTagged": files.len(),
+        "files": files,
+        "warnings": warnings,
+    });
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
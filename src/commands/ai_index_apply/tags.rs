@@ -0,0 +1,137 @@
+//! `--write-frontmatter`: keep the tags the AI index owns in note front matter
+//! in line with the cache. Those tags are namespaced (`cluster/<tag>`), so tags
+//! a note picked up from clusters it has since left can be told apart from
+//! hand-written ones and removed. Notes are only rewritten when their cluster
+//! tags change, and then only the `tags` entry of the front matter is replaced
+//! (see [`FrontMatterDoc::render`]); comments, other keys and TOML datetimes
+//! are left as written.
+
+use anyhow::{Context, Result};
+use heck::ToKebabCase;
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use crate::frontmatter::{string_list, FrontMatterDoc};
+use crate::model::AdrDoc;
+
+/// Prefix marking front matter tags written by `ai index apply`.
+pub const TAG_PREFIX: &str = "cluster/";
+
+/// Planned tag change for one note.
+#[derive(Debug, Clone)]
+pub struct TagEdit {
+    pub path: PathBuf,
+    pub id: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub before: String,
+    pub after: String,
+}
+
+/// Namespaced tags of every member of `clusters`. A cluster without tags
+/// contributes its label in kebab case.
+pub fn cluster_tags(clusters: &[Value]) -> HashMap<String, BTreeSet<String>> {
+    let mut out: HashMap<String, BTreeSet<String>> = HashMap::new();
+    for c in clusters {
+        let mut tags: Vec<String> = c
+            .get("tags")
+            .and_then(|v| v.as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|t| t.as_str())
+            .map(|t| t.to_kebab_case())
+            .filter(|t| !t.is_empty())
+            .collect();
+        if tags.is_empty() {
+            let label = c.get("label").and_then(|v| v.as_str()).unwrap_or("");
+            tags.extend(Some(label.to_kebab_case()).filter(|t| !t.is_empty()));
+        }
+        let members = c.get("members").and_then(|v| v.as_array());
+        for m in members.into_iter().flatten().filter_map(|m| m.as_str()) {
+            let entry = out.entry(m.to_string()).or_default();
+            entry.extend(tags.iter().map(|t| format!("{}{}", TAG_PREFIX, t)));
+        }
+    }
+    out
+}
+
+/// Tag edits bringing each of `docs` to exactly the cluster tags in `wanted`,
+/// keeping its other tags and their order. A missing `tags` key is created;
+/// notes that cannot be edited are reported in `warnings` and skipped.
+pub fn plan_tag_edits(
+    docs: &[AdrDoc],
+    wanted: &HashMap<String, BTreeSet<String>>,
+    warnings: &mut Vec<String>,
+) -> Result<Vec<TagEdit>> {
+    let none = BTreeSet::new();
+    let mut out = Vec::new();
+    for d in docs {
+        let Some(id) = d.id.as_deref() else {
+            continue;
+        };
+        let desired = wanted.get(id).unwrap_or(&none);
+        let has_cluster_tags = d.tags.iter().any(|t| t.starts_with(TAG_PREFIX));
+        if desired.is_empty() && !has_cluster_tags {
+            continue;
+        }
+        let before = std::fs::read_to_string(&d.file)
+            .with_context(|| format!("reading {}", d.file.display()))?;
+        let Some(mut fm) = FrontMatterDoc::parse(&before) else {
+            warnings.push(format!(
+                "{}: no readable front matter; skipping tags",
+                d.file.display()
+            ));
+            continue;
+        };
+        let current = match fm.get("tags") {
+            None | Some(serde_yaml::Value::Null) => Vec::new(),
+            Some(v @ (serde_yaml::Value::Sequence(_) | serde_yaml::Value::String(_))) => {
+                string_list(v)
+            }
+            Some(_) => {
+                warnings.push(format!(
+                    "{}: tags is not a list; skipping tags",
+                    d.file.display()
+                ));
+                continue;
+            }
+        };
+        let existing: BTreeSet<&String> = current
+            .iter()
+            .filter(|t| t.starts_with(TAG_PREFIX))
+            .collect();
+        if existing.iter().copied().eq(desired.iter()) {
+            continue;
+        }
+        let mut tags: Vec<String> = current
+            .iter()
+            .filter(|t| !t.starts_with(TAG_PREFIX))
+            .cloned()
+            .collect();
+        tags.extend(desired.iter().cloned());
+        fm.insert(
+            "tags",
+            serde_yaml::Value::Sequence(tags.into_iter().map(serde_yaml::Value::String).collect()),
+        );
+        let after = fm.render()?;
+        out.push(TagEdit {
+            path: d.file.clone(),
+            id: id.to_string(),
+            added: desired
+                .iter()
+                .filter(|t| !existing.contains(t))
+                .cloned()
+                .collect(),
+            removed: existing
+                .iter()
+                .filter(|t| !desired.contains(**t))
+                .map(|t| t.to_string())
+                .collect(),
+            before,
+            after,
+        });
+    }
+    out.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(out)
+}
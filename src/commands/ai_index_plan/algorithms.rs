@@ -0,0 +1,113 @@
+//! Clustering algorithms for `ai index plan`. All of them are deterministic:
+//! vertices are visited in id order and ties go to the smallest label, so the
+//! same index always yields the same clusters.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::graph::PlanGraph;
+
+/// Communities as sorted vertex lists, ordered by their smallest vertex.
+pub type Partition = Vec<Vec<usize>>;
+
+/// Group vertices by label; vertices without a label are left out.
+pub fn group(labels: &[Option<usize>]) -> Partition {
+    let mut by_label: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (v, l) in labels.iter().enumerate() {
+        if let Some(l) = l {
+            by_label.entry(*l).or_default().push(v);
+        }
+    }
+    let mut out: Partition = by_label.into_values().collect();
+    out.sort_by_key(|c| c[0]);
+    out
+}
+
+/// Connected components of the vertices accepted by `keep`.
+pub fn components_where(adj: &[BTreeMap<usize, f64>], keep: impl Fn(usize) -> bool) -> Partition {
+    let mut labels: Vec<Option<usize>> = vec![None; adj.len()];
+    for start in 0..adj.len() {
+        if labels[start].is_some() || !keep(start) {
+            continue;
+        }
+        labels[start] = Some(start);
+        let mut stack = vec![start];
+        while let Some(v) = stack.pop() {
+            for &n in adj[v].keys() {
+                if labels[n].is_none() && keep(n) {
+                    labels[n] = Some(start);
+                    stack.push(n);
+                }
+            }
+        }
+    }
+    group(&labels)
+}
+
+pub fn components(g: &PlanGraph) -> Partition {
+    components_where(&g.adj, |_| true)
+}
+
+/// Asynchronous label propagation: each vertex takes the label with the
+/// highest summed edge weight among its neighbours, keeping its own on ties
+/// (otherwise the smallest), until nothing changes.
+pub fn label_propagation(g: &PlanGraph) -> Partition {
+    let mut labels: Vec<usize> = (0..g.ids.len()).collect();
+    for _ in 0..100 {
+        let mut changed = false;
+        for v in 0..g.ids.len() {
+            let mut weights: BTreeMap<usize, f64> = BTreeMap::new();
+            for (n, w) in &g.adj[v] {
+                *weights.entry(labels[*n]).or_default() += w;
+            }
+            let Some(best) = weights.values().cloned().reduce(f64::max) else {
+                continue;
+            };
+            let own = weights.get(&labels[v]).copied().unwrap_or(0.0);
+            if own >= best {
+                continue;
+            }
+            let pick = weights.iter().find(|(_, w)| **w >= best).map(|(l, _)| *l);
+            if let Some(l) = pick {
+                labels[v] = l;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    group(&labels.into_iter().map(Some).collect::<Vec<_>>())
+}
+
+/// Core number of every vertex (unweighted degrees), by repeated peeling of
+/// the lowest-degree vertex.
+fn core_numbers(g: &PlanGraph) -> Vec<usize> {
+    let mut degree: Vec<usize> = g.adj.iter().map(|a| a.len()).collect();
+    let mut queue: BTreeSet<(usize, usize)> = degree.iter().cloned().zip(0..).collect();
+    let mut core = vec![0; g.ids.len()];
+    let mut removed = vec![false; g.ids.len()];
+    let mut k = 0;
+    while let Some((d, v)) = queue.pop_first() {
+        k = k.max(d);
+        core[v] = k;
+        removed[v] = true;
+        for &n in g.adj[v].keys() {
+            if !removed[n] {
+                queue.remove(&(degree[n], n));
+                degree[n] -= 1;
+                queue.insert((degree[n], n));
+            }
+        }
+    }
+    core
+}
+
+/// Connected parts of the k-cores for k = `min_core` up to the largest core,
+/// coarsest first; each level nests inside the previous one.
+pub fn kcore_levels(g: &PlanGraph, min_core: usize) -> Vec<Partition> {
+    let core = core_numbers(g);
+    let max = core.iter().copied().max().unwrap_or(0);
+    (min_core.max(1)..=max)
+        .map(|k| components_where(&g.adj, |v| core[v] >= k))
+        .collect()
+}
@@ -0,0 +1,94 @@
+//! Turn algorithm partitions into plan clusters: stable ids, parent links
+//! between hierarchy levels, representatives and density.
+
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+
+use super::algorithms::Partition;
+use super::graph::PlanGraph;
+
+/// `c_` plus the first 8 hex digits of the SHA-256 of the sorted member ids,
+/// so a cluster keeps its id as long as its members do.
+fn cluster_id(members: &[String]) -> String {
+    format!(
+        "c_{}",
+        &super::sha256_hex(members.join("\n").as_bytes())[..8]
+    )
+}
+
+fn cluster_json(g: &PlanGraph, vertices: &[usize], members: Vec<String>, id: String) -> Value {
+    let set: BTreeSet<usize> = vertices.iter().copied().collect();
+    // degree within the cluster; representatives are the top 2, ties by id
+    let mut deg: Vec<(usize, &String)> = vertices
+        .iter()
+        .map(|v| {
+            let d = g.adj[*v].keys().filter(|n| set.contains(n)).count();
+            (d, &g.ids[*v])
+        })
+        .collect();
+    let edge_count: usize = deg.iter().map(|(d, _)| d).sum::<usize>() / 2;
+    deg.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    let representatives: Vec<&String> = deg.iter().take(2).map(|(_, id)| *id).collect();
+    let n = members.len();
+    let density = if n < 2 {
+        0.0
+    } else {
+        edge_count as f64 / (n * (n - 1) / 2) as f64
+    };
+    json!({
+        "clusterId": id,
+        "members": members,
+        "representatives": representatives,
+        "metrics": { "size": n as i64, "density": density },
+        "label": "",
+        "summary": "",
+        "tags": [],
+        "labelSource": "none",
+    })
+}
+
+/// Plan clusters for `levels` (coarsest first). Clusters smaller than
+/// `min_size` are dropped. With `hierarchical`, each cluster records its
+/// `level` and `parentId`; a cluster with the same members as its parent
+/// adds nothing and is skipped.
+pub fn build_clusters(
+    g: &PlanGraph,
+    levels: &[Partition],
+    min_size: usize,
+    hierarchical: bool,
+) -> Vec<Value> {
+    let mut out = Vec::new();
+    // cluster id (and size) owning each vertex at the previous level
+    let mut owner: Vec<Option<(String, usize)>> = vec![None; g.ids.len()];
+    for (level, partition) in levels.iter().enumerate() {
+        let mut next: Vec<Option<(String, usize)>> = vec![None; g.ids.len()];
+        for vertices in partition {
+            let parent = owner[vertices[0]].clone();
+            if parent
+                .as_ref()
+                .is_some_and(|(_, size)| *size == vertices.len())
+            {
+                for v in vertices {
+                    next[*v] = parent.clone();
+                }
+                continue;
+            }
+            if vertices.len() < min_size {
+                continue;
+            }
+            let members: Vec<String> = vertices.iter().map(|v| g.ids[*v].clone()).collect();
+            let id = cluster_id(&members);
+            for v in vertices {
+                next[*v] = Some((id.clone(), vertices.len()));
+            }
+            let mut c = cluster_json(g, vertices, members, id);
+            if hierarchical {
+                c["level"] = json!(level);
+                c["parentId"] = json!(parent.map(|(id, _)| id));
+            }
+            out.push(c);
+        }
+        owner = next;
+    }
+    out
+}
@@ -0,0 +1,97 @@
+//! The undirected, weighted note graph that `ai index plan` clusters.
+
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::config::Config;
+
+pub struct PlanGraph {
+    /// Note ids, sorted; vertices are indices into this list.
+    pub ids: Vec<String>,
+    /// Neighbours with the summed weight of the edge kinds joining them.
+    pub adj: Vec<BTreeMap<usize, f64>>,
+}
+
+/// `weight` of `kind` in the edge policy of `schema`
+/// (`[schema.validate.edges.<kind>]`), 1.0 when unset.
+fn kind_weight(cfg: &Config, schema: Option<&String>, kind: &str) -> f64 {
+    schema
+        .and_then(|name| cfg.schema.iter().find(|s| &s.name == name))
+        .and_then(|s| s.validate.as_ref())
+        .and_then(|v| v.edges.as_ref())
+        .and_then(|e| e.kinds.get(kind))
+        .and_then(|p| p.weight)
+        .unwrap_or(1.0)
+}
+
+impl PlanGraph {
+    /// Graph over index `nodes` (optionally one schema) joined by `edges` of
+    /// the given kinds. Each kind counts once per pair, weighted by the
+    /// source note's schema policy; kinds with weight <= 0 are dropped.
+    pub fn build(
+        cfg: &Config,
+        nodes: &[Value],
+        edges: &[Value],
+        kinds: &BTreeSet<String>,
+        schema_filter: Option<&str>,
+    ) -> PlanGraph {
+        let mut id_schema: HashMap<String, String> = HashMap::new();
+        for n in nodes {
+            if let (Some(id), Some(schema)) = (
+                n.get("id").and_then(|v| v.as_str()),
+                n.get("schema").and_then(|v| v.as_str()),
+            ) {
+                id_schema.insert(id.to_string(), schema.to_string());
+            }
+        }
+        let ids: Vec<String> = id_schema
+            .iter()
+            .filter(|(_, sc)| schema_filter.map_or(true, |sf| sc.as_str() == sf))
+            .map(|(id, _)| id.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let index: HashMap<&str, usize> = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| (id.as_str(), i))
+            .collect();
+
+        // (a, b) with a < b -> kind -> weight
+        let mut pairs: BTreeMap<(usize, usize), BTreeMap<&str, f64>> = BTreeMap::new();
+        for e in edges {
+            let kind = e.get("kind").and_then(|v| v.as_str()).unwrap_or("");
+            if !kinds.contains(kind) {
+                continue;
+            }
+            let (Some(from), Some(to)) = (
+                e.get("from").and_then(|v| v.as_str()),
+                e.get("to").and_then(|v| v.as_str()),
+            ) else {
+                continue;
+            };
+            let (Some(&a), Some(&b)) = (index.get(from), index.get(to)) else {
+                continue;
+            };
+            if a == b {
+                continue;
+            }
+            let w = kind_weight(cfg, id_schema.get(from), kind);
+            let slot = pairs
+                .entry((a.min(b), a.max(b)))
+                .or_default()
+                .entry(kind)
+                .or_insert(w);
+            *slot = slot.max(w);
+        }
+        let mut adj = vec![BTreeMap::new(); ids.len()];
+        for ((a, b), by_kind) in pairs {
+            let w: f64 = by_kind.values().filter(|w| **w > 0.0).sum();
+            if w > 0.0 {
+                adj[a].insert(b, w);
+                adj[b].insert(a, w);
+            }
+        }
+        PlanGraph { ids, adj }
+    }
+}
@@ -0,0 +1,116 @@
+//! `--label-command`: hand the clusters to an external program (for example a
+//! local model) and take back its labels. The command only edits `label`,
+//! `summary` and `tags`; membership and `sourceIndexHash` stay as planned.
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use super::identity::is_curated;
+use super::labels::NoteText;
+
+fn shell(cmd: &str) -> Command {
+    if cfg!(windows) {
+        let mut c = Command::new("cmd");
+        c.args(["/C", cmd]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", cmd]);
+        c
+    }
+}
+
+/// Run `cmd` with `{version, sourceIndexHash, clusters}` on stdin and merge the
+/// `{"clusters": [{clusterId, label?, summary?, tags?}]}` it prints. Clusters
+/// that carried curated labels over from the cache are not sent.
+pub fn run_label_command(
+    cmd: &str,
+    source_hash: &str,
+    notes: &HashMap<String, NoteText>,
+    clusters: &mut [Value],
+) -> Result<()> {
+    let request = json!({
+        "version": 1,
+        "sourceIndexHash": source_hash,
+        "clusters": clusters.iter().filter(|c| !is_curated(c)).map(|c| {
+            let members: Vec<Value> = c["members"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|m| m.as_str())
+                .map(|id| json!({
+                    "id": id,
+                    "title": notes.get(id).map(|n| n.title.as_str()).unwrap_or(id),
+                }))
+                .collect();
+            json!({
+                "clusterId": c["clusterId"],
+                "members": members,
+                "representatives": c["representatives"],
+                "terms": c.get("terms").cloned().unwrap_or_else(|| json!([])),
+                "label": c["label"],
+                "summary": c["summary"],
+                "tags": c["tags"],
+            })
+        }).collect::<Vec<_>>(),
+    });
+    let mut child = shell(cmd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("running label command `{}`", cmd))?;
+    let input = serde_json::to_vec(&request)?;
+    let mut stdin = child.stdin.take().expect("piped stdin");
+    // Write from a thread so a command that prints before reading cannot block us.
+    let writer = std::thread::spawn(move || stdin.write_all(&input));
+    let out = child.wait_with_output()?;
+    // A command that ignores its input closes the pipe early; that is fine.
+    let _ = writer.join();
+    if !out.status.success() {
+        return Err(anyhow!(
+            "label command `{}` failed ({}): {}",
+            cmd,
+            out.status,
+            String::from_utf8_lossy(&out.stderr).trim()
+        ));
+    }
+    let reply: Value = serde_json::from_slice(&out.stdout)
+        .with_context(|| format!("parsing output of label command `{}`", cmd))?;
+    if let Some(hash) = reply.get("sourceIndexHash").and_then(|v| v.as_str()) {
+        if hash != source_hash {
+            return Err(anyhow!(
+                "label command answered for sourceIndexHash {} (plan: {})",
+                hash,
+                source_hash
+            ));
+        }
+    }
+    let updates = reply
+        .get("clusters")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| anyhow!("label command output has no clusters[]"))?;
+    for u in updates {
+        let id = u.get("clusterId").and_then(|v| v.as_str()).unwrap_or("");
+        let c = clusters
+            .iter_mut()
+            .find(|c| c["clusterId"] == id && !is_curated(c))
+            .ok_or_else(|| anyhow!("label command returned unknown clusterId '{}'", id))?;
+        for key in ["label", "summary"] {
+            if let Some(s) = u.get(key).and_then(|v| v.as_str()) {
+                c[key] = json!(s);
+            }
+        }
+        if let Some(tags) = u.get("tags").and_then(|v| v.as_array()) {
+            if tags.iter().any(|t| !t.is_string()) {
+                return Err(anyhow!("label command tags for '{}' must be strings", id));
+            }
+            c["tags"] = json!(tags);
+        }
+        c["labelSource"] = json!("command");
+    }
+    Ok(())
+}
@@ -0,0 +1,137 @@
+//! Cluster identity across re-plans. New clusters are matched to those in the
+//! AI index cache by Jaccard overlap of their members (same hierarchy level):
+//! a one-to-one match is `stable` and keeps the cached id and curated labels;
+//! otherwise clusters are `new`, `split` or `merged`, and cached clusters with
+//! no match are reported as dissolved.
+
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// The AI index cache written by `ai index apply`, relative to the config dir.
+pub const CACHE_RELATIVE: &str = ".cli-rag/cache/ai-index.json";
+
+/// Clusters of the cache at `path`; a missing or unreadable cache has none.
+pub fn load_previous(path: &Path) -> Vec<Value> {
+    std::fs::read(path)
+        .ok()
+        .and_then(|data| serde_json::from_slice::<Value>(&data).ok())
+        .and_then(|v| v.get("clusters").and_then(|c| c.as_array()).cloned())
+        .unwrap_or_default()
+}
+
+pub fn members(v: &Value) -> BTreeSet<&str> {
+    v.get("members")
+        .and_then(|m| m.as_array())
+        .map(|a| a.iter().filter_map(|x| x.as_str()).collect())
+        .unwrap_or_default()
+}
+
+fn jaccard(a: &BTreeSet<&str>, b: &BTreeSet<&str>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    let j = a.intersection(b).count() as f64 / union as f64;
+    (j * 10_000.0).round() / 10_000.0
+}
+
+fn level(v: &Value) -> u64 {
+    v.get("level").and_then(|l| l.as_u64()).unwrap_or(0)
+}
+
+/// Labels written by a person, an agent or `--label-command`; caches from
+/// before `labelSource` existed only held such labels.
+pub fn is_curated(v: &Value) -> bool {
+    let source = v
+        .get("labelSource")
+        .and_then(|s| s.as_str())
+        .unwrap_or("manual");
+    let label = v.get("label").and_then(|s| s.as_str()).unwrap_or("");
+    matches!(source, "manual" | "command") && !label.is_empty()
+}
+
+/// Set `status` and `previous` on each of `clusters`, carry ids and curated
+/// labels over for stable ones, and return the dissolved cached clusters.
+/// Cached clusters without a member in `scope` (e.g. another schema) are
+/// outside this plan and ignored.
+pub fn reconcile(
+    clusters: &mut [Value],
+    previous: &[Value],
+    scope: &BTreeSet<&str>,
+    threshold: f64,
+) -> Vec<Value> {
+    let prev: Vec<&Value> = previous
+        .iter()
+        .filter(|p| members(p).iter().any(|m| scope.contains(m)))
+        .collect();
+    let prev_members: Vec<BTreeSet<&str>> = prev.iter().map(|p| members(p)).collect();
+    let mut links: Vec<Vec<(usize, f64)>> = vec![Vec::new(); clusters.len()];
+    let mut back: Vec<usize> = vec![0; prev.len()];
+    for (i, c) in clusters.iter().enumerate() {
+        let mine = members(c);
+        for (j, p) in prev.iter().enumerate() {
+            let jac = jaccard(&mine, &prev_members[j]);
+            if level(c) == level(p) && jac > 0.0 && jac >= threshold {
+                links[i].push((j, jac));
+                back[j] += 1;
+            }
+        }
+    }
+
+    let mut remap: HashMap<String, String> = HashMap::new();
+    for (c, links) in clusters.iter_mut().zip(&links) {
+        let status = match links.as_slice() {
+            [] => "new",
+            [(j, _)] if back[*j] == 1 => "stable",
+            [_] => "split",
+            _ => "merged",
+        };
+        c["status"] = json!(status);
+        c["previous"] = json!(links
+            .iter()
+            .map(|(j, jac)| json!({
+                "clusterId": prev[*j]["clusterId"],
+                "label": prev[*j].get("label").cloned().unwrap_or_else(|| json!("")),
+                "jaccard": jac,
+            }))
+            .collect::<Vec<_>>());
+        if status != "stable" {
+            continue;
+        }
+        let old = prev[links[0].0];
+        if let (Some(new_id), Some(old_id)) = (c["clusterId"].as_str(), old["clusterId"].as_str()) {
+            remap.insert(new_id.to_string(), old_id.to_string());
+            c["clusterId"] = json!(old_id);
+        }
+        if is_curated(old) {
+            for key in ["label", "summary", "tags"] {
+                if let Some(v) = old.get(key) {
+                    c[key] = v.clone();
+                }
+            }
+            c["labelSource"] = json!(old
+                .get("labelSource")
+                .and_then(|s| s.as_str())
+                .unwrap_or("manual"));
+        }
+    }
+    for c in clusters.iter_mut() {
+        let parent = c.get("parentId").and_then(|p| p.as_str());
+        if let Some(old_id) = parent.and_then(|p| remap.get(p)) {
+            c["parentId"] = json!(old_id);
+        }
+    }
+
+    prev.iter()
+        .zip(&back)
+        .filter(|(_, n)| **n == 0)
+        .map(|(p, _)| {
+            json!({
+                "clusterId": p["clusterId"],
+                "label": p.get("label").cloned().unwrap_or_else(|| json!("")),
+                "members": p.get("members").cloned().unwrap_or_else(|| json!([])),
+            })
+        })
+        .collect()
+}
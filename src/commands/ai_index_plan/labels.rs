@@ -0,0 +1,161 @@
+//! Offline labels for `ai index plan`: the top TF-IDF terms of each cluster
+//! (titles, headings and prose of its members, weighed against every note in
+//! the index) and the titles of its most central members.
+
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::frontmatter::FrontMatterDoc;
+use crate::wikilink::prose_lines;
+
+/// Terms listed per cluster in the plan.
+const MAX_TERMS: usize = 8;
+/// Terms that make up a proposed label and its tags.
+const LABEL_TERMS: usize = 3;
+const TITLE_WEIGHT: f64 = 3.0;
+const HEADING_WEIGHT: f64 = 2.0;
+
+const STOPWORDS: &[&str] = &[
+    "about", "above", "after", "again", "against", "all", "also", "and", "any", "are", "because",
+    "been", "before", "being", "below", "between", "both", "but", "can", "could", "did", "does",
+    "doing", "down", "during", "each", "either", "else", "etc", "even", "every", "few", "for",
+    "from", "further", "had", "has", "have", "having", "her", "here", "hers", "him", "his", "how",
+    "however", "into", "its", "itself", "just", "may", "might", "more", "most", "much", "must",
+    "need", "needs", "not", "now", "off", "once", "one", "only", "other", "our", "ours", "out",
+    "over", "own", "per", "same", "see", "should", "since", "some", "such", "than", "that", "the",
+    "their", "theirs", "them", "then", "there", "these", "they", "this", "those", "through", "too",
+    "under", "until", "upon", "use", "used", "uses", "using", "very", "via", "was", "were", "what",
+    "when", "where", "whether", "which", "while", "who", "whom", "why", "will", "with", "within",
+    "without", "would", "yet", "you", "your", "yours",
+];
+
+/// A note's title and weighted term counts.
+pub struct NoteText {
+    pub title: String,
+    terms: BTreeMap<String, f64>,
+}
+
+fn add_terms(terms: &mut BTreeMap<String, f64>, text: &str, weight: f64) {
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let word = word.to_lowercase();
+        if word.chars().count() < 3
+            || word.chars().all(|c| c.is_ascii_digit())
+            || STOPWORDS.contains(&word.as_str())
+        {
+            continue;
+        }
+        *terms.entry(word).or_default() += weight;
+    }
+}
+
+/// Text of every index node, read from `<cfg_dir>/<path>`; unreadable notes
+/// contribute their title only.
+pub fn note_texts(cfg_dir: &Path, nodes: &[Value]) -> HashMap<String, NoteText> {
+    let mut out = HashMap::new();
+    for n in nodes {
+        let Some(id) = n.get("id").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let title = n.get("title").and_then(|v| v.as_str()).unwrap_or(id);
+        let mut terms = BTreeMap::new();
+        add_terms(&mut terms, title, TITLE_WEIGHT);
+        let path = n.get("path").and_then(|v| v.as_str()).unwrap_or("");
+        if let Ok(content) = std::fs::read_to_string(cfg_dir.join(path)) {
+            let body = FrontMatterDoc::parse(&content)
+                .map(|d| d.body)
+                .unwrap_or(content);
+            for (_, line) in prose_lines(&body) {
+                let heading = line.trim_start().starts_with('#');
+                add_terms(
+                    &mut terms,
+                    &line,
+                    if heading { HEADING_WEIGHT } else { 1.0 },
+                );
+            }
+        }
+        out.insert(
+            id.to_string(),
+            NoteText {
+                title: title.to_string(),
+                terms,
+            },
+        );
+    }
+    out
+}
+
+fn str_list(v: &Value) -> Vec<String> {
+    v.as_array()
+        .map(|a| {
+            a.iter()
+                .filter_map(|x| x.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn round4(x: f64) -> f64 {
+    (x * 10_000.0).round() / 10_000.0
+}
+
+/// Fill `terms`, `label`, `summary` and `tags` of each plan cluster, and keep
+/// the proposal in `proposed` so `apply` can tell edited labels apart.
+pub fn propose_labels(notes: &HashMap<String, NoteText>, clusters: &mut [Value]) {
+    let mut df: HashMap<&str, usize> = HashMap::new();
+    for note in notes.values() {
+        for term in note.terms.keys() {
+            *df.entry(term.as_str()).or_default() += 1;
+        }
+    }
+    let n = notes.len().max(1) as f64;
+    for c in clusters.iter_mut() {
+        let members = str_list(&c["members"]);
+        let mut tf: BTreeMap<&str, f64> = BTreeMap::new();
+        for note in members.iter().filter_map(|m| notes.get(m)) {
+            for (term, w) in &note.terms {
+                *tf.entry(term.as_str()).or_default() += w;
+            }
+        }
+        let total: f64 = tf.values().sum::<f64>().max(1.0);
+        let mut scored: Vec<(&str, f64)> = tf
+            .into_iter()
+            .map(|(t, w)| (t, round4(w / total * (n / df[t] as f64).ln())))
+            .filter(|(_, s)| *s > 0.0)
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        scored.truncate(MAX_TERMS);
+        let top: Vec<&str> = scored.iter().take(LABEL_TERMS).map(|(t, _)| *t).collect();
+
+        let central: Vec<String> = str_list(&c["representatives"])
+            .iter()
+            .map(|id| match notes.get(id) {
+                Some(note) if note.title != *id => format!("{} ({})", note.title, id),
+                _ => id.clone(),
+            })
+            .collect();
+        let label = if top.is_empty() {
+            central.first().cloned().unwrap_or_default()
+        } else {
+            top.join(", ")
+        };
+        let mut summary = format!("{} notes", members.len());
+        if !top.is_empty() {
+            summary.push_str(&format!(" about {}", top.join(", ")));
+        }
+        if !central.is_empty() {
+            summary.push_str(&format!("; central: {}", central.join(", ")));
+        }
+        summary.push('.');
+
+        c["terms"] = json!(scored
+            .iter()
+            .map(|(t, s)| json!({"term": t, "score": s}))
+            .collect::<Vec<_>>());
+        c["proposed"] = json!({"label": label, "summary": summary});
+        c["label"] = json!(label);
+        c["summary"] = json!(summary);
+        c["tags"] = json!(top);
+        c["labelSource"] = json!("tfidf");
+    }
+}
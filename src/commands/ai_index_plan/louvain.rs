@@ -0,0 +1,243 @@
+//! Louvain modularity optimisation with a resolution parameter, and Leiden,
+//! which adds a refinement phase between local moving and aggregation so
+//! every community stays connected (Traag, Waltman & van Eck, 2019). The
+//! refinement is deterministic: vertices go in id order to the best merge
+//! rather than a random one. Every aggregation pass is one level of the
+//! hierarchy.
+
+use std::collections::BTreeMap;
+
+use super::algorithms::{group, Partition};
+use super::graph::PlanGraph;
+
+type Adj = Vec<BTreeMap<usize, f64>>;
+
+/// Community ids numbered by first vertex, so equal partitions compare equal.
+fn renumber(comm: &[usize]) -> Vec<usize> {
+    let mut ids: BTreeMap<usize, usize> = BTreeMap::new();
+    comm.iter()
+        .map(|c| {
+            let next = ids.len();
+            *ids.entry(*c).or_insert(next)
+        })
+        .collect()
+}
+
+/// Move vertices, in order, to the neighbouring community with the best
+/// modularity gain until no move helps, starting from `init`.
+fn local_moving(adj: &Adj, resolution: f64, init: &[usize]) -> Vec<usize> {
+    let n = adj.len();
+    let k: Vec<f64> = adj.iter().map(|a| a.values().sum()).collect();
+    let m2: f64 = k.iter().sum();
+    let mut comm = init.to_vec();
+    if m2 <= 0.0 {
+        return comm;
+    }
+    let mut tot = vec![0.0; n];
+    for v in 0..n {
+        tot[comm[v]] += k[v];
+    }
+    for _ in 0..100 {
+        let mut moved = false;
+        for v in 0..n {
+            let old = comm[v];
+            tot[old] -= k[v];
+            let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+            for (u, w) in &adj[v] {
+                if *u != v {
+                    *links.entry(comm[*u]).or_default() += w;
+                }
+            }
+            let gain = |c: usize, w: f64| w - resolution * k[v] * tot[c] / m2;
+            let mut best = old;
+            let mut best_gain = gain(old, links.get(&old).copied().unwrap_or(0.0));
+            for (&c, &w) in &links {
+                let g = gain(c, w);
+                if g > best_gain + 1e-12 {
+                    best = c;
+                    best_gain = g;
+                }
+            }
+            comm[v] = best;
+            tot[best] += k[v];
+            moved |= best != old;
+        }
+        if !moved {
+            break;
+        }
+    }
+    renumber(&comm)
+}
+
+/// Leiden refinement: inside every community, start from singletons and
+/// merge each vertex that is still alone and well connected to its community
+/// into the well-connected subcommunity with the best positive gain. Merges
+/// follow edges, so every subcommunity is connected.
+fn refine_partition(adj: &Adj, comm: &[usize], resolution: f64) -> Vec<usize> {
+    let n = adj.len();
+    let k: Vec<f64> = adj.iter().map(|a| a.values().sum()).collect();
+    let m2: f64 = k.iter().sum();
+    let mut refined: Vec<usize> = (0..n).collect();
+    if m2 <= 0.0 {
+        return refined;
+    }
+    let mut comm_tot = vec![0.0; n];
+    for v in 0..n {
+        comm_tot[comm[v]] += k[v];
+    }
+    // per subcommunity: total degree, size and weight to the rest of its community
+    let mut tot = k.clone();
+    let mut size = vec![1usize; n];
+    let mut outside: Vec<f64> = (0..n)
+        .map(|v| {
+            adj[v]
+                .iter()
+                .filter(|(u, _)| **u != v && comm[**u] == comm[v])
+                .map(|(_, w)| w)
+                .sum()
+        })
+        .collect();
+    let well_connected = |r: usize, outside: &[f64], tot: &[f64], c: usize| {
+        outside[r] + 1e-12 >= resolution * tot[r] * (comm_tot[c] - tot[r]) / m2
+    };
+    for v in 0..n {
+        let c = comm[v];
+        if size[refined[v]] > 1 || !well_connected(v, &outside, &tot, c) {
+            continue;
+        }
+        let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+        for (u, w) in &adj[v] {
+            if *u != v && comm[*u] == c {
+                *links.entry(refined[*u]).or_default() += w;
+            }
+        }
+        let mut best = None;
+        let mut best_gain = 0.0;
+        for (&r, &w) in &links {
+            if !well_connected(r, &outside, &tot, c) {
+                continue;
+            }
+            let g = w - resolution * k[v] * tot[r] / m2;
+            if g > best_gain + 1e-12 {
+                best = Some((r, w));
+                best_gain = g;
+            }
+        }
+        if let Some((r, w)) = best {
+            outside[r] += outside[v] - 2.0 * w;
+            tot[r] += k[v];
+            size[r] += 1;
+            tot[v] = 0.0;
+            size[v] = 0;
+            refined[v] = r;
+        }
+    }
+    renumber(&refined)
+}
+
+/// One vertex per community; internal weight becomes a self loop.
+fn aggregate(adj: &Adj, comm: &[usize], count: usize) -> Adj {
+    let mut out: Adj = vec![BTreeMap::new(); count];
+    for (v, a) in adj.iter().enumerate() {
+        for (u, w) in a {
+            *out[comm[v]].entry(comm[*u]).or_default() += w;
+        }
+    }
+    out
+}
+
+/// Partitions of the original vertices after each pass, coarsest first.
+/// With `refine` (Leiden), each pass aggregates the refined partition and the
+/// next pass starts from the unrefined communities; the finer levels are the
+/// refined partitions, so the levels stay nested.
+pub fn louvain_levels(g: &PlanGraph, resolution: f64, refine: bool) -> Vec<Partition> {
+    let mut member_of: Vec<usize> = (0..g.ids.len()).collect();
+    let mut adj: Adj = g.adj.clone();
+    let mut init: Vec<usize> = (0..adj.len()).collect();
+    let mut levels: Vec<Vec<usize>> = Vec::new();
+    let mut push = |level: Vec<usize>| {
+        let level = renumber(&level);
+        if levels.last() != Some(&level) {
+            levels.push(level);
+        }
+    };
+    loop {
+        let comm = local_moving(&adj, resolution, &init);
+        let part = if refine {
+            refine_partition(&adj, &comm, resolution)
+        } else {
+            comm.clone()
+        };
+        let count = part.iter().max().map_or(0, |m| m + 1);
+        if count == adj.len() {
+            push(member_of.iter().map(|m| comm[*m]).collect());
+            break;
+        }
+        init = vec![0; count];
+        for (v, p) in part.iter().enumerate() {
+            init[*p] = comm[v];
+        }
+        for m in member_of.iter_mut() {
+            *m = part[*m];
+        }
+        push(member_of.clone());
+        adj = aggregate(&adj, &part, count);
+    }
+    levels
+        .iter()
+        .rev()
+        .map(|l| group(&l.iter().map(|m| Some(*m)).collect::<Vec<_>>()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adj(n: usize, edges: &[(usize, usize)]) -> Adj {
+        let mut out: Adj = vec![BTreeMap::new(); n];
+        for (a, b) in edges {
+            out[*a].insert(*b, 1.0);
+            out[*b].insert(*a, 1.0);
+        }
+        out
+    }
+
+    #[test]
+    fn refinement_keeps_subcommunities_connected_and_inside_their_community() {
+        // two triangles placed in one community, with no edge between them
+        let g = adj(6, &[(0, 1), (1, 2), (0, 2), (3, 4), (4, 5), (3, 5)]);
+        let refined = refine_partition(&g, &[0; 6], 1.0);
+        assert_eq!(refined[0], refined[1]);
+        assert!(refined[..3].iter().all(|r| !refined[3..].contains(r)));
+        // a community is never merged across: 2 and 3 stay apart
+        let g = adj(4, &[(0, 1), (1, 2), (2, 3)]);
+        let refined = refine_partition(&g, &[0, 0, 1, 1], 1.0);
+        assert_eq!(refined, vec![0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn leiden_levels_nest_and_match_louvain_on_two_cliques() {
+        let mut edges = Vec::new();
+        for base in [0, 4] {
+            for a in base..base + 4 {
+                for b in a + 1..base + 4 {
+                    edges.push((a, b));
+                }
+            }
+        }
+        edges.push((3, 4));
+        let g = PlanGraph {
+            ids: (0..8).map(|i| i.to_string()).collect(),
+            adj: adj(8, &edges),
+        };
+        let leiden = louvain_levels(&g, 1.0, true);
+        assert_eq!(leiden[0], vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7]]);
+        assert_eq!(leiden[0], louvain_levels(&g, 1.0, false)[0]);
+        for pair in leiden.windows(2) {
+            for part in &pair[1] {
+                assert!(pair[0].iter().any(|c| part.iter().all(|v| c.contains(v))));
+            }
+        }
+    }
+}
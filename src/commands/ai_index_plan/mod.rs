@@ -0,0 +1,147 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+use crate::cli::{AiIndexPlanArgs, ClusterAlgorithm};
+use crate::config::Config;
+
+mod algorithms;
+mod clusters;
+mod graph;
+mod hook;
+mod identity;
+mod labels;
+mod louvain;
+
+pub use identity::{load_previous, CACHE_RELATIVE};
+
+use graph::PlanGraph;
+
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    let out = hasher.finalize();
+    let mut s = String::with_capacity(out.len() * 2);
+    for b in out {
+        s.push_str(&format!("{:02x}", b));
+    }
+    s
+}
+
+pub fn run(cfg: &Config, cfg_path: &Option<PathBuf>, args: AiIndexPlanArgs) -> Result<()> {
+    let AiIndexPlanArgs {
+        edges: edges_opt,
+        min_cluster_size,
+        schema: schema_filter,
+        output,
+        algorithm,
+        resolution,
+        min_core,
+        hierarchical,
+        no_labels,
+        label_command,
+        previous,
+        match_threshold,
+    } = args;
+    if resolution.is_nan() || resolution <= 0.0 {
+        return Err(anyhow!("--resolution must be a positive number"));
+    }
+    let cfg_dir = cfg_path.as_ref().and_then(|p| p.parent()).ok_or_else(|| {
+        anyhow!("Cannot locate config directory; pass --config and run `validate` first")
+    })?;
+    let index_path = cfg_dir.join(&cfg.index_relative);
+    if !index_path.exists() {
+        return Err(anyhow!(
+            "Unified index not found at {}. Run `cli-rag validate` first.",
+            index_path.display()
+        ));
+    }
+    let data = std::fs::read(&index_path)
+        .with_context(|| format!("reading unified index {}", index_path.display()))?;
+    let source_hash = format!("sha256:{}", sha256_hex(&data));
+    let root: Value = serde_json::from_slice(&data)
+        .with_context(|| format!("parsing unified index {}", index_path.display()))?;
+
+    let nodes_v = root
+        .get("nodes")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| anyhow!("Invalid unified index: missing nodes[]"))?;
+    let edges_v = root
+        .get("edges")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| anyhow!("Invalid unified index: missing edges[]"))?;
+
+    // Edge kinds to include
+    let edge_kinds: BTreeSet<String> = match edges_opt {
+        Some(list) => list.into_iter().collect(),
+        None => ["depends_on", "mentions"]
+            .iter()
+            .map(|k| k.to_string())
+            .collect(),
+    };
+
+    let graph = PlanGraph::build(cfg, nodes_v, edges_v, &edge_kinds, schema_filter.as_deref());
+    let levels = match algorithm {
+        ClusterAlgorithm::Components => vec![algorithms::components(&graph)],
+        ClusterAlgorithm::LabelPropagation => vec![algorithms::label_propagation(&graph)],
+        ClusterAlgorithm::Louvain => louvain::louvain_levels(&graph, resolution, false),
+        ClusterAlgorithm::Leiden => louvain::louvain_levels(&graph, resolution, true),
+        ClusterAlgorithm::Kcore => algorithms::kcore_levels(&graph, min_core),
+    };
+    let levels = if hierarchical {
+        &levels[..]
+    } else {
+        &levels[..levels.len().min(1)]
+    };
+    let mut clusters_json =
+        clusters::build_clusters(&graph, levels, min_cluster_size, hierarchical);
+
+    let previous = previous.unwrap_or_else(|| cfg_dir.join(identity::CACHE_RELATIVE));
+
+    // Build JSON output per contract
+    let params = serde_json::json!({
+        "edges": edge_kinds,
+        "minClusterSize": min_cluster_size as i64,
+        "schema": schema_filter,
+        "algorithm": algorithm.as_str(),
+        "resolution": resolution,
+        "minCore": min_core as i64,
+        "hierarchical": hierarchical,
+        "labels": if no_labels { "none" } else { "tfidf" },
+        "labelCommand": label_command,
+        "previous": previous.exists().then(|| previous.display().to_string()),
+        "matchThreshold": match_threshold,
+    });
+    let notes = labels::note_texts(cfg_dir, nodes_v);
+    if !no_labels {
+        labels::propose_labels(&notes, &mut clusters_json);
+    }
+    let scope: BTreeSet<&str> = graph.ids.iter().map(|s| s.as_str()).collect();
+    let dissolved = identity::reconcile(
+        &mut clusters_json,
+        &identity::load_previous(&previous),
+        &scope,
+        match_threshold,
+    );
+    if let Some(cmd) = &label_command {
+        hook::run_label_command(cmd, &source_hash, &notes, &mut clusters_json)?;
+    }
+    let out = serde_json::json!({
+        "version": 1,
+        "generatedAt": Utc::now().to_rfc3339(),
+        "sourceIndexHash": source_hash,
+        "params": params,
+        "clusters": clusters_json,
+        "dissolved": dissolved,
+    });
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent).ok();
+    }
+    std::fs::write(&output, serde_json::to_string_pretty(&out)?)
+        .with_context(|| format!("writing plan to {}", output.display()))?;
+    eprintln!("Wrote AI index plan: {}", output.display());
+    Ok(())
+}
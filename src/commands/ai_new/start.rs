@@ -3,6 +3,7 @@ use std::fs;
 use std::path::PathBuf;
 
 use crate::commands::lua_integration::{lua_new_hooks, LuaNewArtifacts};
+use crate::commands::new_defaults::apply_schema_defaults;
 use crate::commands::new_helpers::{
     generate_initial_id, render_template, render_text_with_vars, resolve_destination_dir,
     TemplateVars,
@@ -99,7 +100,9 @@ pub fn start(
         filename: &filename,
     };
     let instructions = render_text_with_vars(&instructions_source, &template_vars);
-    let mut note_template = render_template(template_source.clone(), &template_vars);
+    let note_template = render_template(template_source.clone(), &template_vars);
+    let mut note_template =
+        apply_schema_defaults(note_template, &template_source, schema_cfg, &template_vars)?;
 
     if let Some(map) = fm_overrides {
         if note_template.starts_with("---\n") {
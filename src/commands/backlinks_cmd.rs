@@ -0,0 +1,79 @@
+//! `cli-rag backlinks sync`: bring the generated "Referenced by" block of every
+//! opted-in note in line with the graph (see `crate::backlinks`).
+
+use anyhow::{Context, Result};
+use serde_json::json;
+
+use crate::backlinks::plan_blocks;
+use crate::cli::{BacklinksCommands, OutputFormat};
+use crate::commands::output::print_json;
+use crate::config::Config;
+use crate::discovery::scan_docs;
+use crate::util::{normalize_display_path, unified_diff};
+
+pub fn run(cfg: &Config, format: &OutputFormat, command: BacklinksCommands) -> Result<()> {
+    let BacklinksCommands::Sync(args) = command;
+    // Scan rather than read the index: blocks must reflect the notes on disk.
+    let docs = scan_docs(cfg)?;
+    let planned = plan_blocks(cfg, &docs);
+    let mut updated = Vec::new();
+    let mut warnings = Vec::new();
+    for p in &planned {
+        let before = std::fs::read_to_string(&p.doc.file)
+            .with_context(|| format!("reading {}", p.doc.file.display()))?;
+        let after = match p.apply(&before) {
+            Ok(after) => after,
+            Err(e) => {
+                warnings.push(format!("{}: {}", p.doc.display_path(), e));
+                continue;
+            }
+        };
+        if after == before {
+            continue;
+        }
+        if !args.dry_run {
+            std::fs::write(&p.doc.file, &after)
+                .with_context(|| format!("writing {}", p.doc.file.display()))?;
+        }
+        let path = normalize_display_path(&p.doc.file);
+        let diff = unified_diff(&path, &before, &after);
+        updated.push((path, diff));
+    }
+    updated.sort();
+    match format {
+        OutputFormat::Plain => {
+            for w in &warnings {
+                eprintln!("Warning: {}", w);
+            }
+            for (path, diff) in &updated {
+                if args.dry_run {
+                    print!("{}", diff);
+                } else {
+                    println!("Updated {}", path);
+                }
+            }
+            let verb = if args.dry_run {
+                "Would update"
+            } else {
+                "Updated"
+            };
+            println!(
+                "{} {} of {} note(s) with backlinks enabled",
+                verb,
+                updated.len(),
+                planned.len()
+            );
+        }
+        _ => print_json(&json!({
+            "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+            "dryRun": args.dry_run,
+            "notes": planned.len(),
+            "updated": updated
+                .iter()
+                .map(|(path, diff)| json!({"path": path, "diff": diff}))
+                .collect::<Vec<_>>(),
+            "warnings": warnings,
+        }))?,
+    }
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use clap::builder::PossibleValuesParser;
 use clap::Command;
 use clap_complete::{
     generate,
@@ -16,3 +17,13 @@ pub fn run_completions<S: AsRef<str>>(mut cmd: Command, shell: S) {
         }
     }
 }
+
+/// Offer the configured `[[view]]` names as values for `view <NAME>`.
+pub fn with_view_names(cmd: Command, names: Vec<String>) -> Command {
+    if names.is_empty() {
+        return cmd;
+    }
+    cmd.mut_subcommand("view", |sc| {
+        sc.mut_arg("name", |a| a.value_parser(PossibleValuesParser::new(names)))
+    })
+}
@@ -0,0 +1,54 @@
+use anyhow::{anyhow, Result};
+use std::path::PathBuf;
+
+use crate::cli::OutputFormat;
+use crate::commands::output::{print_json, print_ndjson_iter};
+use crate::config::provenance::{explain_config, ConfigEntry, ExplainInputs};
+use crate::config::resolved::resolved_config_json;
+use crate::config::Config;
+
+fn describe_source(entry: &ConfigEntry) -> String {
+    match (&entry.source, entry.line) {
+        (Some(src), Some(line)) => format!("{}: {}:{}", entry.layer.as_str(), src, line),
+        (Some(src), None) => format!("{}: {}", entry.layer.as_str(), src),
+        _ => entry.layer.as_str().to_string(),
+    }
+}
+
+/// Show effective config values and the layer each one came from.
+pub fn explain(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    inputs: &ExplainInputs,
+    key: Option<String>,
+    format: &OutputFormat,
+) -> Result<()> {
+    let mut entries = explain_config(cfg, cfg_path, inputs);
+    if let Some(k) = key.as_deref() {
+        let prefix = format!("{}.", k);
+        entries.retain(|e| e.key == k || e.key.starts_with(&prefix));
+        if entries.is_empty() {
+            return Err(anyhow!("Unknown config key '{}'", k));
+        }
+    }
+    match format {
+        OutputFormat::Json | OutputFormat::Ai => {
+            let project_root = cfg_path
+                .as_ref()
+                .and_then(|p| p.parent().map(|d| d.to_path_buf()))
+                .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+            let mut resolved = resolved_config_json(cfg, &project_root);
+            resolved["provenance"] = serde_json::to_value(&entries)?;
+            print_json(&resolved)?;
+        }
+        OutputFormat::Ndjson => {
+            print_ndjson_iter(&entries)?;
+        }
+        OutputFormat::Plain => {
+            for e in &entries {
+                println!("{} = {}  ({})", e.key, e.value, describe_source(e));
+            }
+        }
+    }
+    Ok(())
+}
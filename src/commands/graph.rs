@@ -1,7 +1,9 @@
 use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 use crate::cli::GraphFormat;
+use crate::commands::lua_integration::render_with_overlay;
 use crate::commands::output::print_json;
 use crate::config::{build_schema_sets, Config};
 use crate::discovery::docs_with_source;
@@ -70,6 +72,50 @@ pub(crate) fn render_dot(cluster: &BTreeMap<String, AdrDoc>) -> String {
     out
 }
 
+/// JSON graph envelope (`contracts/v1/cli/graph.schema.json`) for a cluster.
+pub fn graph_json(cfg: &Config, cluster: &BTreeMap<String, AdrDoc>, root: &str) -> Value {
+    // schema inference by filename
+    let schema_sets = build_schema_sets(cfg);
+    let infer_schema = |path: &std::path::Path| -> String {
+        let fname = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
+        for (sc, set) in &schema_sets {
+            if set.is_match(fname) {
+                return sc.name.clone();
+            }
+        }
+        "UNKNOWN".into()
+    };
+    let mut nodes: Vec<Value> = cluster
+        .iter()
+        .map(|(oid, d)| {
+            json!({
+                "id": oid,
+                "title": d.title,
+                "schema": infer_schema(&d.file),
+            })
+        })
+        .collect();
+    // deterministic order
+    nodes.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
+    let mut edges: Vec<Value> = cluster_edges(cluster)
+        .into_iter()
+        .map(|e| json!({"from": e.from, "to": e.to, "kind": e.kind}))
+        .collect();
+    edges.sort_by(|a, b| {
+        (a["from"].as_str(), a["to"].as_str(), a["kind"].as_str()).cmp(&(
+            b["from"].as_str(),
+            b["to"].as_str(),
+            b["kind"].as_str(),
+        ))
+    });
+    json!({
+        "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+        "root": {"id": root},
+        "nodes": nodes,
+        "edges": edges,
+    })
+}
+
 pub fn run(
     cfg: &Config,
     cfg_path: &Option<std::path::PathBuf>,
@@ -77,6 +123,7 @@ pub fn run(
     id: String,
     depth: Option<usize>,
     include_bidirectional: Option<bool>,
+    render: Option<String>,
 ) -> Result<()> {
     let (docs, used_unified) = docs_with_source(cfg, cfg_path)?;
     if !used_unified {
@@ -94,49 +141,17 @@ pub fn run(
         return Err(anyhow!("ADR not found: {}", id));
     }
     let cluster = compute_cluster(&id, depth, include_bidirectional, &by_id);
+    if let Some(name) = render {
+        let data = graph_json(cfg, &cluster, &id);
+        print!(
+            "{}",
+            render_with_overlay(cfg, cfg_path, &docs, "graph", &name, &data)?
+        );
+        return Ok(());
+    }
     match format {
         GraphFormat::Json => {
-            // schema inference by filename
-            let schema_sets = build_schema_sets(cfg);
-            let infer_schema = |path: &std::path::Path| -> String {
-                let fname = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
-                for (sc, set) in &schema_sets {
-                    if set.is_match(fname) {
-                        return sc.name.clone();
-                    }
-                }
-                "UNKNOWN".into()
-            };
-            let mut nodes: Vec<serde_json::Value> = cluster
-                .iter()
-                .map(|(oid, d)| {
-                    serde_json::json!({
-                        "id": oid,
-                        "title": d.title,
-                        "schema": infer_schema(&d.file),
-                    })
-                })
-                .collect();
-            // deterministic order
-            nodes.sort_by(|a, b| a["id"].as_str().cmp(&b["id"].as_str()));
-            let mut edges: Vec<serde_json::Value> = cluster_edges(&cluster)
-                .into_iter()
-                .map(|e| serde_json::json!({"from": e.from, "to": e.to, "kind": e.kind}))
-                .collect();
-            edges.sort_by(|a, b| {
-                (a["from"].as_str(), a["to"].as_str(), a["kind"].as_str()).cmp(&(
-                    b["from"].as_str(),
-                    b["to"].as_str(),
-                    b["kind"].as_str(),
-                ))
-            });
-            let out = serde_json::json!({
-                "protocolVersion": crate::protocol::PROTOCOL_VERSION,
-                "root": {"id": id},
-                "nodes": nodes,
-                "edges": edges,
-            });
-            print_json(&out)?;
+            print_json(&graph_json(cfg, &cluster, &id))?;
         }
         GraphFormat::Dot => {
             let s = render_dot(&cluster);
@@ -169,6 +184,7 @@ mod tests {
             fm: BTreeMap::new(),
             mtime: None,
             size: None,
+            headings: None,
         }
     }
 
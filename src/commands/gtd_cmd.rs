@@ -0,0 +1,310 @@
+//! `cli-rag todo done|set` and `cli-rag kanban move`: edit GTD items in place.
+//!
+//! Todos are addressed by the id `search` emits, `<NOTE-ID>#<fnv1a_64 of
+//! path:line:text>`. The box is only rewritten when a line still hashes to that
+//! id; if the text now sits on another line, or changed, the edit is refused
+//! so a stale listing never touches the wrong item.
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::json;
+use std::path::PathBuf;
+
+use crate::cli::{KanbanCommands, OutputFormat, TodoCommands};
+use crate::commands::output::print_json;
+use crate::commands::search_gtd::{fnv1a_64, hex_u64, parse_gtd_box, parse_gtd_date, Repeat};
+use crate::config::Config;
+use crate::discovery::docs_with_source;
+use crate::frontmatter::FrontMatterDoc;
+use crate::model::AdrDoc;
+
+fn item_hash(path: &str, line_no: usize, line: &str) -> String {
+    hex_u64(fnv1a_64(&format!("{}:{}:{}", path, line_no, line.trim())))
+}
+
+fn find_note<'a>(docs: &'a [AdrDoc], id: &str) -> Result<&'a AdrDoc> {
+    docs.iter()
+        .find(|d| d.id.as_deref() == Some(id))
+        .ok_or_else(|| anyhow!("note '{}' not found", id))
+}
+
+fn split_item_id(id: &str) -> Result<(&str, &str)> {
+    id.split_once('#')
+        .filter(|(note, hash)| !note.is_empty() && !hash.is_empty())
+        .ok_or_else(|| anyhow!("invalid todo id '{}' (expected <NOTE-ID>#<hash>)", id))
+}
+
+fn is_item_line(line: &str) -> bool {
+    let t = line.trim_start();
+    parse_gtd_box(line).is_some_and(|g| g.cmd.eq_ignore_ascii_case("todo"))
+        || t.starts_with("- [ ] ")
+        || t.starts_with("- [x] ")
+        || t.starts_with("- [X] ")
+}
+
+/// Index of the line `hash` was computed from, or a conflict error.
+fn locate(lines: &[&str], path: &str, note_id: &str, hash: &str) -> Result<usize> {
+    if let Some(i) = lines
+        .iter()
+        .enumerate()
+        .position(|(i, l)| is_item_line(l) && item_hash(path, i + 1, l) == hash)
+    {
+        return Ok(i);
+    }
+    // Same text on a different line: the id was taken before lines shifted.
+    let max_line = lines.len() + 1000;
+    for (i, l) in lines.iter().enumerate().filter(|(_, l)| is_item_line(l)) {
+        if let Some(old) = (1..=max_line).find(|n| item_hash(path, *n, l) == hash) {
+            return Err(anyhow!(
+                "conflict: todo {}#{} moved from line {} to line {} of {}; re-run search and use {}#{}",
+                note_id,
+                hash,
+                old,
+                i + 1,
+                path,
+                note_id,
+                item_hash(path, i + 1, l)
+            ));
+        }
+    }
+    Err(anyhow!(
+        "conflict: todo {}#{} not found in {}; it was edited or removed since it was listed",
+        note_id,
+        hash,
+        path
+    ))
+}
+
+/// Rewrite the attributes of the `[@...]` box on `line`; `None` removes a key.
+/// Existing attributes keep their position, new ones are appended.
+fn rewrite_box(line: &str, edits: &[(String, Option<String>)]) -> Result<String> {
+    let open = line.find('[').ok_or_else(|| anyhow!("not a GTD box"))?;
+    let close = open
+        + line[open..]
+            .find(']')
+            .ok_or_else(|| anyhow!("not a GTD box"))?;
+    let mut tokens: Vec<String> = line[open + 1..close].split(':').map(String::from).collect();
+    for (key, value) in edits {
+        let pos = tokens
+            .iter()
+            .skip(1)
+            .position(|t| {
+                t.split('=')
+                    .next()
+                    .unwrap_or("")
+                    .trim()
+                    .eq_ignore_ascii_case(key)
+            })
+            .map(|p| p + 1);
+        match (pos, value) {
+            (Some(p), Some(v)) => tokens[p] = format!("{}={}", key, v),
+            (Some(p), None) => {
+                tokens.remove(p);
+            }
+            (None, Some(v)) => tokens.push(format!("{}={}", key, v)),
+            (None, None) => {}
+        }
+    }
+    Ok(format!(
+        "{}[{}]{}",
+        &line[..open],
+        tokens.join(":"),
+        &line[close + 1..]
+    ))
+}
+
+fn parse_assignment(raw: &str) -> Result<(String, Option<String>)> {
+    let (k, v) = raw
+        .split_once('=')
+        .ok_or_else(|| anyhow!("invalid attribute '{}' (expected key=value)", raw))?;
+    let k = k.trim().to_lowercase();
+    let bad = |s: &str| s.contains([':', ']', '[']);
+    if k.is_empty() || bad(&k) || bad(v) {
+        return Err(anyhow!(
+            "invalid attribute '{}': keys and values cannot contain ':' or brackets",
+            raw
+        ));
+    }
+    let v = v.trim();
+    Ok((k, (!v.is_empty()).then(|| v.to_string())))
+}
+
+/// Edits for `todo done`. Repeating boxes advance their dates by one step
+/// instead of closing, as org-mode does.
+fn done_edits(line: &str, date: &str) -> Result<Vec<(String, Option<String>)>> {
+    let g = parse_gtd_box(line).ok_or_else(|| anyhow!("not a GTD box"))?;
+    if g.attrs.contains_key("done") {
+        return Err(anyhow!("todo is already done ({})", g.attrs["done"]));
+    }
+    let Some(step) = g.attrs.get("repeat").and_then(|r| Repeat::parse(r)) else {
+        return Ok(vec![("done".into(), Some(date.to_string()))]);
+    };
+    let mut edits = Vec::new();
+    for key in ["scheduled", "due"] {
+        if let Some(next) = g
+            .attrs
+            .get(key)
+            .and_then(|d| parse_gtd_date(d))
+            .and_then(|d| step.nth(d, 1))
+        {
+            edits.push((key.to_string(), Some(next.to_string())));
+        }
+    }
+    if edits.is_empty() {
+        edits.push(("done".into(), Some(date.to_string())));
+    }
+    Ok(edits)
+}
+
+fn load_docs(cfg: &Config, cfg_path: &Option<PathBuf>) -> Result<Vec<AdrDoc>> {
+    let (docs, used_unified) = docs_with_source(cfg, cfg_path)?;
+    if !used_unified {
+        eprintln!("Note: unified index not found; falling back to per-base/scan. Consider `cli-rag validate`.");
+    }
+    Ok(docs)
+}
+
+pub fn run_todo(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    format: &OutputFormat,
+    command: TodoCommands,
+) -> Result<()> {
+    let (item_id, done_date, assignments) = match command {
+        TodoCommands::Done(a) => {
+            let date = match a.date {
+                Some(d) => parse_gtd_date(&d)
+                    .ok_or_else(|| anyhow!("invalid --date '{}' (expected YYYY-MM-DD)", d))?,
+                None => chrono::Local::now().date_naive(),
+            };
+            (a.id, Some(date.to_string()), Vec::new())
+        }
+        TodoCommands::Set(a) => {
+            let edits = a
+                .attrs
+                .iter()
+                .map(|r| parse_assignment(r))
+                .collect::<Result<Vec<_>>>()?;
+            (a.id, None, edits)
+        }
+    };
+    let (note_id, hash) = split_item_id(&item_id)?;
+    let docs = load_docs(cfg, cfg_path)?;
+    let doc = find_note(&docs, note_id)?;
+    let path = doc.display_path();
+    let content = std::fs::read_to_string(&doc.file)
+        .with_context(|| format!("reading {}", doc.file.display()))?;
+    let mut lines: Vec<&str> = content.split('\n').collect();
+    // Keep CRLF endings intact: hash and edit without the '\r'.
+    let cr: Vec<bool> = lines.iter().map(|l| l.ends_with('\r')).collect();
+    for l in lines.iter_mut() {
+        *l = l.strip_suffix('\r').unwrap_or(l);
+    }
+    let idx = locate(&lines, &path, note_id, hash)?;
+    let before = lines[idx].to_string();
+    let after = if parse_gtd_box(&before).is_some() {
+        let edits = match &done_date {
+            Some(date) => done_edits(&before, date)?,
+            None => assignments,
+        };
+        rewrite_box(&before, &edits)?
+    } else if done_date.is_some() {
+        let at = before
+            .find("- [")
+            .ok_or_else(|| anyhow!("not a checkbox"))?;
+        if before[at..].starts_with("- [ ]") {
+            format!("{}- [x]{}", &before[..at], &before[at + 5..])
+        } else {
+            return Err(anyhow!("todo {} is already checked", item_id));
+        }
+    } else {
+        return Err(anyhow!(
+            "todo {} is a checkbox; only [@TODO:...] boxes carry attributes",
+            item_id
+        ));
+    };
+    let new_lines: Vec<String> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, l)| {
+            let l = if i == idx { after.as_str() } else { l };
+            if cr[i] {
+                format!("{}\r", l)
+            } else {
+                l.to_string()
+            }
+        })
+        .collect();
+    std::fs::write(&doc.file, new_lines.join("\n"))
+        .with_context(|| format!("writing {}", doc.file.display()))?;
+    let new_id = format!("{}#{}", note_id, item_hash(&path, idx + 1, &after));
+    match format {
+        OutputFormat::Plain => println!("{}:{}: {}", path, idx + 1, after.trim()),
+        _ => print_json(&json!({
+            "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+            "id": item_id,
+            "newId": new_id,
+            "path": path,
+            "line": idx + 1,
+            "before": before,
+            "after": after,
+        }))?,
+    }
+    Ok(())
+}
+
+pub fn run_kanban(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    format: &OutputFormat,
+    command: KanbanCommands,
+) -> Result<()> {
+    let KanbanCommands::Move(args) = command;
+    let to = args.to.trim();
+    if to.is_empty() {
+        return Err(anyhow!("--to must not be empty"));
+    }
+    let (note_id, hash) = match args.id.split_once('#') {
+        Some((n, h)) => (n, Some(h)),
+        None => (args.id.as_str(), None),
+    };
+    let docs = load_docs(cfg, cfg_path)?;
+    let doc = find_note(&docs, note_id)?;
+    let path = doc.display_path();
+    let content = std::fs::read_to_string(&doc.file)
+        .with_context(|| format!("reading {}", doc.file.display()))?;
+    let mut fm =
+        FrontMatterDoc::parse(&content).ok_or_else(|| anyhow!("{} has no front matter", path))?;
+    let from = fm
+        .get("kanban_status")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .ok_or_else(|| anyhow!("{} is not a kanban card (no kanban_status)", note_id))?;
+    let card_hash = |status: &str| hex_u64(fnv1a_64(&format!("{}:{}:{}", path, note_id, status)));
+    if let Some(h) = hash {
+        if h != card_hash(&from) {
+            return Err(anyhow!(
+                "conflict: card {} changed since it was listed (kanban_status is now '{}')",
+                args.id,
+                from
+            ));
+        }
+    }
+    if from != to {
+        // Only the kanban_status entry is rewritten; the rest of the note keeps its text.
+        fm.insert("kanban_status", serde_yaml::Value::String(to.to_string()));
+        std::fs::write(&doc.file, fm.render()?)
+            .with_context(|| format!("writing {}", doc.file.display()))?;
+    }
+    match format {
+        OutputFormat::Plain => println!("{}: {} -> {}", note_id, from, to),
+        _ => print_json(&json!({
+            "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+            "noteId": note_id,
+            "newId": format!("{}#{}", note_id, card_hash(to)),
+            "path": path,
+            "from": from,
+            "to": to,
+        }))?,
+    }
+    Ok(())
+}
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 
 use crate::cli::OutputFormat;
 use crate::commands::output::print_json;
+use crate::config::lua::load_overlay_state_checked;
 use crate::config::Config;
 
 fn info_json(cfg: &Config, cfg_path: &Option<PathBuf>) -> serde_json::Value {
@@ -31,8 +32,27 @@ fn info_json(cfg: &Config, cfg_path: &Option<PathBuf>) -> serde_json::Value {
         "deprecated": false,
     });
 
+    // Loading the overlays surfaces syntax and top-level runtime errors.
+    let (_, load_errors) = load_overlay_state_checked(&cfg.overlays, &cfg.lua);
+    let diagnostics: Vec<serde_json::Value> = load_errors
+        .iter()
+        .map(|d| {
+            serde_json::json!({
+                "path": d.path.display().to_string(),
+                "line": d.line,
+                "code": d.code,
+                "msg": d.message,
+            })
+        })
+        .collect();
     let overlays = serde_json::json!({
         "enabled": cfg.overlays.enabled,
+        "sandbox": {
+            "capabilities": cfg.lua.capabilities,
+            "instructionLimit": cfg.lua.instruction_limit,
+            "memoryLimitMb": cfg.lua.memory_limit_mb,
+        },
+        "diagnostics": diagnostics,
         "repoPath": cfg
             .overlays
             .repo_path
@@ -116,6 +136,24 @@ pub fn run(cfg: &Config, cfg_path: &Option<PathBuf>, format: &OutputFormat) -> R
                     "Overlays: enabled={}, repo={}, user={}",
                     enabled, repo, user
                 );
+                for d in overlays
+                    .get("diagnostics")
+                    .and_then(|v| v.as_array())
+                    .into_iter()
+                    .flatten()
+                {
+                    let line = d["line"]
+                        .as_u64()
+                        .map(|l| format!(":{}", l))
+                        .unwrap_or_default();
+                    println!(
+                        "  {}{}: {}: {}",
+                        d["path"].as_str().unwrap_or(""),
+                        line,
+                        d["code"].as_str().unwrap_or(""),
+                        d["msg"].as_str().unwrap_or("")
+                    );
+                }
             }
         }
     }
@@ -0,0 +1,241 @@
+//! Overlay-registered subcommands (`cli-rag run <name>`) and output renderers
+//! (`search --render`, `graph --render`).
+
+use anyhow::{anyhow, Result};
+use mlua::{Function as LuaFunction, Lua, LuaSerdeExt, Table as LuaTable, Value as LuaValue};
+use std::rc::Rc;
+
+use super::graph::{graph_table, GraphData};
+use super::load_overlay;
+use crate::cli::OutputFormat;
+use crate::commands::output::{print_json, print_ndjson_value};
+use crate::commands::search_query::SearchQuery;
+use crate::config::lua::{call_hook, load_overlay_state, OverlayDiagnostic};
+use crate::config::Config;
+use crate::discovery::docs_with_source;
+use crate::model::AdrDoc;
+
+fn overlay_state(cfg: &Config) -> Option<Lua> {
+    if !cfg.overlays.enabled {
+        return None;
+    }
+    load_overlay_state(&cfg.overlays, &cfg.lua)
+}
+
+fn hook_error(cfg: &Config, e: &mlua::Error, context: String) -> anyhow::Error {
+    anyhow!(
+        "{}",
+        OverlayDiagnostic::from_error(e, &cfg.overlays).to_message(&context)
+    )
+}
+
+/// Function-valued keys of an overlay sub-table (`commands`, `renderers.<kind>`), sorted.
+fn function_names(tbl: &LuaTable) -> Vec<String> {
+    let mut names: Vec<String> = tbl
+        .clone()
+        .pairs::<String, LuaValue>()
+        .flatten()
+        .filter(|(_, v)| matches!(v, LuaValue::Function(_)))
+        .map(|(k, _)| k)
+        .collect();
+    names.sort();
+    names
+}
+
+/// Turn `--key value`, `--key=value`, `--flag` and positionals into a Lua table:
+/// named options as fields (flags are `true`), positionals in the array part.
+fn args_table<'lua>(lua: &'lua Lua, args: &[String]) -> mlua::Result<LuaTable<'lua>> {
+    let tbl = lua.create_table()?;
+    let mut it = args.iter().peekable();
+    while let Some(arg) = it.next() {
+        let Some(key) = arg.strip_prefix("--").filter(|k| !k.is_empty()) else {
+            tbl.push(arg.as_str())?;
+            continue;
+        };
+        if let Some((k, v)) = key.split_once('=') {
+            tbl.set(k, v)?;
+        } else if let Some(v) = it.next_if(|next| !next.starts_with("--")) {
+            tbl.set(key, v.as_str())?;
+        } else {
+            tbl.set(key, true)?;
+        }
+    }
+    Ok(tbl)
+}
+
+// Search filter option: a single string or a list of strings.
+fn filter_opt(opts: &Option<LuaTable>, key: &str) -> Option<Vec<String>> {
+    match opts.as_ref()?.get::<_, LuaValue>(key).ok()? {
+        LuaValue::String(s) => Some(vec![s.to_str().ok()?.to_string()]),
+        LuaValue::Table(t) => Some(t.sequence_values::<String>().flatten().collect()),
+        _ => None,
+    }
+}
+
+/// Context for commands and renderers: `luaApiVersion`, `graph`, `config`,
+/// `search(query, {kind=, schema=, status=, tag=})` and `get(id)` (with body).
+pub(super) fn command_ctx<'lua>(
+    lua: &'lua Lua,
+    cfg: &Config,
+    cfg_path: &Option<std::path::PathBuf>,
+    data: &Rc<GraphData>,
+) -> mlua::Result<LuaTable<'lua>> {
+    let ctx = lua.create_table()?;
+    ctx.set("luaApiVersion", 1)?;
+    ctx.set("graph", graph_table(lua, data)?)?;
+
+    let cfg_tbl = lua.create_table()?;
+    if let Some(path) = cfg_path.as_ref().and_then(|p| p.to_str()) {
+        cfg_tbl.set("path", path.to_string())?;
+    }
+    ctx.set("config", cfg_tbl)?;
+
+    let g = Rc::clone(data);
+    let cfg = cfg.clone();
+    ctx.set(
+        "search",
+        lua.create_function(move |lua, (query, opts): (String, Option<LuaTable>)| {
+            let parsed =
+                SearchQuery::parse(&query).map_err(|e| mlua::Error::RuntimeError(e.to_string()))?;
+            let results = crate::commands::search::collect_results(
+                &cfg,
+                g.docs(),
+                &parsed,
+                filter_opt(&opts, "kind"),
+                filter_opt(&opts, "schema"),
+                filter_opt(&opts, "status"),
+                filter_opt(&opts, "tag"),
+            );
+            lua.to_value(&results)
+        })?,
+    )?;
+
+    let g = Rc::clone(data);
+    ctx.set(
+        "get",
+        lua.create_function(move |lua, id: String| {
+            let Some(i) = g.index_of(&id) else {
+                return Ok(LuaValue::Nil);
+            };
+            let note = g.note(lua, i)?;
+            if let Some(body) = g.body(i) {
+                note.set("body", body)?;
+            }
+            Ok(LuaValue::Table(note))
+        })?,
+    )?;
+    Ok(ctx)
+}
+
+fn unknown(what: String, available: &[String]) -> anyhow::Error {
+    let list = if available.is_empty() {
+        "none".to_string()
+    } else {
+        available.join(", ")
+    };
+    anyhow!("unknown {} (available: {})", what, list)
+}
+
+fn ensure_newline(mut s: String) -> String {
+    if !s.is_empty() && !s.ends_with('\n') {
+        s.push('\n');
+    }
+    s
+}
+
+/// `cli-rag run [name] [args...]`: call `overlay.commands.<name>(args, ctx)`.
+/// A string result is printed as text; a table is emitted as JSON (NDJSON
+/// prints array items one per line). Without a name, lists the commands.
+pub fn run_overlay_command(
+    cfg: &Config,
+    cfg_path: &Option<std::path::PathBuf>,
+    format: &OutputFormat,
+    name: Option<String>,
+    args: &[String],
+) -> Result<()> {
+    let lua = overlay_state(cfg);
+    let commands = lua
+        .as_ref()
+        .and_then(load_overlay)
+        .and_then(|o| o.get::<_, LuaTable>("commands").ok());
+    let available = commands.as_ref().map(function_names).unwrap_or_default();
+    let Some(name) = name else {
+        match format {
+            OutputFormat::Json | OutputFormat::Ai | OutputFormat::Ndjson => {
+                print_json(&serde_json::json!({
+                    "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+                    "commands": available,
+                }))?;
+            }
+            OutputFormat::Plain => available.iter().for_each(|n| println!("{}", n)),
+        }
+        return Ok(());
+    };
+    let (Some(lua), Some(func)) = (
+        lua.as_ref(),
+        commands.and_then(|c| c.get::<_, LuaFunction>(name.as_str()).ok()),
+    ) else {
+        return Err(unknown(format!("overlay command '{}'", name), &available));
+    };
+    let (docs, _) = docs_with_source(cfg, cfg_path)?;
+    let data = Rc::new(GraphData::build(cfg, &docs));
+    let ctx = command_ctx(lua, cfg, cfg_path, &data)?;
+    let ret = call_hook::<_, LuaValue>(lua, &func, (args_table(lua, args)?, ctx))
+        .map_err(|e| hook_error(cfg, &e, format!("overlay command '{}' failed", name)))?;
+    match ret {
+        LuaValue::Nil => {}
+        LuaValue::String(s) => print!("{}", ensure_newline(s.to_str()?.to_string())),
+        other => {
+            let value: serde_json::Value = lua
+                .from_value(other)
+                .map_err(|e| anyhow!("overlay command '{}' returned invalid data: {}", name, e))?;
+            match (format, value) {
+                (OutputFormat::Ndjson, serde_json::Value::Array(items)) => {
+                    for item in &items {
+                        print_ndjson_value(item)?;
+                    }
+                }
+                (OutputFormat::Ndjson, value) => print_ndjson_value(&value)?,
+                (_, value) => print_json(&value)?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Render command output (`kind` is `graph` or `search`) with
+/// `overlay.renderers.<kind>.<name>(data, ctx)`, which must return a string.
+pub fn render_with_overlay(
+    cfg: &Config,
+    cfg_path: &Option<std::path::PathBuf>,
+    docs: &[AdrDoc],
+    kind: &str,
+    name: &str,
+    data: &serde_json::Value,
+) -> Result<String> {
+    let lua = overlay_state(cfg);
+    let renderers = lua
+        .as_ref()
+        .and_then(load_overlay)
+        .and_then(|o| o.get::<_, LuaTable>("renderers").ok())
+        .and_then(|r| r.get::<_, LuaTable>(kind).ok());
+    let available = renderers.as_ref().map(function_names).unwrap_or_default();
+    let (Some(lua), Some(func)) = (
+        lua.as_ref(),
+        renderers.and_then(|r| r.get::<_, LuaFunction>(name).ok()),
+    ) else {
+        return Err(unknown(format!("{} renderer '{}'", kind, name), &available));
+    };
+    let graph = Rc::new(GraphData::build(cfg, docs));
+    let ctx = command_ctx(lua, cfg, cfg_path, &graph)?;
+    let rendered = match call_hook::<_, LuaValue>(lua, &func, (lua.to_value(data)?, ctx)) {
+        Ok(LuaValue::String(s)) => Ok(ensure_newline(s.to_str()?.to_string())),
+        Ok(_) => Err(anyhow!("{} renderer '{}' must return a string", kind, name)),
+        Err(e) => Err(hook_error(
+            cfg,
+            &e,
+            format!("{} renderer '{}' failed", kind, name),
+        )),
+    };
+    rendered
+}
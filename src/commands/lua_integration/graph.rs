@@ -0,0 +1,251 @@
+//! Read-only `ctx.graph` API for Lua hooks, backed by the loaded notes.
+//!
+//! Every call returns fresh tables, so scripts cannot mutate the corpus.
+
+use mlua::{Lua, Table as LuaTable, Value as LuaValue};
+use std::cell::OnceCell;
+use std::collections::{BTreeSet, HashMap};
+use std::rc::Rc;
+
+use super::note::note_table;
+use crate::config::{build_schema_sets, Config};
+use crate::frontmatter::string_list;
+use crate::index::index_entry_for;
+use crate::model::AdrDoc;
+use crate::wikilink::LinkResolver;
+
+/// Front matter edge kinds the unified index already records.
+const FM_EDGE_KINDS: [&str; 3] = ["depends_on", "supersedes", "superseded_by"];
+
+struct Edge {
+    from: usize,
+    to: String,
+    kind: String,
+}
+
+pub(super) struct GraphData {
+    docs: Vec<AdrDoc>,
+    schemas: Vec<String>,
+    /// Note texts for `fixtures.ctx` notes, which exist only in memory.
+    bodies: Option<Vec<String>>,
+    by_id: HashMap<String, usize>,
+    edge_kinds: BTreeSet<String>,
+    edges: OnceCell<Vec<Edge>>,
+}
+
+// Schema-declared edge kinds (`[schema.validate.edges.<kind>]`) are front matter fields too.
+fn custom_edge_kinds(cfg: &Config) -> BTreeSet<String> {
+    cfg.schema
+        .iter()
+        .filter_map(|sc| sc.validate.as_ref()?.edges.as_ref())
+        .flat_map(|e| e.kinds.keys().cloned())
+        .filter(|k| !FM_EDGE_KINDS.contains(&k.as_str()))
+        .collect()
+}
+
+impl GraphData {
+    pub(super) fn build(cfg: &Config, docs: &[AdrDoc]) -> GraphData {
+        let schema_sets = build_schema_sets(cfg);
+        let schemas: Vec<String> = docs
+            .iter()
+            .map(|d| {
+                let fname = d.file.file_name().and_then(|s| s.to_str()).unwrap_or("");
+                schema_sets
+                    .iter()
+                    .find(|(_, set)| set.is_match(fname))
+                    .map(|(sc, _)| sc.name.clone())
+                    .unwrap_or_else(|| "UNKNOWN".into())
+            })
+            .collect();
+        GraphData::new(cfg, docs.to_vec(), schemas, None)
+    }
+
+    /// A graph over in-memory notes (`fixtures.ctx`), with their full texts.
+    pub(super) fn from_memory(
+        cfg: &Config,
+        docs: Vec<AdrDoc>,
+        schemas: Vec<String>,
+        bodies: Vec<String>,
+    ) -> GraphData {
+        GraphData::new(cfg, docs, schemas, Some(bodies))
+    }
+
+    fn new(
+        cfg: &Config,
+        docs: Vec<AdrDoc>,
+        schemas: Vec<String>,
+        bodies: Option<Vec<String>>,
+    ) -> GraphData {
+        let mut by_id = HashMap::new();
+        for (i, d) in docs.iter().enumerate() {
+            if let Some(ref id) = d.id {
+                by_id.entry(id.clone()).or_insert(i);
+            }
+        }
+        GraphData {
+            docs,
+            schemas,
+            bodies,
+            by_id,
+            edge_kinds: custom_edge_kinds(cfg),
+            edges: OnceCell::new(),
+        }
+    }
+
+    /// Full text of note `idx`, from memory or disk.
+    pub(super) fn body(&self, idx: usize) -> Option<String> {
+        match &self.bodies {
+            Some(bodies) => bodies.get(idx).cloned(),
+            None => std::fs::read_to_string(&self.docs[idx].file).ok(),
+        }
+    }
+
+    // Edges need every body for mentions and links, so they are only built
+    // on first use, from the same entries as the unified index.
+    fn edges(&self) -> &[Edge] {
+        self.edges.get_or_init(|| {
+            let links = LinkResolver::new(&self.docs);
+            let mut edges = Vec::new();
+            for (i, d) in self.docs.iter().enumerate() {
+                let content = self.body(i).unwrap_or_default();
+                let schema = self.schemas[i].clone();
+                let entry = index_entry_for(d, schema, d.display_path(), &content, &links);
+                for e in entry.iter().flat_map(|entry| &entry.edges) {
+                    if let (Some(to), Some(kind)) = (e["to"].as_str(), e["kind"].as_str()) {
+                        edges.push(Edge {
+                            from: i,
+                            to: to.to_string(),
+                            kind: kind.to_string(),
+                        });
+                    }
+                }
+                for kind in &self.edge_kinds {
+                    for to in d.fm.get(kind).map(string_list).unwrap_or_default() {
+                        edges.push(Edge {
+                            from: i,
+                            to,
+                            kind: kind.clone(),
+                        });
+                    }
+                }
+            }
+            edges
+        })
+    }
+
+    pub(super) fn docs(&self) -> &[AdrDoc] {
+        &self.docs
+    }
+
+    pub(super) fn index_of(&self, id: &str) -> Option<usize> {
+        self.by_id.get(id).copied()
+    }
+
+    pub(super) fn note<'lua>(&self, lua: &'lua Lua, idx: usize) -> mlua::Result<LuaTable<'lua>> {
+        note_table(lua, &self.docs[idx], &self.schemas[idx])
+    }
+
+    /// Note tables for every note, in corpus order.
+    pub(super) fn all_notes<'lua>(&self, lua: &'lua Lua) -> mlua::Result<LuaTable<'lua>> {
+        let out = lua.create_table()?;
+        for i in 0..self.docs.len() {
+            out.push(self.note(lua, i)?)?;
+        }
+        Ok(out)
+    }
+
+    fn matches(&self, idx: usize, filter: &HashMap<String, String>) -> bool {
+        let d = &self.docs[idx];
+        filter.iter().all(|(key, want)| match key.as_str() {
+            "schema" => &self.schemas[idx] == want,
+            "status" => d.status.as_deref() == Some(want.as_str()),
+            "tag" => d.tags.iter().any(|t| t == want),
+            "group" => d.groups.iter().any(|g| g == want),
+            _ => false,
+        })
+    }
+}
+
+/// Build the `ctx.graph` table: `get(id)`, `dependents(id)`,
+/// `neighbors(id, kind?)` and `find({schema=, status=, tag=, group=})`.
+pub(super) fn graph_table<'lua>(
+    lua: &'lua Lua,
+    data: &Rc<GraphData>,
+) -> mlua::Result<LuaTable<'lua>> {
+    let graph = lua.create_table()?;
+
+    let g = Rc::clone(data);
+    graph.set(
+        "get",
+        lua.create_function(move |lua, id: String| match g.by_id.get(&id) {
+            Some(&i) => Ok(LuaValue::Table(g.note(lua, i)?)),
+            None => Ok(LuaValue::Nil),
+        })?,
+    )?;
+
+    let g = Rc::clone(data);
+    graph.set(
+        "dependents",
+        lua.create_function(move |lua, id: String| {
+            let out = lua.create_table()?;
+            for e in g
+                .edges()
+                .iter()
+                .filter(|e| e.kind == "depends_on" && e.to == id)
+            {
+                out.push(g.note(lua, e.from)?)?;
+            }
+            Ok(out)
+        })?,
+    )?;
+
+    let g = Rc::clone(data);
+    graph.set(
+        "neighbors",
+        lua.create_function(move |lua, (id, kind): (String, Option<String>)| {
+            let out = lua.create_table()?;
+            let Some(&me) = g.by_id.get(&id) else {
+                return Ok(out);
+            };
+            let mut seen = BTreeSet::new();
+            for e in g.edges() {
+                if kind.as_ref().is_some_and(|k| k != &e.kind) {
+                    continue;
+                }
+                let (other, direction) = if e.from == me {
+                    match g.by_id.get(&e.to) {
+                        Some(&t) => (t, "out"),
+                        None => continue,
+                    }
+                } else if e.to == id {
+                    (e.from, "in")
+                } else {
+                    continue;
+                };
+                if !seen.insert((other, e.kind.clone(), direction)) {
+                    continue;
+                }
+                let note = g.note(lua, other)?;
+                note.set("edge", e.kind.clone())?;
+                note.set("direction", direction)?;
+                out.push(note)?;
+            }
+            Ok(out)
+        })?,
+    )?;
+
+    let g = Rc::clone(data);
+    graph.set(
+        "find",
+        lua.create_function(move |lua, filter: Option<HashMap<String, String>>| {
+            let filter = filter.unwrap_or_default();
+            let out = lua.create_table()?;
+            for i in (0..g.docs.len()).filter(|&i| g.matches(i, &filter)) {
+                out.push(g.note(lua, i)?)?;
+            }
+            Ok(out)
+        })?,
+    )?;
+
+    Ok(graph)
+}
@@ -0,0 +1,317 @@
+use crate::config::lua::{
+    call_hook, load_overlay_state, load_overlay_state_checked, OverlayDiagnostic,
+};
+use crate::config::Config;
+use crate::model::AdrDoc;
+use chrono::Utc;
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use mlua::{Function as LuaFunction, Lua, Table as LuaTable, Value as LuaValue};
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+mod commands;
+mod graph;
+mod note;
+mod spec;
+
+pub use commands::{render_with_overlay, run_overlay_command};
+use graph::{graph_table, GraphData};
+pub use spec::run_lua_tests;
+
+fn load_overlay(lua: &Lua) -> Option<LuaTable<'_>> {
+    let globals = lua.globals();
+    globals.get::<_, LuaTable>("overlay").ok()
+}
+
+/// Append `{diagnostics = {...}}` returned by a hook. `prefix_path` attributes
+/// each entry to a note (`id` or `path` field) for corpus-wide hooks.
+fn collect_diagnostics(
+    ret: LuaValue,
+    prefix_path: Option<&HashMap<String, String>>,
+    errors: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) {
+    let LuaValue::Table(t) = ret else { return };
+    let Ok(LuaValue::Table(arr)) = t.get::<_, LuaValue>("diagnostics") else {
+        return;
+    };
+    for dv in arr.sequence_values::<LuaTable>().flatten() {
+        let sev = dv
+            .get::<_, String>("severity")
+            .unwrap_or_else(|_| "warning".into());
+        let code = dv.get::<_, String>("code").unwrap_or_else(|_| "LUA".into());
+        let msg = dv.get::<_, String>("msg").unwrap_or_else(|_| "".into());
+        let line = dv.get::<_, i64>("line").ok();
+        let mut text = if let Some(l) = line {
+            format!("LUA[{}]: {} (line {})", code, msg, l)
+        } else {
+            format!("LUA[{}]: {}", code, msg)
+        };
+        if let Some(paths) = prefix_path {
+            let path = dv.get::<_, String>("path").ok().or_else(|| {
+                let id = dv.get::<_, String>("id").ok()?;
+                paths.get(&id).cloned()
+            });
+            if let Some(path) = path {
+                text = format!("{}: {}", path, text);
+            }
+        }
+        if sev == "error" {
+            errors.push(text);
+        } else {
+            warnings.push(text);
+        }
+    }
+}
+
+pub fn lua_validate_augment(
+    cfg: &Config,
+    docs: &[AdrDoc],
+    errors: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) {
+    if !cfg.overlays.enabled {
+        return;
+    }
+    let (lua, load_errors) = load_overlay_state_checked(&cfg.overlays, &cfg.lua);
+    errors.extend(load_errors.iter().map(|d| d.to_message("")));
+    let Some(lua) = lua else {
+        return;
+    };
+    let Some(overlay) = load_overlay(&lua) else {
+        return;
+    };
+    let per_note = overlay.get::<_, LuaFunction>("validate").ok();
+    let corpus = overlay.get::<_, LuaFunction>("validate_all").ok();
+    if per_note.is_none() && corpus.is_none() {
+        return;
+    }
+    let data = Rc::new(GraphData::build(cfg, docs));
+    let hook_error = |e: &mlua::Error, context: String| {
+        OverlayDiagnostic::from_error(e, &cfg.overlays).to_message(&context)
+    };
+    let ctx = match validate_ctx(&lua, &data) {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            errors.push(hook_error(
+                &e,
+                "building the validate context failed".into(),
+            ));
+            return;
+        }
+    };
+    if let Some(func) = per_note {
+        for (i, d) in docs.iter().enumerate() {
+            let Ok(note) = data.note(&lua, i) else {
+                continue;
+            };
+            if let Some(body) = data.body(i) {
+                let _ = note.set("body", body);
+            }
+            match call_hook::<_, LuaValue>(&lua, &func, (note, ctx.clone())) {
+                Ok(ret) => collect_diagnostics(ret, None, errors, warnings),
+                Err(e) => errors.push(hook_error(
+                    &e,
+                    format!("validate failed for {}", d.display_path()),
+                )),
+            }
+        }
+    }
+    if let Some(func) = corpus {
+        let paths: HashMap<String, String> = docs
+            .iter()
+            .filter_map(|d| Some((d.id.clone()?, d.display_path())))
+            .collect();
+        if let Ok(notes) = data.all_notes(&lua) {
+            match call_hook::<_, LuaValue>(&lua, &func, (notes, ctx)) {
+                Ok(ret) => collect_diagnostics(ret, Some(&paths), errors, warnings),
+                Err(e) => errors.push(hook_error(&e, "validate_all failed".into())),
+            }
+        }
+    }
+}
+
+fn validate_ctx<'lua>(lua: &'lua Lua, data: &Rc<GraphData>) -> mlua::Result<LuaTable<'lua>> {
+    let ctx = lua.create_table()?;
+    ctx.set("luaApiVersion", 1)?;
+    ctx.set("graph", graph_table(lua, data)?)?;
+    Ok(ctx)
+}
+
+/// `ctx.util`: case conversion helpers.
+fn util_table(lua: &Lua) -> mlua::Result<LuaTable<'_>> {
+    let util = lua.create_table()?;
+    util.set(
+        "kebab_case",
+        lua.create_function(|_, s: String| Ok(s.to_kebab_case()))?,
+    )?;
+    util.set(
+        "snake_case",
+        lua.create_function(|_, s: String| Ok(s.to_snake_case()))?,
+    )?;
+    util.set(
+        "pascal_case",
+        lua.create_function(|_, s: String| Ok(s.to_upper_camel_case()))?,
+    )?;
+    util.set(
+        "camel_case",
+        lua.create_function(|_, s: String| Ok(s.to_lower_camel_case()))?,
+    )?;
+    util.set(
+        "screaming_snake_case",
+        lua.create_function(|_, s: String| Ok(s.to_shouty_snake_case()))?,
+    )?;
+    Ok(util)
+}
+
+pub struct LuaNewArtifacts {
+    pub id_override: Option<String>,
+    pub frontmatter_overrides: Option<BTreeMap<String, serde_yaml::Value>>,
+    pub template_prompt: Option<String>,
+    pub template_note: Option<String>,
+}
+
+pub fn lua_new_hooks(
+    cfg: &Config,
+    cfg_path: &Option<std::path::PathBuf>,
+    schema: &str,
+    title: &str,
+    docs: &[AdrDoc],
+) -> LuaNewArtifacts {
+    if !cfg.overlays.enabled {
+        return LuaNewArtifacts {
+            id_override: None,
+            frontmatter_overrides: None,
+            template_prompt: None,
+            template_note: None,
+        };
+    }
+    if let Some(lua) = load_overlay_state(&cfg.overlays, &cfg.lua) {
+        if let Some(overlay) = load_overlay(&lua) {
+            let ctx = lua.create_table().unwrap();
+            let req = lua.create_table().unwrap();
+            let _ = req.set("title", title.to_string());
+            let _ = ctx.set("request", req);
+            let idx_tbl = lua.create_table().unwrap();
+            let docs_clone = docs.to_owned();
+            let func = lua
+                .create_function(move |_, prefix: String| {
+                    let re =
+                        regex::Regex::new(&format!(r"^{}-(\d+)$", regex::escape(&prefix))).unwrap();
+                    let mut max_n: i64 = 0;
+                    for d in &docs_clone {
+                        if let Some(ref oid) = d.id {
+                            if let Some(caps) = re.captures(oid) {
+                                if let Some(m) = caps.get(1) {
+                                    if let Ok(n) = m.as_str().parse::<i64>() {
+                                        if n > max_n {
+                                            max_n = n;
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Ok(max_n + 1)
+                })
+                .unwrap();
+            let _ = idx_tbl.set("next_numeric_id", func);
+            let _ = ctx.set("index", idx_tbl);
+            if let Ok(graph) = graph_table(&lua, &Rc::new(GraphData::build(cfg, docs))) {
+                let _ = ctx.set("graph", graph);
+            }
+            if let Ok(util) = util_table(&lua) {
+                let _ = ctx.set("util", util);
+            }
+
+            let clock_tbl = lua.create_table().unwrap();
+            let _ = clock_tbl.set(
+                "today_iso",
+                lua.create_function(|_, ()| Ok(Utc::now().date_naive().to_string()))
+                    .unwrap(),
+            );
+            let _ = clock_tbl.set(
+                "now_iso",
+                lua.create_function(|_, ()| Ok(Utc::now().to_rfc3339()))
+                    .unwrap(),
+            );
+            let _ = ctx.set("clock", clock_tbl);
+
+            let schema_tbl = lua.create_table().unwrap();
+            let _ = schema_tbl.set("name", schema.to_string());
+            let _ = ctx.set("schema", schema_tbl);
+
+            let cfg_tbl = lua.create_table().unwrap();
+            if let Some(cfg_version) = &cfg.config_version {
+                let _ = cfg_tbl.set("config_version", cfg_version.clone());
+            }
+            if let Some(path) = cfg_path.as_ref().and_then(|p| p.to_str()) {
+                let _ = cfg_tbl.set("path", path.to_string());
+            }
+            let _ = ctx.set("config", cfg_tbl);
+
+            // id_generator
+            let mut id_override = None;
+            if let Ok(f) = overlay.get::<_, LuaFunction>("id_generator") {
+                if let Ok(LuaValue::Table(t)) =
+                    f.call::<_, LuaValue>((schema.to_string(), ctx.clone()))
+                {
+                    if let Ok(newid) = t.get::<_, String>("id") {
+                        id_override = Some(newid);
+                    }
+                }
+            }
+            // FM overrides
+            let fm = render_fm(&overlay, &lua, schema, title, ctx.clone());
+
+            // template prompt/note
+            let template_prompt = if let Ok(func) = overlay.get::<_, LuaFunction>("template_prompt")
+            {
+                match func.call::<_, LuaValue>(ctx.clone()) {
+                    Ok(LuaValue::String(s)) => Some(s.to_str().unwrap_or_default().to_string()),
+                    Ok(LuaValue::Nil) | Err(_) => None,
+                    _ => None,
+                }
+            } else {
+                None
+            };
+
+            let template_note = if let Ok(func) = overlay.get::<_, LuaFunction>("template_note") {
+                match func.call::<_, LuaValue>(ctx.clone()) {
+                    Ok(LuaValue::String(s)) => Some(s.to_str().unwrap_or_default().to_string()),
+                    Ok(LuaValue::Nil) | Err(_) => None,
+                    _ => None,
+                }
+            } else {
+                None
+            };
+
+            return LuaNewArtifacts {
+                id_override,
+                frontmatter_overrides: fm,
+                template_prompt,
+                template_note,
+            };
+        }
+    }
+    LuaNewArtifacts {
+        id_override: None,
+        frontmatter_overrides: None,
+        template_prompt: None,
+        template_note: None,
+    }
+}
+
+fn render_fm(
+    overlay: &LuaTable,
+    lua: &Lua,
+    schema: &str,
+    title: &str,
+    ctx: LuaTable,
+) -> Option<BTreeMap<String, serde_yaml::Value>> {
+    let fm_func = overlay.get::<_, LuaFunction>("render_frontmatter").ok()?;
+    match fm_func.call::<_, LuaValue>((schema.to_string(), title.to_string(), ctx)) {
+        Ok(LuaValue::Table(t)) => note::frontmatter_from_lua(lua, t).ok(),
+        _ => None,
+    }
+}
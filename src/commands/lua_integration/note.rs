@@ -0,0 +1,116 @@
+use mlua::{Lua, LuaSerdeExt, SerializeOptions, Table as LuaTable, Value as LuaValue};
+use std::collections::BTreeMap;
+
+use crate::model::{heading_texts, AdrDoc};
+
+fn string_list<'lua>(lua: &'lua Lua, items: &[String]) -> mlua::Result<LuaTable<'lua>> {
+    lua.create_sequence_from(items.iter().cloned())
+}
+
+// YAML nulls become `nil` rather than `mlua::Value::NULL`, as hooks expect.
+fn lua_options() -> SerializeOptions {
+    SerializeOptions::new()
+        .serialize_none_to_null(false)
+        .serialize_unit_to_null(false)
+}
+
+/// Front matter as a Lua table with the full value tree: numbers, booleans,
+/// dates (as strings), nested tables and mixed arrays.
+pub(super) fn frontmatter_table<'lua>(
+    lua: &'lua Lua,
+    doc: &AdrDoc,
+) -> mlua::Result<LuaTable<'lua>> {
+    match lua.to_value_with(&doc.fm, lua_options())? {
+        LuaValue::Table(t) => Ok(t),
+        _ => lua.create_table(),
+    }
+}
+
+// Lua cannot tell `{}` from an empty list; front matter means a list far more often.
+fn empty_maps_to_lists(value: &mut serde_yaml::Value) {
+    match value {
+        serde_yaml::Value::Mapping(m) if m.is_empty() => {
+            *value = serde_yaml::Value::Sequence(Vec::new())
+        }
+        serde_yaml::Value::Mapping(m) => m.values_mut().for_each(empty_maps_to_lists),
+        serde_yaml::Value::Sequence(items) => items.iter_mut().for_each(empty_maps_to_lists),
+        _ => {}
+    }
+}
+
+/// Front matter returned by a hook (`render_frontmatter`), keeping nested values.
+pub(super) fn frontmatter_from_lua(
+    lua: &Lua,
+    table: LuaTable,
+) -> mlua::Result<BTreeMap<String, serde_yaml::Value>> {
+    let mut out = BTreeMap::new();
+    for pair in table.pairs::<LuaValue, LuaValue>() {
+        let (k, v) = pair?;
+        let LuaValue::String(key) = k else { continue };
+        let key = key.to_str()?.to_string();
+        if key.is_empty() {
+            continue;
+        }
+        let mut value: serde_yaml::Value = lua.from_value(v)?;
+        empty_maps_to_lists(&mut value);
+        out.insert(key, value);
+    }
+    Ok(out)
+}
+
+/// Note table handed to Lua hooks. Bodies are not included; hooks that need
+/// them get the note through `validate(note, ctx)`.
+pub(super) fn note_table<'lua>(
+    lua: &'lua Lua,
+    doc: &AdrDoc,
+    schema: &str,
+) -> mlua::Result<LuaTable<'lua>> {
+    let note = lua.create_table()?;
+    if let Some(ref id) = doc.id {
+        note.set("id", id.clone())?;
+    }
+    note.set("title", doc.title.clone())?;
+    note.set("schema", schema.to_string())?;
+    note.set("path", doc.display_path())?;
+    if let Some(ref status) = doc.status {
+        note.set("status", status.clone())?;
+    }
+    note.set("tags", string_list(lua, &doc.tags)?)?;
+    note.set("depends_on", string_list(lua, &doc.depends_on)?)?;
+    note.set("supersedes", string_list(lua, &doc.supersedes)?)?;
+    note.set("superseded_by", string_list(lua, &doc.superseded_by)?)?;
+    note.set("frontmatter", frontmatter_table(lua, doc)?)?;
+    Ok(note)
+}
+
+/// The note a `fixtures.note` table describes, as `(doc, schema, body)`;
+/// the inverse of [`note_table`] plus the body.
+pub(super) fn doc_from_note(lua: &Lua, note: LuaTable) -> mlua::Result<(AdrDoc, String, String)> {
+    let list = |key: &str| -> mlua::Result<Vec<String>> {
+        Ok(note.get::<_, Option<Vec<String>>>(key)?.unwrap_or_default())
+    };
+    let path: String = note.get("path")?;
+    let fm = match note.get::<_, Option<LuaTable>>("frontmatter")? {
+        Some(t) => frontmatter_from_lua(lua, t)?,
+        None => BTreeMap::new(),
+    };
+    let schema = note.get::<_, Option<String>>("schema")?;
+    let body = note.get::<_, Option<String>>("body")?.unwrap_or_default();
+    let doc = AdrDoc {
+        file: path.into(),
+        id: note.get("id")?,
+        title: note.get("title")?,
+        tags: list("tags")?,
+        status: note.get("status")?,
+        groups: list("groups")?,
+        depends_on: list("depends_on")?,
+        supersedes: list("supersedes")?,
+        superseded_by: list("superseded_by")?,
+        fm,
+        mtime: None,
+        size: None,
+        headings: Some(heading_texts(&body)),
+    };
+
+    Ok((doc, schema.unwrap_or_else(|| "UNKNOWN".into()), body))
+}
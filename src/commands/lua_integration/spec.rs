@@ -0,0 +1,248 @@
+//! `cli-rag lua test`: run `*_spec.lua` files against the loaded overlays.
+
+use anyhow::Result;
+use globset::{Glob, GlobSetBuilder};
+use mlua::{Function as LuaFunction, Lua, Table as LuaTable};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use super::commands::command_ctx;
+use super::graph::GraphData;
+use super::note::doc_from_note;
+use super::util_table;
+use crate::cli::OutputFormat;
+use crate::commands::output::print_json;
+use crate::config::lua::{
+    call_hook, load_overlay_state_checked, sandboxed_state, OverlayDiagnostic,
+};
+use crate::config::Config;
+
+const PRELUDE: &str = include_str!("spec_prelude.lua");
+
+struct SpecResult {
+    file: String,
+    name: String,
+    error: Option<(String, Option<u32>)>,
+}
+
+/// Collect `*_spec.lua` files under `paths` (files are taken as given), skipping
+/// hidden directories and the config's `ignore_globs`.
+fn spec_files(cfg: &Config, root: &Path, paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut ignore = GlobSetBuilder::new();
+    for g in &cfg.ignore_globs {
+        if let Ok(glob) = Glob::new(g) {
+            ignore.add(glob);
+        }
+    }
+    let ignore = ignore.build().unwrap_or_else(|_| globset::GlobSet::empty());
+    let roots = if paths.is_empty() {
+        vec![root.to_path_buf()]
+    } else {
+        paths.to_vec()
+    };
+    let mut files = Vec::new();
+    for r in roots {
+        if r.is_file() {
+            files.push(r);
+            continue;
+        }
+        let walker = walkdir::WalkDir::new(&r)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !e.file_name().to_string_lossy().starts_with('.'));
+        for entry in walker.flatten() {
+            let p = entry.path();
+            let is_spec = p
+                .file_name()
+                .and_then(|s| s.to_str())
+                .is_some_and(|s| s.ends_with("_spec.lua"));
+            if entry.file_type().is_file() && is_spec && !ignore.is_match(p) {
+                files.push(p.to_path_buf());
+            }
+        }
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+// "chunk:12: message" -> ("message", Some(12)) when the chunk is the spec file.
+fn split_position(message: &str, chunk: &str) -> (String, Option<u32>) {
+    let Some(rest) = message
+        .find(&format!("{}:", chunk))
+        .map(|at| &message[at + chunk.len() + 1..])
+    else {
+        return (message.to_string(), None);
+    };
+    let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+    match digits.parse::<u32>() {
+        Ok(line) => {
+            let text = rest[digits.len()..].trim_start_matches(':').trim_start();
+            (text.to_string(), Some(line))
+        }
+        Err(_) => (message.to_string(), None),
+    }
+}
+
+fn first_line(err: &mlua::Error) -> String {
+    let text = err.to_string();
+    let line = text.lines().next().unwrap_or("").trim();
+    line.strip_prefix("runtime error: ")
+        .or_else(|| line.strip_prefix("syntax error: "))
+        .unwrap_or(line)
+        .to_string()
+}
+
+/// Fresh state for one spec file: overlays (when enabled), `ctx.util` and the prelude.
+fn spec_state(cfg: &Config) -> mlua::Result<(Lua, Vec<OverlayDiagnostic>)> {
+    let (lua, load_errors) = load_overlay_state_checked(&cfg.overlays, &cfg.lua);
+    let lua = match lua {
+        Some(lua) => lua,
+        None => {
+            let lua = sandboxed_state(&cfg.lua)?;
+            lua.globals().set("overlay", lua.create_table()?)?;
+            lua
+        }
+    };
+    lua.globals().set("__cli_rag_util", util_table(&lua)?)?;
+    // `fixtures.ctx` answers graph, search and get with the same code as real
+    // hooks, over the injected notes instead of the corpus.
+    let cfg = cfg.clone();
+    let fixture_ctx = lua.create_function(move |lua, notes: Vec<LuaTable>| {
+        let (mut docs, mut schemas, mut bodies) = (Vec::new(), Vec::new(), Vec::new());
+        for note in notes {
+            let (doc, schema, body) = doc_from_note(lua, note)?;
+            docs.push(doc);
+            schemas.push(schema);
+            bodies.push(body);
+        }
+        let data = Rc::new(GraphData::from_memory(&cfg, docs, schemas, bodies));
+        command_ctx(lua, &cfg, &None, &data)
+    })?;
+    lua.globals().set("__cli_rag_fixture_ctx", fixture_ctx)?;
+    lua.load(PRELUDE).set_name("=spec_prelude").exec()?;
+    Ok((lua, load_errors))
+}
+
+fn run_spec_file(
+    cfg: &Config,
+    file: &Path,
+    display: &str,
+    filter: Option<&str>,
+    results: &mut Vec<SpecResult>,
+) -> Result<Vec<OverlayDiagnostic>> {
+    let (lua, load_errors) = spec_state(cfg)?;
+    let failed = |name: &str, msg: (String, Option<u32>)| SpecResult {
+        file: display.to_string(),
+        name: name.to_string(),
+        error: Some(msg),
+    };
+    let code = std::fs::read_to_string(file)?;
+    let chunk = lua.load(&code).set_name(format!("={}", display));
+    if let Err(e) = chunk.exec() {
+        results.push(failed("(load)", split_position(&first_line(&e), display)));
+        return Ok(load_errors);
+    }
+    let tests: LuaTable = lua.globals().get("__cli_rag_tests")?;
+    for t in tests.sequence_values::<LuaTable>().flatten() {
+        let name: String = t.get("name")?;
+        if filter.is_some_and(|f| !name.contains(f)) {
+            continue;
+        }
+        let func: LuaFunction = t.get("fn")?;
+        results.push(match call_hook::<_, ()>(&lua, &func, ()) {
+            Ok(()) => SpecResult {
+                file: display.to_string(),
+                name,
+                error: None,
+            },
+            Err(e) => failed(&name, split_position(&first_line(&e), display)),
+        });
+    }
+    Ok(load_errors)
+}
+
+/// Run every spec file; exits with code 2 when a test fails.
+pub fn run_lua_tests(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    format: &OutputFormat,
+    paths: Vec<PathBuf>,
+    filter: Option<String>,
+) -> Result<()> {
+    let root = cfg_path
+        .as_ref()
+        .and_then(|p| p.parent().map(|d| d.to_path_buf()))
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
+    let files = spec_files(cfg, &root, &paths);
+    let mut results = Vec::new();
+    let mut overlay_errors: Vec<OverlayDiagnostic> = Vec::new();
+    for file in &files {
+        let display = file
+            .strip_prefix(&root)
+            .unwrap_or(file)
+            .display()
+            .to_string();
+        let load_errors = run_spec_file(cfg, file, &display, filter.as_deref(), &mut results)?;
+        if overlay_errors.is_empty() {
+            overlay_errors = load_errors;
+        }
+    }
+    // Overlays that fail to load fail the run even if every spec passes.
+    for d in &overlay_errors {
+        results.insert(
+            0,
+            SpecResult {
+                file: d.path.display().to_string(),
+                name: format!("(overlay {})", d.code),
+                error: Some((d.message.clone(), d.line)),
+            },
+        );
+    }
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    let passed = results.len() - failed;
+    match format {
+        OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Ai => {
+            let items: Vec<serde_json::Value> = results
+                .iter()
+                .map(|r| {
+                    let mut v = serde_json::json!({"file": r.file, "name": r.name, "ok": r.error.is_none()});
+                    if let Some((msg, line)) = &r.error {
+                        v["msg"] = serde_json::json!(msg);
+                        if let Some(line) = line {
+                            v["line"] = serde_json::json!(line);
+                        }
+                    }
+                    v
+                })
+                .collect();
+            print_json(&serde_json::json!({
+                "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+                "ok": failed == 0,
+                "files": files.len(),
+                "passed": passed,
+                "failed": failed,
+                "results": items,
+            }))?;
+        }
+        OutputFormat::Plain => {
+            if files.is_empty() {
+                eprintln!("No *_spec.lua files found under {}", root.display());
+            }
+            for r in &results {
+                match &r.error {
+                    None => println!("ok    {}  {}", r.file, r.name),
+                    Some((msg, line)) => {
+                        let at = line.map(|l| format!(":{}", l)).unwrap_or_default();
+                        println!("FAIL  {}{}  {}", r.file, at, r.name);
+                        println!("      {}", msg);
+                    }
+                }
+            }
+            println!("{} passed, {} failed", passed, failed);
+        }
+    }
+    if failed > 0 {
+        std::process::exit(2);
+    }
+    Ok(())
+}
@@ -0,0 +1,193 @@
+-- Harness for `cli-rag lua test`: describe/it, assertions and fixtures.
+-- Loaded before each *_spec.lua file; `overlay` holds the merged overlay table.
+
+local tests = {}
+local stack = {}
+__cli_rag_tests = tests
+
+function describe(name, fn)
+  table.insert(stack, name)
+  fn()
+  table.remove(stack)
+end
+
+function it(name, fn)
+  local parts = {}
+  for _, s in ipairs(stack) do
+    table.insert(parts, s)
+  end
+  table.insert(parts, name)
+  table.insert(tests, { name = table.concat(parts, " > "), fn = fn })
+end
+
+local function fmt(v)
+  if type(v) == "string" then
+    return string.format("%q", v)
+  end
+  if type(v) ~= "table" then
+    return tostring(v)
+  end
+  local out, n = {}, #v
+  for i = 1, n do
+    table.insert(out, fmt(v[i]))
+  end
+  local keys = {}
+  for k in pairs(v) do
+    if not (math.type(k) == "integer" and k >= 1 and k <= n) then
+      table.insert(keys, k)
+    end
+  end
+  table.sort(keys, function(a, b)
+    return tostring(a) < tostring(b)
+  end)
+  for _, k in ipairs(keys) do
+    table.insert(out, tostring(k) .. " = " .. fmt(v[k]))
+  end
+  return "{ " .. table.concat(out, ", ") .. " }"
+end
+
+local function deep_equal(a, b)
+  if a == b then
+    return true
+  end
+  if type(a) ~= "table" or type(b) ~= "table" then
+    return false
+  end
+  for k, v in pairs(a) do
+    if not deep_equal(v, b[k]) then
+      return false
+    end
+  end
+  for k in pairs(b) do
+    if a[k] == nil then
+      return false
+    end
+  end
+  return true
+end
+
+-- Level 3 points the error at the spec line that called the assertion.
+local function fail(msg, note)
+  if note then
+    msg = note .. ": " .. msg
+  end
+  error(msg, 3)
+end
+
+local builtin_assert = assert
+assert = setmetatable({
+  equal = function(actual, expected, note)
+    if actual ~= expected then
+      fail("expected " .. fmt(expected) .. ", got " .. fmt(actual), note)
+    end
+  end,
+  same = function(actual, expected, note)
+    if not deep_equal(actual, expected) then
+      fail("expected " .. fmt(expected) .. ", got " .. fmt(actual), note)
+    end
+  end,
+  truthy = function(v, note)
+    if not v then
+      fail("expected a truthy value, got " .. fmt(v), note)
+    end
+  end,
+  falsy = function(v, note)
+    if v then
+      fail("expected a falsy value, got " .. fmt(v), note)
+    end
+  end,
+  matches = function(s, pattern, note)
+    if type(s) ~= "string" or not s:find(pattern) then
+      fail(fmt(s) .. " does not match " .. fmt(pattern), note)
+    end
+  end,
+  errors = function(fn, pattern, note)
+    local ok, err = pcall(fn)
+    if ok then
+      fail("expected an error", note)
+    end
+    if pattern and not tostring(err):find(pattern) then
+      fail("error " .. fmt(tostring(err)) .. " does not match " .. fmt(pattern), note)
+    end
+  end,
+  -- Find the diagnostic with `code` in a hook result and return it.
+  diagnostic = function(ret, code, note)
+    for _, d in ipairs((ret or {}).diagnostics or {}) do
+      if d.code == code then
+        return d
+      end
+    end
+    fail("no diagnostic with code " .. fmt(code) .. " in " .. fmt(ret), note)
+  end,
+  no_diagnostics = function(ret, note)
+    local diags = (ret or {}).diagnostics or {}
+    if #diags > 0 then
+      fail("expected no diagnostics, got " .. fmt(diags), note)
+    end
+  end,
+}, {
+  __call = function(_, ...)
+    return builtin_assert(...)
+  end,
+})
+
+fixtures = {}
+
+-- A note table shaped like the ones hooks receive, with defaults filled in.
+function fixtures.note(t)
+  t = t or {}
+  local id = t.id
+  return {
+    id = id,
+    title = t.title or id or "Untitled",
+    schema = t.schema or (id and id:match("^(%a+)%-")) or "UNKNOWN",
+    path = t.path or ((id or "note") .. ".md"),
+    status = t.status,
+    tags = t.tags or {},
+    groups = t.groups or {},
+    depends_on = t.depends_on or {},
+    supersedes = t.supersedes or {},
+    superseded_by = t.superseded_by or {},
+    frontmatter = t.frontmatter or {},
+    body = t.body or "",
+  }
+end
+
+-- A fake ctx over injected notes: pinned clock and index here; graph, search
+-- and get come from the Rust implementation run over the injected notes.
+function fixtures.ctx(t)
+  t = t or {}
+  local notes = {}
+  for _, n in ipairs(t.notes or {}) do
+    table.insert(notes, fixtures.note(n))
+  end
+  local today = t.today or "2025-01-01"
+  local now = t.now or (today .. "T00:00:00+00:00")
+
+  local ctx = __cli_rag_fixture_ctx(notes)
+  ctx.util = __cli_rag_util
+  ctx.clock = {
+    today_iso = function()
+      return today
+    end,
+    now_iso = function()
+      return now
+    end,
+  }
+  ctx.index = {
+    next_numeric_id = function(prefix)
+      local max = 0
+      for _, n in ipairs(notes) do
+        local num = n.id and n.id:match("^" .. prefix:gsub("%p", "%%%0") .. "%-(%d+)$")
+        if num and tonumber(num) > max then
+          max = tonumber(num)
+        end
+      end
+      return max + 1
+    end,
+  }
+  ctx.schema = { name = t.schema or "UNKNOWN" }
+  ctx.request = { title = t.title }
+  ctx.config = t.config or {}
+  return ctx
+end
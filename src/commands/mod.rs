@@ -1,19 +1,31 @@
+pub mod agenda;
 pub mod ai_index_apply;
 pub mod ai_index_plan;
 pub mod ai_new;
+pub mod backlinks_cmd;
 pub mod cluster;
 pub mod completions;
+pub mod config_cmd;
 pub mod get;
+pub mod get_json;
 pub mod graph;
+pub mod gtd_cmd;
 pub mod info;
 pub mod init;
 pub mod init_support;
 pub mod lua_integration;
+pub mod new_defaults;
 pub mod new_helpers;
 pub mod new_legacy;
 pub mod output;
 pub mod path;
+pub mod review_queue;
+pub mod schema_export;
 pub mod search;
+pub mod search_fuzzy;
 pub mod search_gtd;
+pub mod search_query;
 pub mod validate_cmd;
+pub mod validate_fix;
+pub mod view;
 pub mod watch_cmd;
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use serde_yaml::{Mapping, Value};
+
+use crate::commands::new_helpers::{render_text_with_vars, TemplateVars};
+use crate::config::SchemaCfg;
+use crate::frontmatter::{toml_to_yaml_with, FrontMatterDoc};
+
+/// Render `[schema.new.defaults]` for one note. String values (including those
+/// nested in arrays/tables) go through the same token substitution as templates.
+/// `id` is never defaulted; it always comes from the id generator.
+pub fn render_schema_defaults(sc: Option<&SchemaCfg>, vars: &TemplateVars) -> Mapping {
+    let mut out = Mapping::new();
+    let Some(defaults) = sc.and_then(|s| s.new.as_ref()).map(|n| &n.defaults) else {
+        return out;
+    };
+    for (key, value) in defaults {
+        if key == "id" {
+            continue;
+        }
+        out.insert(
+            Value::String(key.clone()),
+            toml_to_yaml_with(value, &|s| render_text_with_vars(s, vars)),
+        );
+    }
+    out
+}
+
+// True when the raw template writes `key` itself in its front matter block, as
+// opposed to getting it from the generated `{{frontmatter}}` placeholder.
+fn template_sets_key(template: &str, key: &str) -> bool {
+    let norm = template.replace("\r\n", "\n");
+    let Some(delim) = ["---", "+++"]
+        .into_iter()
+        .find(|d| norm.starts_with(&format!("{}\n", d)))
+    else {
+        return false;
+    };
+    norm.lines().skip(1).take_while(|l| *l != delim).any(|l| {
+        l.strip_prefix(key)
+            .map(|rest| rest.trim_start().starts_with([':', '=']))
+            .unwrap_or(false)
+    })
+}
+
+/// Apply schema defaults to a rendered note. Keys the template spells out keep
+/// their value; defaults replace the generic `{{frontmatter}}` placeholders
+/// (`status: draft`, empty lists) and fill anything still missing. Notes
+/// without a front matter block are returned unchanged.
+pub fn apply_schema_defaults(
+    note: String,
+    template: &str,
+    sc: Option<&SchemaCfg>,
+    vars: &TemplateVars,
+) -> Result<String> {
+    let defaults = render_schema_defaults(sc, vars);
+    if defaults.is_empty() {
+        return Ok(note);
+    }
+    let Some(mut doc) = FrontMatterDoc::parse(&note) else {
+        return Ok(note);
+    };
+    let mut changed = false;
+    for (key, value) in defaults {
+        let Some(name) = key.as_str() else { continue };
+        if template_sets_key(template, name) || doc.get(name) == Some(&value) {
+            continue;
+        }
+        doc.insert(name, value);
+        changed = true;
+    }
+    if changed {
+        doc.render()
+    } else {
+        Ok(note)
+    }
+}
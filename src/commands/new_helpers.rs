@@ -2,7 +2,9 @@ use anyhow::{anyhow, Result};
 use chrono::Local;
 use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use regex::Regex;
+use std::fmt::Write;
 use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
 use uuid::Uuid;
 
 use crate::config::Config;
@@ -74,7 +76,58 @@ depends_on:",
     serde_yaml::to_string(&Value::Mapping(map)).unwrap_or_default()
 }
 
+// `{{now:FMT}}`, `{{now+90d}}`, `{{now-1w:FMT}}` and `{{env:VAR}}`. Offsets accept
+// d/w/h units and render as a plain date unless a format is given. An offset
+// outside chrono's range leaves the token as written.
+fn substitute_dynamic_tokens(s: &str, now: chrono::DateTime<Local>) -> String {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(r"\{\{\s*(now|env)(?:([+-])(\d+)([dwh]))?(?::([^}]*))?\s*\}\}").unwrap()
+    });
+    re.replace_all(s, |caps: &regex::Captures| {
+        let full = caps.get(0).map(|m| m.as_str()).unwrap_or("");
+        let arg = caps.get(5).map(|m| m.as_str().trim());
+        if &caps[1] == "env" {
+            return match (caps.get(2), arg) {
+                (None, Some(name)) if !name.is_empty() => std::env::var(name).unwrap_or_default(),
+                _ => full.to_string(),
+            };
+        }
+        let offset = caps.get(3).and_then(|m| m.as_str().parse::<i64>().ok());
+        if offset.is_none() && arg.is_none() {
+            return full.to_string();
+        }
+        let mut at = now;
+        if let Some(n) = offset {
+            let n = if &caps[2] == "-" { -n } else { n };
+            let delta = match &caps[4] {
+                "w" => chrono::Duration::try_weeks(n),
+                "h" => chrono::Duration::try_hours(n),
+                _ => chrono::Duration::try_days(n),
+            };
+            match delta.and_then(|d| at.checked_add_signed(d)) {
+                Some(shifted) => at = shifted,
+                None => return full.to_string(),
+            }
+        }
+        let pat = arg.unwrap_or("%Y-%m-%d");
+        let mut out = String::new();
+        // Invalid strftime patterns make `format` fail; leave the token as written.
+        match write!(
+            out,
+            "{}",
+            at.format(pat.trim_matches('"').trim_matches('\''))
+        ) {
+            Ok(()) => out,
+            Err(_) => full.to_string(),
+        }
+    })
+    .to_string()
+}
+
 fn substitute_tokens(s: &str, vars: &TemplateVars, now: chrono::DateTime<Local>) -> String {
+    let s = substitute_dynamic_tokens(s, now);
+    let s = s.as_str();
     let var_re = Regex::new(r"\{\{\s*([a-zA-Z0-9_.]+)\s*(?:\|\s*([^}]+))?\s*\}\}").unwrap();
     var_re
         .replace_all(s, |caps: &regex::Captures| {
@@ -4,6 +4,7 @@ use std::path::PathBuf;
 
 use crate::cli::OutputFormat;
 use crate::commands::ai_new::template_utils::{default_template, load_repo_template};
+use crate::commands::new_defaults::apply_schema_defaults;
 use crate::commands::new_helpers::{
     generate_initial_id, render_filename_template, render_template, resolve_destination_dir,
     TemplateVars,
@@ -70,7 +71,9 @@ pub fn run(
         schema: &schema,
         filename: &filename,
     };
-    let note_body = render_template(template_source, &vars);
+    let schema_cfg = cfg.schema.iter().find(|s| s.name == schema);
+    let note_body = render_template(template_source.clone(), &vars);
+    let note_body = apply_schema_defaults(note_body, &template_source, schema_cfg, &vars)?;
 
     let target = base_dir.join(filename);
     if let Some(parent) = target.parent() {
@@ -5,6 +5,7 @@ use crate::commands::output::print_json;
 use crate::config::{build_schema_sets, Config};
 use crate::discovery::docs_with_source;
 use crate::graph::bfs_path;
+use crate::wikilink::{read_wikilinks, LinkResolver};
 
 pub fn run(
     cfg: &Config,
@@ -25,6 +26,7 @@ pub fn run(
         }
     }
     let res = bfs_path(&from, &to, max_depth, &by_id);
+    let links = LinkResolver::new(&docs);
     match format {
         OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Ai => {
             // Build contract-shaped output per contracts/v1/cli/path.schema.json
@@ -70,17 +72,15 @@ pub fn run(
                             let mut locs: Vec<serde_json::Value> = Vec::new();
                             if kind == "mentions" {
                                 // Try a mentions scan in both files; record first hit each
-                                let mut push_loc = |file: &std::path::Path, needle: &str| {
-                                    if let Ok(content) = std::fs::read_to_string(file) {
-                                        for (i, l) in content.lines().enumerate() {
-                                            if l.contains(needle) {
-                                                locs.push(serde_json::json!({
-                                                    "path": file.display().to_string(),
-                                                    "line": i as i32 + 1
-                                                }));
-                                                break;
-                                            }
-                                        }
+                                let mut push_loc = |file: &std::path::Path, target: &str| {
+                                    let hit = read_wikilinks(file)
+                                        .into_iter()
+                                        .find(|l| links.resolve(&l.target) == Some(target));
+                                    if let Some(l) = hit {
+                                        locs.push(serde_json::json!({
+                                            "path": file.display().to_string(),
+                                            "line": l.line
+                                        }));
                                     }
                                 };
                                 if let (Some(daid), Some(dbid)) =
@@ -0,0 +1,79 @@
+use anyhow::Result;
+use std::path::PathBuf;
+
+use crate::cli::OutputFormat;
+use crate::commands::output::{print_json, print_ndjson_iter};
+use crate::config::Config;
+use crate::discovery::docs_with_source;
+use crate::util::normalize_display_path;
+use crate::validate::dates::{date_findings, DateFinding};
+
+fn item_json(f: &DateFinding) -> serde_json::Value {
+    serde_json::json!({
+        "id": f.id,
+        "title": f.title,
+        "path": normalize_display_path(&f.file),
+        "schema": f.schema,
+        "kind": f.issue.as_str(),
+        "field": f.field,
+        "date": f.date.to_string(),
+        "daysOverdue": f.days_overdue,
+        "severity": if f.is_error { "error" } else { "warning" },
+        "msg": f.message,
+    })
+}
+
+/// List notes whose review date passed, that expired, or that went stale,
+/// most overdue first.
+pub fn run(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    format: &OutputFormat,
+    schema: Option<Vec<String>>,
+) -> Result<()> {
+    let (docs, used_unified) = docs_with_source(cfg, cfg_path)?;
+    if !used_unified {
+        eprintln!("Note: unified index not found; falling back to per-base/scan. Consider `cli-rag validate`.");
+    }
+    let today = chrono::Local::now().date_naive();
+    let mut items: Vec<DateFinding> = date_findings(cfg, &docs, today)
+        .into_iter()
+        .filter(|f| f.issue.is_overdue())
+        .filter(|f| match &schema {
+            Some(names) => names.contains(&f.schema),
+            None => true,
+        })
+        .collect();
+    items.sort_by(|a, b| {
+        b.days_overdue
+            .cmp(&a.days_overdue)
+            .then_with(|| a.file.cmp(&b.file))
+    });
+    match format {
+        OutputFormat::Json | OutputFormat::Ai => {
+            let body = serde_json::json!({
+                "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+                "today": today.to_string(),
+                "items": items.iter().map(item_json).collect::<Vec<_>>(),
+            });
+            print_json(&body)?;
+        }
+        OutputFormat::Ndjson => {
+            print_ndjson_iter::<serde_json::Value, _>(items.iter().map(item_json))?;
+        }
+        OutputFormat::Plain => {
+            for f in &items {
+                println!(
+                    "{}d\t{}\t{}\t{}\t{} {}",
+                    f.days_overdue,
+                    f.issue.as_str(),
+                    f.id.as_deref().unwrap_or(""),
+                    normalize_display_path(&f.file),
+                    f.field,
+                    f.date
+                );
+            }
+        }
+    }
+    Ok(())
+}
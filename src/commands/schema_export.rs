@@ -0,0 +1,77 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cli::OutputFormat;
+use crate::commands::output::print_json;
+use crate::config::json_schema::{
+    config_json_schema, frontmatter_json_schema, frontmatter_schema_filename,
+    CONFIG_SCHEMA_FILENAME,
+};
+use crate::config::{Config, SchemaCfg};
+
+/// Export JSON Schemas for the config file and each note schema's front matter.
+///
+/// Without `--out` the schemas are printed; with `--out` each one is written to
+/// its own file so editors (e.g. yaml-language-server) can reference it.
+pub fn run(
+    cfg: &Config,
+    name: Option<String>,
+    out: Option<PathBuf>,
+    format: &OutputFormat,
+) -> Result<()> {
+    let selected: Vec<&SchemaCfg> = match name.as_deref() {
+        Some(n) => {
+            let sc = cfg
+                .schema
+                .iter()
+                .find(|s| s.name == n)
+                .ok_or_else(|| anyhow!("Unknown schema '{}'", n))?;
+            vec![sc]
+        }
+        None => cfg.schema.iter().collect(),
+    };
+
+    let Some(dir) = out else {
+        if let (Some(_), [sc]) = (&name, selected.as_slice()) {
+            return print_json(&frontmatter_json_schema(cfg, sc));
+        }
+        let schemas: serde_json::Map<String, serde_json::Value> = selected
+            .iter()
+            .map(|sc| (sc.name.clone(), frontmatter_json_schema(cfg, sc)))
+            .collect();
+        return print_json(&serde_json::json!({
+            "config": config_json_schema(),
+            "schemas": schemas,
+        }));
+    };
+
+    fs::create_dir_all(&dir).with_context(|| format!("creating {}", dir.display()))?;
+    let mut docs: Vec<(PathBuf, serde_json::Value)> = Vec::new();
+    if name.is_none() {
+        docs.push((dir.join(CONFIG_SCHEMA_FILENAME), config_json_schema()));
+    }
+    for sc in selected {
+        docs.push((
+            dir.join(frontmatter_schema_filename(&sc.name)),
+            frontmatter_json_schema(cfg, sc),
+        ));
+    }
+    let mut written = Vec::new();
+    for (path, doc) in docs {
+        fs::write(&path, serde_json::to_string_pretty(&doc)? + "\n")
+            .with_context(|| format!("writing {}", path.display()))?;
+        written.push(path.display().to_string());
+    }
+    match format {
+        OutputFormat::Json | OutputFormat::Ai | OutputFormat::Ndjson => {
+            print_json(&serde_json::json!({ "written": written }))?;
+        }
+        OutputFormat::Plain => {
+            for p in &written {
+                println!("Wrote {}", p);
+            }
+        }
+    }
+    Ok(())
+}
@@ -2,9 +2,13 @@ use crate::cli::OutputFormat;
 use crate::commands::output::{print_json, print_ndjson_iter};
 use anyhow::Result;
 
+use crate::commands::lua_integration::render_with_overlay;
+use crate::commands::search_fuzzy::NoteText;
 use crate::commands::search_gtd::{fnv1a_64, hex_u64, map_rank_to_priority_score, parse_gtd_box};
+use crate::commands::search_query::SearchQuery;
 use crate::config::{build_schema_sets, Config};
 use crate::discovery::docs_with_source;
+use crate::model::{heading_texts, AdrDoc};
 
 #[allow(clippy::too_many_arguments)]
 pub fn run(
@@ -16,19 +20,87 @@ pub fn run(
     schema_filter: Option<Vec<String>>, // schema names
     status_filter: Option<Vec<String>>, // status strings
     tag_filter: Option<Vec<String>>,    // tags
+    render: Option<String>,
 ) -> Result<()> {
-    let q = query.to_lowercase();
+    let parsed = SearchQuery::parse(&query).map_err(|e| anyhow::anyhow!(e.render(&query)))?;
+    if render.is_none() {
+        let params = serde_json::json!({
+            "query": query,
+            "kind": kind,
+            "schema": schema_filter,
+            "status": status_filter,
+            "tag": tag_filter,
+        });
+        if let Some(body) = crate::daemon::request(cfg, cfg_path, "search", params) {
+            let results = body?["results"].as_array().cloned().unwrap_or_default();
+            return print_results(format, results);
+        }
+    }
     let (docs, used_unified) = docs_with_source(cfg, cfg_path)?;
     if !used_unified {
         eprintln!("Note: unified index not found; falling back to per-base/scan. Consider `cli-rag validate`.");
     }
-    let mut hits: Vec<&crate::model::AdrDoc> = Vec::new();
-    for d in &docs {
-        let id = d.id.clone().unwrap_or_default();
-        if id.to_lowercase().contains(&q) || d.title.to_lowercase().contains(&q) {
-            hits.push(d);
+    let enriched = collect_results(
+        cfg,
+        &docs,
+        &parsed,
+        kind,
+        schema_filter,
+        status_filter,
+        tag_filter,
+    );
+    if let Some(name) = render {
+        let body = serde_json::json!({
+            "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+            "results": enriched
+        });
+        print!(
+            "{}",
+            render_with_overlay(cfg, cfg_path, &docs, "search", &name, &body)?
+        );
+        return Ok(());
+    }
+    print_results(format, enriched)
+}
+
+fn print_results(format: &OutputFormat, enriched: Vec<serde_json::Value>) -> Result<()> {
+    match format {
+        OutputFormat::Json | OutputFormat::Ai => {
+            let body = serde_json::json!({
+                "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+                "results": enriched
+            });
+            print_json(&body)?;
+        }
+        OutputFormat::Ndjson => {
+            // For NDJSON, emit each result as a JSON object consistent with envelope items
+            print_ndjson_iter::<serde_json::Value, _>(enriched)?;
+        }
+        OutputFormat::Plain => {
+            for v in &enriched {
+                println!(
+                    "{}\t{}\t{}",
+                    v["id"].as_str().unwrap_or(""),
+                    v["title"].as_str().unwrap_or(""),
+                    v["path"].as_str().unwrap_or("")
+                );
+            }
         }
     }
+    Ok(())
+}
+
+/// Search results (notes, kanban and todo items) in output order, shaped per
+/// `contracts/v1/cli/search_result.schema.json`.
+pub fn collect_results(
+    cfg: &Config,
+    docs: &[AdrDoc],
+    query: &SearchQuery,
+    kind: Option<Vec<String>>,
+    schema_filter: Option<Vec<String>>,
+    status_filter: Option<Vec<String>>,
+    tag_filter: Option<Vec<String>>,
+) -> Vec<serde_json::Value> {
     // Normalize filters
     let kinds: Option<Vec<String>> =
         kind.map(|v| v.into_iter().map(|s| s.to_lowercase()).collect());
@@ -52,16 +124,28 @@ pub fn run(
         "UNKNOWN".into()
     };
     let mut enriched: Vec<serde_json::Value> = Vec::new();
-    // Prepare query tokens for simple scoring
-    let tokens: Vec<String> = q
-        .split_whitespace()
-        .filter(|s| !s.is_empty())
-        .map(|s| s.to_string())
-        .collect();
+    // Free-text terms drive the fuzzy score; headings come from the index or
+    // parse, and are only read from disk for docs loaded without them
+    let tokens = query.text_terms();
+    let boosts = &cfg.search.boosts;
+    let read_headings = boosts.headings > 0.0 && query.has_terms();
 
-    for d in hits {
+    for d in docs {
         let Some(id) = d.id.as_ref() else { continue };
         let schema = infer_schema(&d.file);
+        let text = NoteText {
+            doc: d,
+            headings: match &d.headings {
+                _ if !read_headings => Vec::new(),
+                Some(known) => known.clone(),
+                None => std::fs::read_to_string(&d.file)
+                    .map(|c| heading_texts(&c))
+                    .unwrap_or_default(),
+            },
+        };
+        if !query.matches(d, &schema, &|t| text.term_matches(t, boosts)) {
+            continue;
+        }
         let path_str = d.display_path();
         let last_modified = std::fs::metadata(&d.file)
             .and_then(|md| md.modified())
@@ -79,19 +163,7 @@ pub fn run(
         if let Some(v) = d.fm.get("kanban_status").and_then(|v| v.as_str()) {
             kanban_status = Some(v.to_string());
         }
-        // Compute simple score for note
-        let mut score: f64 = 0.0;
-        for t in &tokens {
-            if id.to_lowercase() == *t {
-                score += 1.0;
-            }
-            if d.title.to_lowercase().contains(t) {
-                score += 0.5;
-            }
-            if d.tags.iter().any(|tag| tag.to_lowercase() == *t) {
-                score += 0.25;
-            }
-        }
+        let (score, matches) = text.score(&tokens, boosts);
 
         // Filter helpers
         let schema_ok = schema_set
@@ -133,6 +205,7 @@ pub fn run(
                 "kanbanStatusLine": kanban_status_line,
                 "kanbanStatus": kanban_status,
                 "score": score,
+                "matches": matches,
                 "lastModified": last_modified,
                 "lastAccessed": serde_json::Value::Null,
             }));
@@ -270,28 +343,5 @@ pub fn run(
         let idb = b.get("id").and_then(|v| v.as_str()).unwrap_or("");
         ida.cmp(idb)
     });
-    match format {
-        OutputFormat::Json | OutputFormat::Ai => {
-            let body = serde_json::json!({
-                "protocolVersion": crate::protocol::PROTOCOL_VERSION,
-                "results": enriched
-            });
-            print_json(&body)?;
-        }
-        OutputFormat::Ndjson => {
-            // For NDJSON, emit each result as a JSON object consistent with envelope items
-            print_ndjson_iter::<serde_json::Value, _>(enriched.into_iter())?;
-        }
-        OutputFormat::Plain => {
-            for v in &enriched {
-                println!(
-                    "{}\t{}\t{}",
-                    v["id"].as_str().unwrap_or(""),
-                    v["title"].as_str().unwrap_or(""),
-                    v["path"].as_str().unwrap_or("")
-                );
-            }
-        }
-    }
-    Ok(())
+    enriched
 }
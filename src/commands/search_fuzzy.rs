@@ -0,0 +1,300 @@
+//! Typo-tolerant matching for free-text search terms.
+//!
+//! A term matches a field by (best first) substring, fzf-style subsequence, or
+//! bounded edit distance to a word. Text is folded (lowercase, Latin diacritics
+//! stripped) and match positions are reported as char offsets into the original.
+
+use serde_json::{json, Value};
+
+use crate::config::schema::SearchBoosts;
+use crate::model::AdrDoc;
+
+const FOLD_FROM: &str = "ÀÁÂÃÄÅÇÈÉÊËÌÍÎÏÑÒÓÔÕÖÙÚÛÜÝàáâãäåçèéêëìíîïñòóôõöùúûüýÿĀāĂăĄąĆćĈĉĊċČčĎďĒēĔĕĖėĘęĚěĜĝĞğĠġĢģĤĥĨĩĪīĬĭĮįİĴĵĶķĹĺĻļĽľŃńŅņŇňŌōŎŏŐőŔŕŖŗŘřŚśŜŝŞşŠšŢţŤťŨũŪūŬŭŮůŰűŲųŴŵŶŷŸŹźŻżŽžØøĐđŁłĦħıŦŧ";
+const FOLD_TO: &str = "aaaaaaceeeeiiiinooooouuuuyaaaaaaceeeeiiiinooooouuuuyyaaaaaaccccccccddeeeeeeeeeegggggggghhiiiiiiiiijjkkllllllnnnnnnoooooorrrrrrssssssssttttuuuuuuuuuuuuwwyyyzzzzzzooddllhhitt";
+
+/// Folded chars of `s`, each paired with the index of the original char it came from.
+pub fn fold(s: &str) -> Vec<(char, usize)> {
+    let mut out = Vec::with_capacity(s.len());
+    for (i, c) in s.chars().enumerate() {
+        if c.is_ascii() {
+            out.push((c.to_ascii_lowercase(), i));
+            continue;
+        }
+        // Combining diacritical marks (decomposed input) are dropped.
+        if ('\u{300}'..='\u{36f}').contains(&c) {
+            continue;
+        }
+        if let Some(at) = FOLD_FROM.chars().position(|f| f == c) {
+            out.push((FOLD_TO.as_bytes()[at] as char, i));
+            continue;
+        }
+        let expanded = match c {
+            'ß' => "ss",
+            'æ' | 'Æ' => "ae",
+            'œ' | 'Œ' => "oe",
+            'þ' | 'Þ' => "th",
+            _ => "",
+        };
+        if expanded.is_empty() {
+            out.extend(c.to_lowercase().map(|l| (l, i)));
+        } else {
+            out.extend(expanded.chars().map(|l| (l, i)));
+        }
+    }
+    out
+}
+
+fn fold_str(s: &str) -> Vec<char> {
+    fold(s).into_iter().map(|(c, _)| c).collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    /// 1.0 for an exact field match, down to ~0.2 for loose matches.
+    pub score: f64,
+    /// Sorted char offsets into the original text.
+    pub positions: Vec<usize>,
+}
+
+fn boundary(text: &[(char, usize)], at: usize) -> bool {
+    at == 0 || !text[at - 1].0.is_alphanumeric()
+}
+
+fn positions(text: &[(char, usize)], idx: impl Iterator<Item = usize>) -> Vec<usize> {
+    let mut out: Vec<usize> = idx.map(|i| text[i].1).collect();
+    out.dedup();
+    out
+}
+
+fn substring(term: &[char], text: &[(char, usize)]) -> Option<FuzzyMatch> {
+    let n = term.len();
+    let starts = (0..=text.len().checked_sub(n)?).filter(|&s| {
+        text[s..s + n]
+            .iter()
+            .map(|(c, _)| *c)
+            .eq(term.iter().copied())
+    });
+    // Prefer an occurrence that starts a word.
+    let start = starts.fold(None, |best: Option<usize>, s| match best {
+        Some(b) if boundary(text, b) || !boundary(text, s) => Some(b),
+        _ => Some(s),
+    })?;
+    let score = if n == text.len() {
+        1.0
+    } else if boundary(text, start) {
+        0.9
+    } else {
+        0.8
+    };
+    Some(FuzzyMatch {
+        score,
+        positions: positions(text, start..start + n),
+    })
+}
+
+// fzf v1: greedy forward scan, then shrink the window backwards from its end.
+fn subsequence(term: &[char], text: &[(char, usize)]) -> Option<FuzzyMatch> {
+    let mut ti = 0;
+    let mut end = None;
+    for (i, (c, _)) in text.iter().enumerate() {
+        if *c == term[ti] {
+            ti += 1;
+            if ti == term.len() {
+                end = Some(i);
+                break;
+            }
+        }
+    }
+    let end = end?;
+    let mut idx = Vec::with_capacity(term.len());
+    let mut ti = term.len();
+    for i in (0..=end).rev() {
+        if ti > 0 && text[i].0 == term[ti - 1] {
+            ti -= 1;
+            idx.push(i);
+        }
+    }
+    idx.reverse();
+    let span = end - idx[0] + 1;
+    let m = term.len();
+    if span > 2 * m + 2 {
+        return None;
+    }
+    let bonus = idx.iter().filter(|&&i| boundary(text, i)).count() as f64 / m as f64;
+    Some(FuzzyMatch {
+        score: 0.35 + 0.25 * (m as f64 / span as f64) + 0.1 * bonus,
+        positions: positions(text, idx.into_iter()),
+    })
+}
+
+/// Optimal string alignment distance, or None once it exceeds `max`.
+fn edit_distance(a: &[char], b: &[char], max: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+    let mut prev2: Vec<usize> = Vec::new();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut cur = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(prev2[j - 2] + 1);
+            }
+        }
+        if cur.iter().min().copied().unwrap_or(0) > max {
+            return None;
+        }
+        prev2 = std::mem::replace(&mut prev, cur);
+    }
+    Some(prev[b.len()]).filter(|d| *d <= max)
+}
+
+// Words closest to the term within 1 edit (5-8 chars) or 2 edits (9+).
+// Terms with digits (ids, versions) never match by typo.
+fn typo(term: &[char], text: &[(char, usize)]) -> Option<FuzzyMatch> {
+    let max = match term.len() {
+        0..=4 => return None,
+        5..=8 => 1,
+        _ => 2,
+    };
+    if term.iter().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let mut best: Option<(usize, usize, usize)> = None;
+    let mut i = 0;
+    while i < text.len() {
+        if !text[i].0.is_alphanumeric() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < text.len() && text[i].0.is_alphanumeric() {
+            i += 1;
+        }
+        let word: Vec<char> = text[start..i].iter().map(|(c, _)| *c).collect();
+        if let Some(d) = edit_distance(term, &word, max) {
+            if best.map_or(true, |(bd, ..)| d < bd) {
+                best = Some((d, start, i));
+            }
+        }
+    }
+    let (d, start, end) = best?;
+    Some(FuzzyMatch {
+        score: 0.6 - 0.15 * d as f64,
+        positions: positions(text, start..end),
+    })
+}
+
+/// Best match of `term` in `text`, if any.
+pub fn fuzzy_match(term: &str, text: &str) -> Option<FuzzyMatch> {
+    let term = fold_str(term);
+    if term.is_empty() {
+        return None;
+    }
+    let folded = fold(text);
+    if let Some(m) = substring(&term, &folded) {
+        return Some(m);
+    }
+    match (subsequence(&term, &folded), typo(&term, &folded)) {
+        (Some(a), Some(b)) => Some(if b.score > a.score { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+/// The free-text fields of a note that search terms are matched against.
+pub struct NoteText<'a> {
+    pub doc: &'a AdrDoc,
+    pub headings: Vec<String>,
+}
+
+impl NoteText<'_> {
+    // (field, index within a list field, value, boost) for fields with a positive boost.
+    fn fields<'s>(&'s self, boosts: &SearchBoosts) -> Vec<(&'static str, usize, &'s str, f64)> {
+        let mut out = Vec::new();
+        if let Some(id) = self.doc.id.as_deref() {
+            out.push(("id", 0, id, boosts.id));
+        }
+        out.push(("title", 0, self.doc.title.as_str(), boosts.title));
+        for (i, t) in self.doc.tags.iter().enumerate() {
+            out.push(("tags", i, t.as_str(), boosts.tags));
+        }
+        // The title usually is the first heading; it is matched once, as the title.
+        for (i, h) in self.headings.iter().enumerate() {
+            if *h != self.doc.title {
+                out.push(("headings", i, h.as_str(), boosts.headings));
+            }
+        }
+        out.retain(|f| f.3 > 0.0);
+        out
+    }
+
+    pub fn term_matches(&self, term: &str, boosts: &SearchBoosts) -> bool {
+        self.fields(boosts)
+            .iter()
+            .any(|(_, _, v, _)| fuzzy_match(term, v).is_some())
+    }
+
+    /// Sum over terms of the best boosted field score, plus per-field match
+    /// positions (merged across terms) for highlighting.
+    pub fn score(&self, terms: &[String], boosts: &SearchBoosts) -> (f64, Vec<Value>) {
+        let fields = self.fields(boosts);
+        let mut hits: Vec<Vec<usize>> = vec![Vec::new(); fields.len()];
+        let mut total = 0.0;
+        for term in terms {
+            let mut best: f64 = 0.0;
+            for (k, (_, _, value, boost)) in fields.iter().enumerate() {
+                if let Some(m) = fuzzy_match(term, value) {
+                    best = best.max(boost * m.score);
+                    hits[k].extend(m.positions);
+                }
+            }
+            total += best;
+        }
+        let mut matches = Vec::new();
+        for ((field, index, value, _), mut pos) in fields.into_iter().zip(hits) {
+            if pos.is_empty() {
+                continue;
+            }
+            pos.sort_unstable();
+            pos.dedup();
+            matches.push(match field {
+                "tags" => json!({"field": field, "index": index, "positions": pos}),
+                "headings" => json!({"field": field, "text": value, "positions": pos}),
+                _ => json!({"field": field, "positions": pos}),
+            });
+        }
+        // Rounded so scores are stable in output.
+        ((total * 1000.0).round() / 1000.0, matches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_diacritics_and_keeps_original_offsets() {
+        let m = fuzzy_match("resume", "Le Résumé").unwrap();
+        assert_eq!(m.positions, vec![3, 4, 5, 6, 7, 8]);
+        assert!(fuzzy_match("STRASSE", "Straße").is_some());
+        assert!(fuzzy_match("cafe", "cafe\u{301}").is_some());
+    }
+
+    #[test]
+    fn ranks_substring_over_subsequence_over_typo() {
+        let exact = fuzzy_match("rate limiting", "Rate limiting").unwrap();
+        assert_eq!(exact.score, 1.0);
+        let sub = fuzzy_match("limit", "Rate limiting").unwrap();
+        assert_eq!((sub.score, sub.positions), (0.9, vec![5, 6, 7, 8, 9]));
+        let seq = fuzzy_match("rtlim", "Rate limiting").unwrap();
+        assert_eq!(seq.positions, vec![0, 2, 5, 6, 7]);
+        assert!(seq.score < sub.score);
+        let typo = fuzzy_match("limitnig", "Rate limiting").unwrap();
+        assert_eq!(typo.positions, (5..13).collect::<Vec<_>>());
+        assert!(fuzzy_match("authentcation", "Token authentication").is_some());
+        assert!(fuzzy_match("adr-004", "ADR-010").is_none());
+        assert!(fuzzy_match("zebra", "Rate limiting").is_none());
+    }
+}
@@ -80,3 +80,116 @@ pub fn map_rank_to_priority_score(rank: &str) -> Option<i64> {
         _ => None,
     }
 }
+
+/// Leading `YYYY-MM-DD` of a GTD or front matter date value.
+pub fn parse_gtd_date(value: &str) -> Option<chrono::NaiveDate> {
+    value
+        .trim()
+        .get(..10)
+        .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+}
+
+/// Recurrence from a `repeat=` attribute: daily|weekly|biweekly|monthly|yearly
+/// or `<n>d|w|m|y` (an optional leading `+` is accepted, as in org-mode).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Repeat {
+    Days(u32),
+    Months(u32),
+}
+
+impl Repeat {
+    pub fn parse(value: &str) -> Option<Repeat> {
+        let v = value.trim().trim_start_matches('+').to_lowercase();
+        let named = match v.as_str() {
+            "daily" => Some(Repeat::Days(1)),
+            "weekly" => Some(Repeat::Days(7)),
+            "biweekly" => Some(Repeat::Days(14)),
+            "monthly" => Some(Repeat::Months(1)),
+            "yearly" => Some(Repeat::Months(12)),
+            _ => None,
+        };
+        if named.is_some() {
+            return named;
+        }
+        let (split, unit) = v.char_indices().last()?;
+        let n: u32 = v[..split].parse().ok().filter(|n| *n > 0)?;
+        match unit {
+            'd' => Some(Repeat::Days(n)),
+            'w' => n.checked_mul(7).map(Repeat::Days),
+            'm' => Some(Repeat::Months(n)),
+            'y' => n.checked_mul(12).map(Repeat::Months),
+            _ => None,
+        }
+    }
+
+    /// Index of the first occurrence on or after `from`, found without walking
+    /// every earlier one; `None` when it lies beyond the representable dates.
+    pub fn first_on_or_after(
+        self,
+        base: chrono::NaiveDate,
+        from: chrono::NaiveDate,
+    ) -> Option<u32> {
+        use chrono::Datelike;
+        if from <= base {
+            return Some(0);
+        }
+        // A lower bound: whole steps that certainly end before `from`.
+        let estimate = match self {
+            Repeat::Days(n) => (from - base).num_days() / n as i64,
+            Repeat::Months(n) => {
+                let months = (from.year() - base.year()) as i64 * 12 + from.month() as i64
+                    - base.month() as i64;
+                months.max(0) / n as i64
+            }
+        };
+        let mut k = u32::try_from(estimate).ok()?;
+        while self.nth(base, k)? < from {
+            k = k.checked_add(1)?;
+        }
+        Some(k)
+    }
+
+    /// The `k`-th occurrence counting from `base` (k = 0 is `base`). Month steps
+    /// are taken from `base`, so a 31st clamps per month without drifting.
+    pub fn nth(self, base: chrono::NaiveDate, k: u32) -> Option<chrono::NaiveDate> {
+        match self {
+            Repeat::Days(n) => base.checked_add_days(chrono::Days::new(n as u64 * k as u64)),
+            Repeat::Months(n) => base.checked_add_months(chrono::Months::new(n.checked_mul(k)?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn repeat_rejects_multibyte_units_and_overflow() {
+        assert_eq!(Repeat::parse("5é"), None);
+        assert_eq!(Repeat::parse("é"), None);
+        assert_eq!(Repeat::parse("999999999w"), None);
+        assert_eq!(Repeat::parse("999999999y"), None);
+        assert_eq!(Repeat::parse("+2w"), Some(Repeat::Days(14)));
+        assert_eq!(Repeat::parse("3m"), Some(Repeat::Months(3)));
+    }
+
+    #[test]
+    fn first_occurrence_on_or_after_skips_ahead() {
+        let d = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let daily = Repeat::Days(1);
+        assert_eq!(
+            daily.first_on_or_after(d("1990-01-01"), d("2026-10-19")),
+            Some(13_440)
+        );
+        let monthly = Repeat::Months(1);
+        let k = monthly
+            .first_on_or_after(d("2020-01-31"), d("2026-03-01"))
+            .unwrap();
+        assert_eq!(monthly.nth(d("2020-01-31"), k), Some(d("2026-03-31")));
+        assert_eq!(
+            Repeat::Days(7).first_on_or_after(d("2026-10-19"), d("2026-10-01")),
+            Some(0)
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! Search query language: free text, `field:value` predicates with comparisons,
+//! `OR`, implicit/explicit `AND`, `-`/`NOT` negation and parenthesized groups.
+//!
+//! Built-in fields are `id`, `title`, `schema`, `status`, `path`, `tag`/`tags`,
+//! `group`/`groups`, `depends_on`, `supersedes` and `superseded_by`; any other
+//! name is looked up in the note's front matter.
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::model::AdrDoc;
+
+mod parse;
+
+/// Parse error with the offending token's character span (0-based, half-open).
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryError {
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl QueryError {
+    fn new(message: impl Into<String>, start: usize, end: usize) -> Self {
+        Self {
+            message: message.into(),
+            start,
+            end,
+        }
+    }
+
+    /// Message plus the query with the offending token underlined.
+    pub fn render(&self, input: &str) -> String {
+        let width = self.end.saturating_sub(self.start).max(1);
+        format!(
+            "{}\n  {}\n  {}{}",
+            self,
+            input,
+            " ".repeat(self.start),
+            "^".repeat(width)
+        )
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid query at column {}: {}",
+            self.start + 1,
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cmp {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Cmp {
+    fn split(word: &str) -> (Cmp, &str) {
+        for (prefix, op) in [
+            (">=", Cmp::Ge),
+            ("<=", Cmp::Le),
+            (">", Cmp::Gt),
+            ("<", Cmp::Lt),
+            ("=", Cmp::Eq),
+        ] {
+            if let Some(rest) = word.strip_prefix(prefix) {
+                return (op, rest);
+            }
+        }
+        (Cmp::Eq, word)
+    }
+
+    fn accepts(self, ord: Ordering) -> bool {
+        match self {
+            Cmp::Eq => ord == Ordering::Equal,
+            Cmp::Lt => ord == Ordering::Less,
+            Cmp::Le => ord != Ordering::Greater,
+            Cmp::Gt => ord == Ordering::Greater,
+            Cmp::Ge => ord != Ordering::Less,
+        }
+    }
+}
+
+/// Query AST.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// The empty query.
+    All,
+    /// Free text (word or quoted phrase), matched by the caller's term matcher.
+    Term(String),
+    Field {
+        name: String,
+        op: Cmp,
+        value: String,
+    },
+    Not(Box<Expr>),
+    And(Vec<Expr>),
+    Or(Vec<Expr>),
+}
+
+impl Expr {
+    fn field(name: &str, op: Cmp, value: &str) -> Expr {
+        Expr::Field {
+            name: name.to_string(),
+            op,
+            value: value.to_string(),
+        }
+    }
+}
+
+/// A parsed `search --query`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchQuery {
+    pub expr: Expr,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        Ok(Self {
+            expr: parse::parse(input)?,
+        })
+    }
+
+    /// Whether `doc` (with its resolved schema name) satisfies the query;
+    /// free-text terms are decided by `term`.
+    pub fn matches(&self, doc: &AdrDoc, schema: &str, term: &dyn Fn(&str) -> bool) -> bool {
+        eval(&self.expr, doc, schema, term)
+    }
+
+    /// Whether the query has any free-text term (negated or not).
+    pub fn has_terms(&self) -> bool {
+        fn walk(e: &Expr) -> bool {
+            match e {
+                Expr::Term(_) => true,
+                Expr::Not(inner) => walk(inner),
+                Expr::And(items) | Expr::Or(items) => items.iter().any(walk),
+                _ => false,
+            }
+        }
+        walk(&self.expr)
+    }
+
+    /// Lowercased free-text terms outside negations, used for scoring.
+    pub fn text_terms(&self) -> Vec<String> {
+        fn walk(e: &Expr, out: &mut Vec<String>) {
+            match e {
+                Expr::Term(t) => out.push(t.to_lowercase()),
+                Expr::And(items) | Expr::Or(items) => items.iter().for_each(|i| walk(i, out)),
+                _ => {}
+            }
+        }
+        let mut out = Vec::new();
+        walk(&self.expr, &mut out);
+        out
+    }
+}
+
+fn eval(e: &Expr, doc: &AdrDoc, schema: &str, term: &dyn Fn(&str) -> bool) -> bool {
+    match e {
+        Expr::All => true,
+        Expr::Term(t) => term(t),
+        Expr::Field { name, op, value } => {
+            let substring = *op == Cmp::Eq && matches!(name.as_str(), "title" | "path");
+            field_values(doc, schema, name).iter().any(|v| {
+                if substring {
+                    v.to_lowercase().contains(&value.to_lowercase())
+                } else {
+                    compare(v, *op, value)
+                }
+            })
+        }
+        Expr::Not(inner) => !eval(inner, doc, schema, term),
+        Expr::And(items) => items.iter().all(|i| eval(i, doc, schema, term)),
+        Expr::Or(items) => items.iter().any(|i| eval(i, doc, schema, term)),
+    }
+}
+
+fn field_values(doc: &AdrDoc, schema: &str, name: &str) -> Vec<String> {
+    let one = |v: Option<&String>| v.cloned().into_iter().collect();
+    match name {
+        "id" => one(doc.id.as_ref()),
+        "title" => vec![doc.title.clone()],
+        "schema" => vec![schema.to_string()],
+        "status" => one(doc.status.as_ref()),
+        "path" => vec![doc.display_path()],
+        "tag" | "tags" => doc.tags.clone(),
+        "group" | "groups" => doc.groups.clone(),
+        "depends_on" => doc.depends_on.clone(),
+        "supersedes" => doc.supersedes.clone(),
+        "superseded_by" => doc.superseded_by.clone(),
+        _ => match doc.fm.get(name) {
+            Some(serde_yaml::Value::Sequence(items)) => items.iter().filter_map(scalar).collect(),
+            Some(v) => scalar(v).into_iter().collect(),
+            None => Vec::new(),
+        },
+    }
+}
+
+fn scalar(v: &serde_yaml::Value) -> Option<String> {
+    match v {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+// Leading `YYYY-MM-DD` of a date or datetime string.
+fn as_date(s: &str) -> Option<NaiveDate> {
+    s.get(..10)
+        .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+}
+
+// `*` matches any run of characters; case-insensitive.
+fn wildcard(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        if i == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+/// Dates compare as dates, numbers as numbers, anything else as lowercase text.
+fn compare(actual: &str, op: Cmp, wanted: &str) -> bool {
+    let (a, w) = (actual.to_lowercase(), wanted.to_lowercase());
+    if op == Cmp::Eq && w.contains('*') {
+        return wildcard(&w, &a);
+    }
+    let ord = match (as_date(&a), as_date(&w)) {
+        (Some(x), Some(y)) => x.cmp(&y),
+        _ => match (a.parse::<f64>(), w.parse::<f64>()) {
+            (Ok(x), Ok(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+            _ => a.as_str().cmp(w.as_str()),
+        },
+    };
+    op.accepts(ord)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(name: &str, op: Cmp, value: &str) -> Expr {
+        Expr::field(name, op, value)
+    }
+
+    #[test]
+    fn parses_fields_groups_and_negation() {
+        let q = SearchQuery::parse(
+            "schema:ADR status:(accepted OR proposed) -tag:deprecated updated:>2025-01-01 \"rate limiting\"",
+        )
+        .unwrap();
+        assert_eq!(
+            q.expr,
+            Expr::And(vec![
+                field("schema", Cmp::Eq, "ADR"),
+                Expr::Or(vec![
+                    field("status", Cmp::Eq, "accepted"),
+                    field("status", Cmp::Eq, "proposed"),
+                ]),
+                Expr::Not(Box::new(field("tag", Cmp::Eq, "deprecated"))),
+                field("updated", Cmp::Gt, "2025-01-01"),
+                Expr::Term("rate limiting".into()),
+            ])
+        );
+        assert_eq!(SearchQuery::parse("  ").unwrap().expr, Expr::All);
+        assert_eq!(
+            SearchQuery::parse("ADR-004").unwrap().expr,
+            Expr::Term("ADR-004".into())
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_offending_token() {
+        let cases = [
+            ("status:(accepted OR proposed", 7, 8, "unclosed '('"),
+            ("tag:a OR", 6, 8, "expected a term after OR"),
+            ("a )", 2, 3, "unexpected ')'"),
+            ("status: x", 0, 7, "expected a value after 'status:'"),
+            ("updated:>", 8, 9, "expected a value after '>'"),
+            ("\"open", 0, 5, "unterminated quote"),
+            ("x :y", 2, 3, "missing field name before ':'"),
+        ];
+        for (input, start, end, msg) in cases {
+            let err = SearchQuery::parse(input).unwrap_err();
+            assert_eq!(
+                (err.start, err.end, err.message.as_str()),
+                (start, end, msg),
+                "{}",
+                input
+            );
+        }
+        let err = SearchQuery::parse("tag:a OR").unwrap_err();
+        assert_eq!(
+            err.render("tag:a OR"),
+            "invalid query at column 7: expected a term after OR\n  tag:a OR\n        ^^"
+        );
+    }
+
+    #[test]
+    fn compares_dates_numbers_and_wildcards() {
+        assert!(compare("2025-02-01T10:00:00Z", Cmp::Gt, "2025-01-01"));
+        assert!(compare("10", Cmp::Ge, "9"));
+        assert!(compare("Security-Review", Cmp::Eq, "security*"));
+        assert!(!compare("draft", Cmp::Eq, "accepted"));
+    }
+}
@@ -0,0 +1,260 @@
+//! Lexer and recursive-descent parser for search queries.
+//!
+//! ```text
+//! query   := or?
+//! or      := and ("OR" and)*
+//! and     := unary ("AND"? unary)*
+//! unary   := ("-" | "NOT") unary | primary
+//! primary := "(" or ")" | FIELD ":" value | WORD | "PHRASE"
+//! value   := "(" or ")" | ("<" | "<=" | ">" | ">=" | "=")? WORD | "PHRASE"
+//! ```
+
+use super::{Cmp, Expr, QueryError};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    LParen,
+    RParen,
+    Or,
+    And,
+    Not,
+    Field(String),
+    Word(String),
+    Phrase(String),
+}
+
+type Spanned = (Tok, usize, usize);
+
+fn is_word_end(c: char) -> bool {
+    c.is_whitespace() || c == '(' || c == ')' || c == '"'
+}
+
+fn lex(input: &str) -> Result<Vec<Spanned>, QueryError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut toks = Vec::new();
+    let mut i = 0;
+    // A value directly after `field:` is taken verbatim (no further `:` splitting).
+    let mut after_field = false;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            after_field = false;
+            continue;
+        }
+        match c {
+            '(' | ')' => {
+                let t = if c == '(' { Tok::LParen } else { Tok::RParen };
+                toks.push((t, i, i + 1));
+                i += 1;
+                after_field = false;
+                continue;
+            }
+            '"' => {
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return Err(QueryError::new("unterminated quote", start, i)),
+                        Some('"') => break,
+                        Some('\\') if matches!(chars.get(i + 1), Some('"') | Some('\\')) => {
+                            text.push(chars[i + 1]);
+                            i += 2;
+                        }
+                        Some(&ch) => {
+                            text.push(ch);
+                            i += 1;
+                        }
+                    }
+                }
+                i += 1;
+                toks.push((Tok::Phrase(text), start, i));
+                after_field = false;
+                continue;
+            }
+            '-' if !after_field => {
+                toks.push((Tok::Not, i, i + 1));
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        while i < chars.len() && !is_word_end(chars[i]) {
+            if chars[i] == ':' && !after_field {
+                if i == start {
+                    return Err(QueryError::new("missing field name before ':'", i, i + 1));
+                }
+                break;
+            }
+            i += 1;
+        }
+        let word: String = chars[start..i].iter().collect();
+        if chars.get(i) == Some(&':') && !after_field {
+            i += 1;
+            toks.push((Tok::Field(word), start, i));
+            after_field = true;
+            continue;
+        }
+        let tok = match word.as_str() {
+            "OR" if !after_field => Tok::Or,
+            "AND" if !after_field => Tok::And,
+            "NOT" if !after_field => Tok::Not,
+            _ => Tok::Word(word),
+        };
+        toks.push((tok, start, i));
+        after_field = false;
+    }
+    Ok(toks)
+}
+
+struct Parser {
+    toks: Vec<Spanned>,
+    pos: usize,
+    len: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Spanned> {
+        self.toks.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Spanned> {
+        let t = self.toks.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    // Error for a missing operand after the token at `after`.
+    fn expect_operand(&self, after: &Spanned, what: &str) -> Result<(), QueryError> {
+        match self.peek() {
+            None | Some((Tok::RParen, ..)) | Some((Tok::Or, ..)) | Some((Tok::And, ..)) => Err(
+                QueryError::new(format!("expected a term after {}", what), after.1, after.2),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    fn or(&mut self, field: Option<&str>) -> Result<Expr, QueryError> {
+        let mut items = vec![self.and(field)?];
+        while let Some(t) = self.peek().filter(|t| t.0 == Tok::Or).cloned() {
+            self.pos += 1;
+            self.expect_operand(&t, "OR")?;
+            items.push(self.and(field)?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Expr::Or(items)
+        })
+    }
+
+    fn and(&mut self, field: Option<&str>) -> Result<Expr, QueryError> {
+        let mut items = vec![self.unary(field)?];
+        loop {
+            match self.peek().cloned() {
+                None | Some((Tok::RParen, ..)) | Some((Tok::Or, ..)) => break,
+                Some(t @ (Tok::And, ..)) => {
+                    self.pos += 1;
+                    self.expect_operand(&t, "AND")?;
+                }
+                Some(_) => {}
+            }
+            items.push(self.unary(field)?);
+        }
+        Ok(if items.len() == 1 {
+            items.remove(0)
+        } else {
+            Expr::And(items)
+        })
+    }
+
+    fn unary(&mut self, field: Option<&str>) -> Result<Expr, QueryError> {
+        if let Some(t) = self.peek().filter(|t| t.0 == Tok::Not).cloned() {
+            self.pos += 1;
+            let what = if t.2 - t.1 == 1 { "'-'" } else { "NOT" };
+            self.expect_operand(&t, what)?;
+            return Ok(Expr::Not(Box::new(self.unary(field)?)));
+        }
+        self.primary(field)
+    }
+
+    fn primary(&mut self, field: Option<&str>) -> Result<Expr, QueryError> {
+        let Some((tok, start, end)) = self.next() else {
+            return Err(QueryError::new("expected a term", self.len, self.len));
+        };
+        match tok {
+            Tok::LParen => {
+                if matches!(self.peek(), Some((Tok::RParen, ..))) {
+                    return Err(QueryError::new("empty group", start, self.toks[self.pos].2));
+                }
+                let inner = self.or(field)?;
+                match self.next() {
+                    Some((Tok::RParen, ..)) => Ok(inner),
+                    _ => Err(QueryError::new("unclosed '('", start, end)),
+                }
+            }
+            Tok::RParen => Err(QueryError::new("unexpected ')'", start, end)),
+            Tok::Or | Tok::And => Err(QueryError::new(
+                "expected a term before the operator",
+                start,
+                end,
+            )),
+            Tok::Not => unreachable!("handled in unary"),
+            Tok::Field(name) => {
+                if let Some(outer) = field {
+                    return Err(QueryError::new(
+                        format!("field '{}:' inside '{}:' value", name, outer),
+                        start,
+                        end,
+                    ));
+                }
+                match self.peek() {
+                    Some((Tok::LParen | Tok::Word(_) | Tok::Phrase(_), s, _)) if *s == end => {
+                        self.primary(Some(&name))
+                    }
+                    _ => Err(QueryError::new(
+                        format!("expected a value after '{}:'", name),
+                        start,
+                        end,
+                    )),
+                }
+            }
+            Tok::Word(w) => match field {
+                None => Ok(Expr::Term(w)),
+                Some(name) => {
+                    let (op, value) = Cmp::split(&w);
+                    if value.is_empty() {
+                        return Err(QueryError::new(
+                            format!("expected a value after '{}'", &w),
+                            start,
+                            end,
+                        ));
+                    }
+                    Ok(Expr::field(name, op, value))
+                }
+            },
+            Tok::Phrase(p) => Ok(match field {
+                None => Expr::Term(p),
+                Some(name) => Expr::field(name, Cmp::Eq, &p),
+            }),
+        }
+    }
+}
+
+pub(super) fn parse(input: &str) -> Result<Expr, QueryError> {
+    let toks = lex(input)?;
+    if toks.is_empty() {
+        return Ok(Expr::All);
+    }
+    let mut p = Parser {
+        toks,
+        pos: 0,
+        len: input.chars().count(),
+    };
+    let expr = p.or(None)?;
+    if let Some((_, start, end)) = p.next() {
+        return Err(QueryError::new("unexpected ')'", start, end));
+    }
+    Ok(expr)
+}
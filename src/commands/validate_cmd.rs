@@ -3,6 +3,8 @@ use anyhow::Result;
 use crate::cli::OutputFormat;
 use crate::commands::lua_integration::lua_validate_augment;
 use crate::commands::output::{print_json, print_ndjson_iter, print_ndjson_value};
+use crate::commands::validate_fix;
+use crate::config::resolved::resolved_config_json;
 use crate::config::Config;
 use crate::discovery::incremental_collect_docs;
 use crate::index::write_indexes;
@@ -16,16 +18,18 @@ pub fn run(
     format: &OutputFormat,
     dry_run: bool,
     full_rescan: bool,
+    fix: bool,
 ) -> Result<()> {
-    let docs = incremental_collect_docs(cfg, full_rescan)?;
+    let mut docs = incremental_collect_docs(cfg, full_rescan)?;
+    let fixes = if fix {
+        validate_fix::apply(cfg, &mut docs, dry_run)?
+    } else {
+        Vec::new()
+    };
     let mut report = validate_docs(cfg, cfg_path, &docs);
-    lua_validate_augment(
-        cfg,
-        cfg_path,
-        &docs,
-        &mut report.errors,
-        &mut report.warnings,
-    );
+    lua_validate_augment(cfg, &docs, &mut report.errors, &mut report.warnings);
+    // Lua error diagnostics fail validation like built-in ones.
+    report.ok = report.errors.is_empty();
     // Helper to derive location from message when possible
     fn derive_location(message: &str) -> Option<(String, ToolCallLocation)> {
         // Expected leading pattern: "/path/to/file.md: ..."
@@ -45,8 +49,12 @@ pub fn run(
             None
         };
         let path = std::path::PathBuf::from(&file);
-        let mut line: Option<u32> = None;
-        if let Some(needle) = needle {
+        // Explicit positions (Lua diagnostics) end with "(line N)".
+        let mut line: Option<u32> = rest
+            .strip_suffix(')')
+            .and_then(|r| r.rsplit_once("(line "))
+            .and_then(|(_, n)| n.parse().ok());
+        if let (None, Some(needle)) = (line, needle) {
             if let Ok(content) = std::fs::read_to_string(&path) {
                 for (i, l) in content.lines().enumerate() {
                     if l.contains(&needle) {
@@ -82,10 +90,38 @@ pub fn run(
         if m.contains("conflict for id") {
             return Some("E214".into());
         }
+        if m.contains("unresolved wikilink '") {
+            return Some("W233".into());
+        }
+        if m.contains("wikilink anchor '#") {
+            return Some("W234".into());
+        }
+        if m.contains(": broken link '") {
+            return Some("W235".into());
+        }
+        if m.contains(": backlinks block ") {
+            return Some(if kind == "warning" { "W236" } else { "E236" }.into());
+        }
+        if m.contains("missing reciprocal superseded_by") {
+            return Some("W232".into());
+        }
         if m.contains("depends_on '") || m.contains("supersedes '") || m.contains("superseded_by '")
         {
             return Some("E230".into());
         }
+        let sev = if kind == "warning" { "W" } else { "E" };
+        if m.contains("' expired on ") || (m.contains("' date ") && m.contains(" is ")) {
+            return Some(format!("{}226", sev));
+        }
+        if m.contains(") must not be ") {
+            return Some(format!("{}227", sev));
+        }
+        if m.contains("review date '") {
+            return Some(format!("{}228", sev));
+        }
+        if m.contains(": stale: status '") {
+            return Some(format!("{}229", sev));
+        }
         if m.contains("unknown keys") {
             return Some(if kind == "warning" { "W221" } else { "E221" }.into());
         }
@@ -132,7 +168,7 @@ pub fn run(
             // Build diagnostics array per contracts/cli/validate_result.schema.json
             let mut diagnostics: Vec<serde_json::Value> = Vec::new();
             for m in &report.errors {
-                let (file, _loc) = derive_location(m).unwrap_or((
+                let (file, loc) = derive_location(m).unwrap_or((
                     String::new(),
                     ToolCallLocation {
                         path: std::path::PathBuf::new(),
@@ -140,15 +176,19 @@ pub fn run(
                     },
                 ));
                 let code = classify_code(m, "error").unwrap_or_else(|| "E000".to_string());
-                diagnostics.push(serde_json::json!({
+                let mut d = serde_json::json!({
                     "severity": "error",
                     "code": code,
                     "msg": m,
                     "path": if file.is_empty() { serde_json::Value::Null } else { serde_json::Value::String(file) }
-                }));
+                });
+                if let Some(line) = loc.line {
+                    d["line"] = serde_json::json!(line);
+                }
+                diagnostics.push(d);
             }
             for m in &report.warnings {
-                let (file, _loc) = derive_location(m).unwrap_or((
+                let (file, loc) = derive_location(m).unwrap_or((
                     String::new(),
                     ToolCallLocation {
                         path: std::path::PathBuf::new(),
@@ -156,19 +196,26 @@ pub fn run(
                     },
                 ));
                 let code = classify_code(m, "warning").unwrap_or_else(|| "W000".to_string());
-                diagnostics.push(serde_json::json!({
+                let mut d = serde_json::json!({
                     "severity": "warning",
                     "code": code,
                     "msg": m,
                     "path": if file.is_empty() { serde_json::Value::Null } else { serde_json::Value::String(file) }
-                }));
+                });
+                if let Some(line) = loc.line {
+                    d["line"] = serde_json::json!(line);
+                }
+                diagnostics.push(d);
             }
-            let obj = serde_json::json!({
+            let mut obj = serde_json::json!({
                 "protocolVersion": crate::protocol::PROTOCOL_VERSION,
                 "ok": report.ok,
                 "docCount": docs.len(),
                 "diagnostics": diagnostics,
             });
+            if fix {
+                obj["fixes"] = serde_json::Value::Array(validate_fix::fixes_json(&fixes));
+            }
             print_json(&obj)?;
         }
         OutputFormat::Ndjson => {
@@ -178,6 +225,10 @@ pub fn run(
                 doc_count: docs.len(),
             };
             print_ndjson_value(&serde_json::to_value(&header)?)?;
+            for mut f in validate_fix::fixes_json(&fixes) {
+                f["kind"] = serde_json::json!("fix");
+                print_ndjson_value(&f)?;
+            }
             let errs = report.errors.iter().map(|m| {
                 let (file, location) = derive_location(m).unwrap_or((
                     String::new(),
@@ -223,6 +274,9 @@ pub fn run(
             print_ndjson_iter(errs.chain(warns))?;
         }
         OutputFormat::Plain => {
+            if fix {
+                validate_fix::print_plain(&fixes, dry_run);
+            }
             if report.ok {
                 println!("Validation OK ({} docs)", docs.len());
             } else {
@@ -252,62 +306,7 @@ pub fn run(
             if let Some(parent) = resolved_path.parent() {
                 let _ = std::fs::create_dir_all(parent);
             }
-            let scan = serde_json::json!({
-                "filepaths": cfg.bases.iter().map(|p| p.display().to_string()).collect::<Vec<_>>(),
-                "indexPath": project_root.join(&cfg.index_relative).display().to_string(),
-                "hashMode": "mtime",
-                "indexStrategy": "content",
-                "ignoreGlobs": cfg.ignore_globs,
-                "ignoreSymlinks": true
-            });
-            let authoring = serde_json::json!({
-                "editor": cfg
-                    .authoring
-                    .editor
-                    .clone()
-                    .unwrap_or_else(|| "nvim".to_string()),
-                "backgroundWatch": cfg.authoring.background_watch.unwrap_or(true),
-                "outputPath": cfg.authoring.output_path.clone(),
-                "destinations": cfg.authoring.destinations.clone()
-            });
-            let graph = serde_json::json!({
-                "depth": cfg.defaults.depth as i64,
-                "includeBidirectional": cfg.defaults.include_bidirectional,
-                "ai": {"depth": 1, "defaultFanout": 5, "includeBidirectional": true, "neighborStyle": "metadata", "outlineLines": 2}
-            });
-            let templates = serde_json::json!({
-                "import": cfg.import
-            });
-            let schemas: Vec<serde_json::Value> = cfg
-                .schema
-                .iter()
-                .map(|s| serde_json::json!({"name": s.name, "filePatterns": s.file_patterns}))
-                .collect();
-            let overlays = serde_json::json!({
-                "enabled": cfg.overlays.enabled,
-                "repoPath": cfg
-                    .overlays
-                    .repo_path
-                    .as_ref()
-                    .map(|p| p.display().to_string()),
-                "userPath": cfg
-                    .overlays
-                    .user_path
-                    .as_ref()
-                    .map(|p| p.display().to_string()),
-            });
-            let resolved = serde_json::json!({
-                "protocolVersion": crate::protocol::PROTOCOL_VERSION,
-                "configVersion": cfg.config_version.as_deref().unwrap_or("0.1"),
-                "luaApiVersion": 1,
-                "projectRoot": project_root.display().to_string(),
-                "scan": scan,
-                "authoring": authoring,
-                "graph": graph,
-                "templates": templates,
-                "schemas": schemas,
-                "overlays": overlays
-            });
+            let resolved = resolved_config_json(cfg, &project_root);
             let _ = std::fs::write(
                 &resolved_path,
                 serde_json::to_string_pretty(&resolved).unwrap_or_else(|_| "{}".into()),
@@ -0,0 +1,47 @@
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::model::{parse_front_matter_and_title, AdrDoc};
+use crate::util::{normalize_display_path, unified_diff};
+use crate::validate::fix::{plan_fixes, write_fixes, FileFix};
+
+/// Plan fixes, write them unless `dry_run`, and swap fixed notes into `docs`
+/// so the follow-up validation reports only what remains.
+pub fn apply(cfg: &Config, docs: &mut [AdrDoc], dry_run: bool) -> Result<Vec<FileFix>> {
+    let fixes = plan_fixes(cfg, docs)?;
+    if !dry_run {
+        write_fixes(&fixes)?;
+    }
+    for f in &fixes {
+        if let Some(doc) = docs.iter_mut().find(|d| d.file == f.path) {
+            *doc = parse_front_matter_and_title(&f.after, &f.path);
+        }
+    }
+    Ok(fixes)
+}
+
+fn diff_of(f: &FileFix) -> String {
+    unified_diff(&normalize_display_path(&f.path), &f.before, &f.after)
+}
+
+pub fn fixes_json(fixes: &[FileFix]) -> Vec<serde_json::Value> {
+    fixes
+        .iter()
+        .map(|f| {
+            serde_json::json!({
+                "path": normalize_display_path(&f.path),
+                "applied": f.applied,
+                "diff": diff_of(f),
+            })
+        })
+        .collect()
+}
+
+pub fn print_plain(fixes: &[FileFix], dry_run: bool) {
+    for f in fixes {
+        print!("{}", diff_of(f));
+    }
+    let verb = if dry_run { "Would fix" } else { "Fixed" };
+    let count: usize = fixes.iter().map(|f| f.applied.len()).sum();
+    println!("{} {} issue(s) in {} file(s)", verb, count, fixes.len());
+}
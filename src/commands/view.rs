@@ -0,0 +1,195 @@
+//! `cli-rag view`: run a saved search from a `[[view]]` config block.
+
+use anyhow::{anyhow, Result};
+use heck::ToLowerCamelCase;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::cli::OutputFormat;
+use crate::commands::output::{print_json, print_ndjson_iter};
+use crate::commands::search::collect_results;
+use crate::commands::search_query::SearchQuery;
+use crate::config::schema::ViewCfg;
+use crate::config::Config;
+use crate::discovery::docs_with_source;
+use crate::model::AdrDoc;
+
+fn list_views(cfg: &Config, format: &OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Plain => {
+            for v in &cfg.view {
+                match &v.description {
+                    Some(d) => println!("{}\t{}", v.name, d),
+                    None => println!("{}", v.name),
+                }
+            }
+        }
+        _ => {
+            let views: Vec<Value> = cfg
+                .view
+                .iter()
+                .map(|v| serde_json::json!({"name": v.name, "description": v.description, "query": v.query}))
+                .collect();
+            print_json(&serde_json::json!({
+                "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+                "views": views,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// Column value: a search result field (snake_case names map to camelCase),
+/// else the note's front matter key.
+fn cell(result: &Value, docs: &HashMap<&str, &AdrDoc>, column: &str) -> Value {
+    for key in [column.to_string(), column.to_lower_camel_case()] {
+        if let Some(v) = result.get(&key).filter(|v| !v.is_null()) {
+            return v.clone();
+        }
+    }
+    let note_id = result
+        .get("noteId")
+        .or_else(|| result.get("id"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+    docs.get(note_id)
+        .and_then(|d| d.fm.get(column))
+        .and_then(|v| serde_json::to_value(v).ok())
+        .unwrap_or(Value::Null)
+}
+
+// Numbers compare numerically, everything else as text; missing values sort last.
+fn compare_cells(a: &Value, b: &Value, descending: bool) -> Ordering {
+    let ord = match (a, b) {
+        (Value::Null, Value::Null) => return Ordering::Equal,
+        (Value::Null, _) => return Ordering::Greater,
+        (_, Value::Null) => return Ordering::Less,
+        (Value::Number(x), Value::Number(y)) => x
+            .as_f64()
+            .partial_cmp(&y.as_f64())
+            .unwrap_or(Ordering::Equal),
+        _ => plain_cell(a).cmp(&plain_cell(b)),
+    };
+    if descending {
+        ord.reverse()
+    } else {
+        ord
+    }
+}
+
+fn plain_cell(v: &Value) -> String {
+    match v {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(plain_cell).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    }
+}
+
+/// Rows of the view in output order, each holding only the view's columns.
+pub fn view_rows(cfg: &Config, docs: &[AdrDoc], view: &ViewCfg) -> Result<Vec<Value>> {
+    let query = SearchQuery::parse(&view.query)
+        .map_err(|e| anyhow!("view '{}': {}", view.name, e.render(&view.query)))?;
+    let results = collect_results(cfg, docs, &query, view.kind.clone(), None, None, None);
+    let by_id: HashMap<&str, &AdrDoc> = docs
+        .iter()
+        .filter_map(|d| d.id.as_deref().map(|id| (id, d)))
+        .collect();
+    let mut rows: Vec<(Vec<Value>, Value)> = results
+        .iter()
+        .map(|r| {
+            let keys = view
+                .sort
+                .iter()
+                .map(|k| cell(r, &by_id, k.trim_start_matches('-')))
+                .collect();
+            let mut row = serde_json::Map::new();
+            for c in &view.columns {
+                row.insert(c.clone(), cell(r, &by_id, c));
+            }
+            (keys, Value::Object(row))
+        })
+        .collect();
+    // Stable sort keeps the search order for ties.
+    rows.sort_by(|(a, _), (b, _)| {
+        view.sort
+            .iter()
+            .enumerate()
+            .map(|(i, k)| compare_cells(&a[i], &b[i], k.starts_with('-')))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    });
+    let limit = view.limit.unwrap_or(usize::MAX);
+    Ok(rows.into_iter().take(limit).map(|(_, row)| row).collect())
+}
+
+fn print_table(columns: &[String], rows: &[Value]) {
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|r| columns.iter().map(|c| plain_cell(&r[c])).collect())
+        .collect();
+    let header: Vec<String> = columns.iter().map(|c| c.to_uppercase()).collect();
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|i| {
+            cells
+                .iter()
+                .map(|r| r[i].chars().count())
+                .chain(std::iter::once(header[i].len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for line in std::iter::once(&header).chain(cells.iter()) {
+        let mut out = String::new();
+        for (i, text) in line.iter().enumerate() {
+            if i + 1 == line.len() {
+                out.push_str(text);
+            } else {
+                out.push_str(&format!("{:width$}  ", text, width = widths[i]));
+            }
+        }
+        println!("{}", out.trim_end());
+    }
+}
+
+/// Run a saved view, or list views when `name` is omitted.
+pub fn run(
+    cfg: &Config,
+    cfg_path: &Option<std::path::PathBuf>,
+    format: &OutputFormat,
+    name: Option<String>,
+) -> Result<()> {
+    let Some(name) = name else {
+        return list_views(cfg, format);
+    };
+    let Some(view) = cfg.view.iter().find(|v| v.name == name) else {
+        let names: Vec<&str> = cfg.view.iter().map(|v| v.name.as_str()).collect();
+        let available = if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        };
+        return Err(anyhow!(
+            "unknown view '{}' (available: {})",
+            name,
+            available
+        ));
+    };
+    let (docs, used_unified) = docs_with_source(cfg, cfg_path)?;
+    if !used_unified {
+        eprintln!("Note: unified index not found; falling back to per-base/scan. Consider `cli-rag validate`.");
+    }
+    let rows = view_rows(cfg, &docs, view)?;
+    match format {
+        OutputFormat::Json | OutputFormat::Ai => print_json(&serde_json::json!({
+            "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+            "view": view.name,
+            "columns": view.columns,
+            "rows": rows,
+        }))?,
+        OutputFormat::Ndjson => print_ndjson_iter::<Value, _>(rows)?,
+        OutputFormat::Plain => print_table(&view.columns, &rows),
+    }
+    Ok(())
+}
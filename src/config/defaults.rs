@@ -43,6 +43,17 @@ pub fn default_defaults() -> DefaultsCfg {
     }
 }
 
+pub fn default_lua_instruction_limit() -> u64 {
+    10_000_000
+}
+pub fn default_lua_memory_limit_mb() -> u64 {
+    64
+}
+
+pub fn default_view_columns() -> Vec<String> {
+    vec!["id".into(), "title".into(), "path".into()]
+}
+
 pub fn default_config_version() -> String {
     "0.1".to_string()
 }
@@ -0,0 +1,290 @@
+use serde_json::{json, Value};
+
+use super::DRAFT;
+
+fn strings() -> Value {
+    json!({"type": "array", "items": {"type": "string"}})
+}
+
+fn severity() -> Value {
+    json!({"type": "string", "enum": ["error", "warning", "warn", "ignore"]})
+}
+
+fn schema_rule() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "type": {"type": "string", "enum": ["string", "array", "date", "integer", "number", "boolean", "object"]},
+            "allowed": strings(),
+            "enum": strings(),
+            "min_items": {"type": "integer", "minimum": 0},
+            "regex": {"type": "string", "format": "regex"},
+            "refers_to_types": strings(),
+            "severity": severity(),
+            "format": {"type": "string"},
+            "globs": strings(),
+            "integer": {
+                "type": "object",
+                "properties": {"min": {"type": "integer"}, "max": {"type": "integer"}},
+                "additionalProperties": false
+            },
+            "float": {
+                "type": "object",
+                "properties": {"min": {"type": "number"}, "max": {"type": "number"}},
+                "additionalProperties": false
+            },
+            "date": {
+                "type": "object",
+                "description": "Bounds are YYYY-MM-DD, today, today+30d / today-2w, or another date field name.",
+                "properties": {
+                    "not_before": {"type": "string"},
+                    "not_after": {"type": "string"},
+                    "review_by": {"type": "boolean"}
+                },
+                "additionalProperties": false
+            }
+        },
+        "additionalProperties": false
+    })
+}
+
+fn schema_new() -> Value {
+    let template = json!({"type": "object", "properties": {"template": {"type": "string"}}});
+    json!({
+        "type": "object",
+        "properties": {
+            "id_generator": {
+                "type": "object",
+                "properties": {
+                    "strategy": {"type": "string", "enum": ["increment", "datetime", "uuid"]},
+                    "prefix": {"type": "string"},
+                    "padding": {"type": "integer", "minimum": 0}
+                }
+            },
+            "filename_template": {"type": "string"},
+            "lua_generator": {"type": "string"},
+            "output_path": {"oneOf": [{"type": "string"}, strings()]},
+            "template": {
+                "type": "object",
+                "properties": {"prompt": template, "note": template}
+            },
+            "defaults": {
+                "type": "object",
+                "description": "Front matter values seeded into new notes; strings may use template tokens such as {{now:%Y-%m-%d}}, {{now+90d}} or {{env:USER}}."
+            }
+        }
+    })
+}
+
+fn schema_validate() -> Value {
+    let edge_policy = json!({
+        "type": "object",
+        "properties": {
+            "weight": {"type": "number"},
+            "required": severity(),
+            "cycle_detection": severity()
+        }
+    });
+    json!({
+        "type": "object",
+        "properties": {
+            "severity": severity(),
+            "body": {
+                "type": "object",
+                "properties": {
+                    "headings": {
+                        "type": "object",
+                        "properties": {
+                            "heading_check": {"type": "string", "enum": ["exact", "missing_only", "ignore"]},
+                            "max_count": {"type": "integer", "minimum": 0},
+                            "severity": severity()
+                        }
+                    },
+                    "line_count": {
+                        "type": "object",
+                        "properties": {
+                            "scan_policy": {"type": "string", "enum": ["on_creation", "on_validate"]},
+                            "severity": severity()
+                        }
+                    }
+                }
+            },
+            "edges": {
+                "type": "object",
+                "properties": {
+                    "cross_schema": {
+                        "type": "object",
+                        "properties": {"allowed_targets": strings()}
+                    },
+                    "wikilinks": {
+                        "type": "object",
+                        "properties": {
+                            "min_outgoing": {"type": "integer", "minimum": 0},
+                            "min_incoming": {"type": "integer", "minimum": 0},
+                            "severity": severity()
+                        }
+                    }
+                },
+                "additionalProperties": edge_policy
+            },
+            "staleness": {
+                "type": "object",
+                "required": ["after_days"],
+                "properties": {
+                    "after_days": {"type": "integer", "minimum": 0},
+                    "statuses": strings(),
+                    "field": {"type": "string"},
+                    "severity": severity()
+                }
+            }
+        }
+    })
+}
+
+/// `[[schema]]` block as deserialized into `SchemaCfg`.
+fn schema_block() -> Value {
+    json!({
+        "type": "object",
+        "required": ["name", "file_patterns"],
+        "properties": {
+            "name": {"type": "string"},
+            "config_version": {"type": "string"},
+            "file_patterns": strings(),
+            "required": strings(),
+            "allowed_keys": strings(),
+            "unknown_policy": {"type": "string", "enum": ["ignore", "warn", "error"]},
+            "cycle_policy": {"type": "string", "enum": ["ignore", "warn", "error"]},
+            "filename_template": {"type": "string"},
+            "new": schema_new(),
+            "rules": {"type": "object", "additionalProperties": schema_rule()},
+            "validate": schema_validate(),
+            "backlinks": {
+                "type": "object",
+                "properties": {
+                    "enabled": {"type": "boolean"},
+                    "kinds": strings(),
+                    "severity": severity()
+                }
+            }
+        }
+    })
+}
+
+fn authoring() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "editor": {"type": "string"},
+            "background_watch": {"type": "boolean"},
+            "output_path": {"type": "string"},
+            "destinations": {"type": "object", "additionalProperties": {"type": "string"}}
+        }
+    })
+}
+
+fn lua() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "capabilities": {
+                "type": "array",
+                "items": {"type": "string", "enum": ["io", "os", "require"]}
+            },
+            "instruction_limit": {"type": "integer", "minimum": 0},
+            "memory_limit_mb": {"type": "integer", "minimum": 0}
+        }
+    })
+}
+
+fn search() -> Value {
+    let boost = json!({"type": "number", "minimum": 0});
+    json!({
+        "type": "object",
+        "properties": {
+            "boosts": {
+                "type": "object",
+                "properties": {"id": boost, "title": boost, "tags": boost, "headings": boost}
+            }
+        }
+    })
+}
+
+/// `[[view]]` block as deserialized into `ViewCfg`.
+fn view_block() -> Value {
+    json!({
+        "type": "object",
+        "required": ["name"],
+        "properties": {
+            "name": {"type": "string"},
+            "description": {"type": "string"},
+            "query": {"type": "string"},
+            "kind": {"type": "array", "items": {"type": "string", "enum": ["note", "todo", "kanban"]}},
+            "sort": strings(),
+            "columns": strings(),
+            "limit": {"type": "integer", "minimum": 0}
+        }
+    })
+}
+
+/// JSON Schema for `.cli-rag.toml`, accepting both the nested `[config.*]`
+/// layout and the legacy flat keys understood by `Config`. Written by hand;
+/// `tests/integration_config_schema_fields.rs` checks it covers every field.
+pub fn config_json_schema() -> Value {
+    let graph = json!({
+        "type": "object",
+        "properties": {
+            "depth": {"type": "integer", "minimum": 0},
+            "include_bidirectional": {"type": "boolean"},
+            "include_content": {"type": "boolean"}
+        }
+    });
+    json!({
+        "$schema": DRAFT,
+        "$id": super::CONFIG_SCHEMA_FILENAME,
+        "title": "cli-rag project config (.cli-rag.toml)",
+        "type": "object",
+        "properties": {
+            "config": {
+                "type": "object",
+                "properties": {
+                    "config_version": {"type": "string"},
+                    "scan": {
+                        "type": "object",
+                        "properties": {
+                            "filepaths": strings(),
+                            "index_path": {"type": "string"},
+                            "hash_mode": {"type": "string", "enum": ["mtime", "content"]},
+                            "index_strategy": {"type": "string", "enum": ["metadata", "content"]},
+                            "ignore_globs": strings(),
+                            "ignore_symlinks": {"type": "boolean"}
+                        }
+                    },
+                    "authoring": authoring(),
+                    "lua": lua(),
+                    "search": search(),
+                    "graph": graph.clone(),
+                    "templates": {
+                        "type": "object",
+                        "properties": {"import": strings()}
+                    }
+                }
+            },
+            "config_version": {"type": "string"},
+            "import": strings(),
+            "bases": strings(),
+            "filepaths": strings(),
+            "index_relative": {"type": "string"},
+            "groups_relative": {"type": "string"},
+            "file_patterns": strings(),
+            "ignore_globs": strings(),
+            "allowed_statuses": strings(),
+            "defaults": graph,
+            "authoring": authoring(),
+            "lua": lua(),
+            "search": search(),
+            "schema": {"type": "array", "items": {"$ref": "#/definitions/schema"}},
+            "view": {"type": "array", "items": {"$ref": "#/definitions/view"}}
+        },
+        "definitions": {"schema": schema_block(), "view": view_block()}
+    })
+}
@@ -0,0 +1,20 @@
+//! JSON Schema export for `.cli-rag.toml` and per-schema note front matter.
+//!
+//! Draft-07 is used because that is what yaml-language-server and most editor
+//! integrations validate against.
+
+mod config;
+mod notes;
+
+pub use config::config_json_schema;
+pub use notes::{frontmatter_json_schema, rule_json_schema};
+
+pub(crate) const DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// File name used when writing a note schema's JSON Schema to disk.
+pub fn frontmatter_schema_filename(schema_name: &str) -> String {
+    format!("{}.frontmatter.schema.json", schema_name)
+}
+
+/// File name used when writing the config JSON Schema to disk.
+pub const CONFIG_SCHEMA_FILENAME: &str = "cli-rag.config.schema.json";
@@ -0,0 +1,165 @@
+use serde_json::{json, Map, Value};
+
+use super::DRAFT;
+use crate::config::schema::{Config, SchemaCfg, SchemaRule};
+
+/// Translate a strftime date format into an anchored regex (common specifiers only).
+fn strftime_pattern(format: &str) -> String {
+    let mut out = String::from("^");
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push_str(&regex::escape(&c.to_string()));
+            continue;
+        }
+        match chars.next() {
+            Some('Y') => out.push_str(r"\d{4}"),
+            Some('y' | 'm' | 'd' | 'H' | 'M' | 'S') => out.push_str(r"\d{2}"),
+            Some('j') => out.push_str(r"\d{3}"),
+            Some('%') => out.push('%'),
+            _ => out.push_str(".+"),
+        }
+    }
+    out.push('$');
+    out
+}
+
+/// Constraints that the validator applies to each scalar (or each array item).
+fn item_schema(rule: &SchemaRule) -> Map<String, Value> {
+    let mut item = Map::new();
+    let allowed = rule.enum_values.as_ref().or(if rule.allowed.is_empty() {
+        None
+    } else {
+        Some(&rule.allowed)
+    });
+    if let Some(values) = allowed {
+        item.insert("enum".into(), json!(values));
+    }
+    if let Some(re) = &rule.regex {
+        item.insert("type".into(), json!("string"));
+        item.insert("pattern".into(), json!(re));
+    }
+    if let Some(int) = &rule.integer {
+        item.insert("type".into(), json!("integer"));
+        if let Some(min) = int.min {
+            item.insert("minimum".into(), json!(min));
+        }
+        if let Some(max) = int.max {
+            item.insert("maximum".into(), json!(max));
+        }
+    }
+    if let Some(float) = &rule.float {
+        item.insert("type".into(), json!("number"));
+        if let Some(min) = float.min {
+            item.insert("minimum".into(), json!(min));
+        }
+        if let Some(max) = float.max {
+            item.insert("maximum".into(), json!(max));
+        }
+    }
+    match rule.r#type.as_deref() {
+        Some("date") => {
+            item.insert("type".into(), json!("string"));
+            match rule.format.as_deref() {
+                None | Some("%Y-%m-%d") => {
+                    item.insert("format".into(), json!("date"));
+                }
+                Some(fmt) => {
+                    item.insert("pattern".into(), json!(strftime_pattern(fmt)));
+                }
+            }
+        }
+        Some(t @ ("string" | "integer" | "number" | "boolean" | "object")) => {
+            item.insert("type".into(), json!(t));
+        }
+        _ => {}
+    }
+    item
+}
+
+/// JSON Schema for a single front matter field governed by a `SchemaRule`.
+pub fn rule_json_schema(rule: &SchemaRule) -> Value {
+    let item = item_schema(rule);
+    let is_array = rule.r#type.as_deref() == Some("array")
+        || rule.min_items.is_some()
+        || rule.refers_to_types.is_some();
+    let mut out = Map::new();
+    if is_array {
+        out.insert("type".into(), json!("array"));
+        if !item.is_empty() {
+            out.insert("items".into(), Value::Object(item));
+        }
+        if let Some(min) = rule.min_items {
+            out.insert("minItems".into(), json!(min));
+        }
+    } else if item.contains_key("enum") {
+        // The validator accepts either a single value or a list of values.
+        let item = Value::Object(item);
+        out.insert(
+            "anyOf".into(),
+            json!([item.clone(), {"type": "array", "items": item}]),
+        );
+    } else {
+        out = item;
+    }
+    if let Some(refs) = &rule.refers_to_types {
+        out.insert(
+            "description".into(),
+            json!(format!("IDs of notes with schema {}", refs.join(" | "))),
+        );
+    }
+    Value::Object(out)
+}
+
+fn reserved_properties(cfg: &Config) -> Map<String, Value> {
+    // Mirrors `model::FrontMatter`.
+    let list = json!({"type": "array", "items": {"type": "string"}});
+    let one_or_many = json!({"anyOf": [{"type": "string"}, list.clone()]});
+    let mut props = Map::new();
+    props.insert("id".into(), json!({"type": "string"}));
+    props.insert(
+        "status".into(),
+        json!({"type": "string", "enum": cfg.allowed_statuses}),
+    );
+    for key in ["tags", "groups", "depends_on"] {
+        props.insert(key.into(), list.clone());
+    }
+    for key in ["supersedes", "superseded_by"] {
+        props.insert(key.into(), one_or_many.clone());
+    }
+    props
+}
+
+/// JSON Schema for the front matter of notes matching `sc`.
+pub fn frontmatter_json_schema(cfg: &Config, sc: &SchemaCfg) -> Value {
+    let mut props = reserved_properties(cfg);
+    for key in sc.required.iter().chain(sc.allowed_keys.iter()) {
+        props.entry(key.clone()).or_insert_with(|| json!({}));
+    }
+    for (field, rule) in &sc.rules {
+        props.insert(field.clone(), rule_json_schema(rule));
+    }
+    // Only literal defaults are meaningful here; templated ones vary per note.
+    for (field, value) in sc.new.iter().flat_map(|n| n.defaults.iter()) {
+        if value.as_str().is_some_and(|s| s.contains("{{")) {
+            continue;
+        }
+        if let (Some(Value::Object(prop)), Ok(default)) =
+            (props.get_mut(field), serde_json::to_value(value))
+        {
+            prop.insert("default".into(), default);
+        }
+    }
+    // JSON Schema has no warnings, so only `unknown_policy = "error"` closes the object.
+    let closed = sc.unknown_policy.as_deref() == Some("error");
+    json!({
+        "$schema": DRAFT,
+        "$id": super::frontmatter_schema_filename(&sc.name),
+        "title": format!("{} front matter", sc.name),
+        "description": format!("Notes matching {}", sc.file_patterns.join(", ")),
+        "type": "object",
+        "required": sc.required,
+        "properties": props,
+        "additionalProperties": !closed
+    })
+}
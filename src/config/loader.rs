@@ -104,6 +104,12 @@ fn normalize_nested_user_config(mut tv: toml::Value) -> toml::Value {
         if let Some(V::Table(authoring)) = cfg_tbl.get("authoring") {
             root.insert("authoring".into(), V::Table(authoring.clone()));
         }
+        // [config.lua] -> lua, [config.search] -> search
+        for key in ["lua", "search"] {
+            if let Some(V::Table(t)) = cfg_tbl.get(key) {
+                root.insert(key.into(), V::Table(t.clone()));
+            }
+        }
         // We've consumed the nested table; not re-inserting keeps the normalized shape.
     }
     tv
@@ -152,6 +158,9 @@ pub fn load_config(
             defaults: default_defaults(),
             schema: Vec::new(),
             authoring: super::schema::AuthoringCfg::default(),
+            lua: super::schema::LuaCfg::default(),
+            search: super::schema::SearchCfg::default(),
+            view: Vec::new(),
             overlays: super::schema::OverlayInfo::default(),
         }
     };
@@ -193,31 +202,7 @@ pub fn load_config(
                     .push(cfg_path.display().to_string());
             }
             for patt in &cfg.import {
-                let patt_path = cfg_dir.join(patt);
-                let mut files: Vec<PathBuf> = Vec::new();
-                // Expand globs if any; if no matches, try as a direct file path.
-                let walk_res = GlobWalkerBuilder::from_patterns(cfg_dir, &[patt.as_str()])
-                    .max_depth(10)
-                    .follow_links(true)
-                    .build();
-                if let Ok(walker) = walk_res {
-                    for entry in walker.filter_map(Result::ok) {
-                        if entry.path().is_file() {
-                            files.push(entry.path().to_path_buf());
-                        }
-                    }
-                }
-                if files.is_empty() {
-                    if patt_path.exists() && patt_path.is_file() {
-                        files.push(patt_path);
-                    } else {
-                        let abs = PathBuf::from(patt);
-                        if abs.is_absolute() && abs.exists() && abs.is_file() {
-                            files.push(abs);
-                        }
-                    }
-                }
-                for fpath in files {
+                for fpath in expand_import(cfg_dir, patt) {
                     let s = fs::read_to_string(&fpath)
                         .with_context(|| format!("reading import {:?}", fpath))?;
                     let tv: toml::Value = toml::from_str(&s)
@@ -268,25 +253,62 @@ pub fn load_config(
             cfg.schema.extend(imported);
         }
     }
-    // Invariant: unique schema names across the effective config
-    if !cfg.schema.is_empty() {
-        use std::collections::BTreeMap;
-        let mut seen: BTreeMap<String, usize> = BTreeMap::new();
-        for sc in &cfg.schema {
-            *seen.entry(sc.name.clone()).or_insert(0) += 1;
+    // Invariant: unique schema and view names across the effective config
+    let dups = duplicate_names(cfg.schema.iter().map(|s| s.name.as_str()));
+    if !dups.is_empty() {
+        return Err(anyhow!(
+            "E120: Duplicate schema name(s) detected: {}",
+            dups.join(", ")
+        ));
+    }
+    let dups = duplicate_names(cfg.view.iter().map(|v| v.name.as_str()));
+    if !dups.is_empty() {
+        return Err(anyhow!(
+            "Duplicate view name(s) detected: {}",
+            dups.join(", ")
+        ));
+    }
+    Ok((cfg, path))
+}
+
+fn duplicate_names<'a>(names: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut seen: std::collections::BTreeMap<&str, usize> = std::collections::BTreeMap::new();
+    for n in names {
+        *seen.entry(n).or_insert(0) += 1;
+    }
+    seen.into_iter()
+        .filter(|(_, v)| *v > 1)
+        .map(|(k, _)| k.to_string())
+        .collect()
+}
+
+/// Expand one `import` entry relative to the config directory. Globs are walked first;
+/// if nothing matches, the entry is tried as a direct (relative or absolute) file path.
+pub(crate) fn expand_import(cfg_dir: &Path, patt: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    let walk_res = GlobWalkerBuilder::from_patterns(cfg_dir, &[patt])
+        .max_depth(10)
+        .follow_links(true)
+        .build();
+    if let Ok(walker) = walk_res {
+        for entry in walker.filter_map(Result::ok) {
+            if entry.path().is_file() {
+                files.push(entry.path().to_path_buf());
+            }
         }
-        let dups: Vec<String> = seen
-            .into_iter()
-            .filter_map(|(k, v)| if v > 1 { Some(k) } else { None })
-            .collect();
-        if !dups.is_empty() {
-            return Err(anyhow!(
-                "E120: Duplicate schema name(s) detected: {}",
-                dups.join(", ")
-            ));
+    }
+    if files.is_empty() {
+        let patt_path = cfg_dir.join(patt);
+        if patt_path.exists() && patt_path.is_file() {
+            files.push(patt_path);
+        } else {
+            let abs = PathBuf::from(patt);
+            if abs.is_absolute() && abs.exists() && abs.is_file() {
+                files.push(abs);
+            }
         }
     }
-    Ok((cfg, path))
+    files
 }
 
 // Helper: compile schema globsets once for reuse across modules.
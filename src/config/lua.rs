@@ -1,7 +1,12 @@
+use std::cell::Cell;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
-use crate::config::schema::OverlayInfo;
-use mlua::{Lua, Table, Value as LuaValue};
+use crate::config::schema::{LuaCfg, OverlayInfo};
+use mlua::{
+    FromLuaMulti, Function, HookTriggers, IntoLuaMulti, Lua, LuaOptions, StdLib, Table,
+    Value as LuaValue,
+};
 
 fn home_dir() -> Option<PathBuf> {
     if let Ok(h) = std::env::var("HOME") {
@@ -69,28 +74,204 @@ fn merge_tables(_base_lua: &Lua, base: &Table, add: &Table) -> mlua::Result<()>
     Ok(())
 }
 
-/// Load overlay Lua state and return a merged overlay table (repo overlaid by user).
-pub fn load_overlay_state(_cfg_path: &Option<PathBuf>, overlays: &OverlayInfo) -> Option<Lua> {
+// Chunk names used in Lua error positions ("repo_overlay:12: ...").
+const REPO_CHUNK: &str = "repo_overlay";
+const USER_CHUNK: &str = "user_overlay";
+
+/// Problem loading or running overlay code, attributed to the overlay file.
+#[derive(Debug, Clone)]
+pub struct OverlayDiagnostic {
+    pub path: PathBuf,
+    pub line: Option<u32>,
+    /// `LUA_SYNTAX`, `LUA_RUNTIME` or `LUA_LIMIT`.
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl OverlayDiagnostic {
+    /// Classify an mlua error and locate it in the overlay that raised it. Errors
+    /// without a position (limits) are attributed to the repo overlay if present.
+    pub fn from_error(err: &mlua::Error, overlays: &OverlayInfo) -> OverlayDiagnostic {
+        let text = err.to_string();
+        let code = match err {
+            mlua::Error::SyntaxError { .. } => "LUA_SYNTAX",
+            mlua::Error::MemoryError(_) => "LUA_LIMIT",
+            _ if text.contains(INSTRUCTION_LIMIT_MSG) => "LUA_LIMIT",
+            _ => "LUA_RUNTIME",
+        };
+        let located = [
+            (REPO_CHUNK, &overlays.repo_path),
+            (USER_CHUNK, &overlays.user_path),
+        ]
+        .into_iter()
+        .filter_map(|(chunk, path)| {
+            let at = text.find(&format!("{}:", chunk))?;
+            let rest = &text[at + chunk.len() + 1..];
+            let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+            Some((at, path.clone()?, digits.parse::<u32>().ok()))
+        })
+        .min_by_key(|(at, _, _)| *at);
+        let (path, line) = match located {
+            Some((_, path, line)) => (path, line),
+            None => (
+                overlays
+                    .repo_path
+                    .clone()
+                    .or_else(|| overlays.user_path.clone())
+                    .unwrap_or_default(),
+                None,
+            ),
+        };
+        // First line only, without the "chunk:line: " prefix and traceback.
+        let first = text.lines().next().unwrap_or("").trim();
+        let first = first.strip_prefix("runtime error: ").unwrap_or(first);
+        let first = first.strip_prefix("syntax error: ").unwrap_or(first);
+        let message = [REPO_CHUNK, USER_CHUNK]
+            .iter()
+            .find_map(|chunk| {
+                let rest = first.strip_prefix(chunk)?.strip_prefix(':')?;
+                let rest = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+                Some(rest.strip_prefix(": ").unwrap_or(rest).to_string())
+            })
+            .unwrap_or_else(|| first.to_string());
+        OverlayDiagnostic {
+            path,
+            line,
+            code,
+            message,
+        }
+    }
+
+    /// Validate-style message: `<overlay>: LUA[<code>]: <context>: <message> (line N)`.
+    pub fn to_message(&self, context: &str) -> String {
+        let mut text = format!("{}: LUA[{}]: ", self.path.display(), self.code);
+        if !context.is_empty() {
+            text.push_str(context);
+            text.push_str(": ");
+        }
+        text.push_str(&self.message);
+        if let Some(line) = self.line {
+            text.push_str(&format!(" (line {})", line));
+        }
+        text
+    }
+}
+
+const INSTRUCTION_LIMIT_MSG: &str = "instruction limit exceeded";
+// Instructions between budget checks.
+const HOOK_STEP: u32 = 1000;
+
+struct InstructionBudget(Rc<Cell<u64>>);
+
+// Trim globals that reach outside the process unless granted.
+fn sandbox_globals(lua: &Lua, has: &dyn Fn(&str) -> bool) -> mlua::Result<()> {
+    let globals = lua.globals();
+    if !has("os") {
+        let os: Table = globals.get("os")?;
+        let safe = lua.create_table()?;
+        for name in ["clock", "date", "difftime", "time"] {
+            safe.set(name, os.get::<_, LuaValue>(name)?)?;
+        }
+        globals.set("os", safe)?;
+    }
+    if !has("io") {
+        globals.set("dofile", LuaValue::Nil)?;
+        globals.set("loadfile", LuaValue::Nil)?;
+    }
+    Ok(())
+}
+
+/// Fresh Lua state with the sandboxed standard library and configured limits.
+/// Without capabilities there is no `io`, no `require`/`package`, no
+/// `dofile`/`loadfile`, and `os` only offers `clock`, `date`, `difftime`, `time`.
+pub fn sandboxed_state(sandbox: &LuaCfg) -> mlua::Result<Lua> {
+    let has = |cap: &str| sandbox.capabilities.iter().any(|c| c == cap);
+    let mut libs = StdLib::COROUTINE | StdLib::TABLE | StdLib::STRING | StdLib::UTF8;
+    libs |= StdLib::MATH | StdLib::OS;
+    if has("io") {
+        libs |= StdLib::IO;
+    }
+    if has("require") {
+        libs |= StdLib::PACKAGE;
+    }
+    let lua = Lua::new_with(libs, LuaOptions::default())?;
+    sandbox_globals(&lua, &has)?;
+    if sandbox.memory_limit_mb > 0 {
+        lua.set_memory_limit((sandbox.memory_limit_mb as usize).saturating_mul(1024 * 1024))?;
+    }
+    if sandbox.instruction_limit > 0 {
+        let used = Rc::new(Cell::new(0u64));
+        let limit = sandbox.instruction_limit;
+        let counter = Rc::clone(&used);
+        lua.set_hook(
+            HookTriggers::new().every_nth_instruction(HOOK_STEP),
+            move |_, _| {
+                counter.set(counter.get() + HOOK_STEP as u64);
+                if counter.get() > limit {
+                    return Err(mlua::Error::RuntimeError(format!(
+                        "{} ({})",
+                        INSTRUCTION_LIMIT_MSG, limit
+                    )));
+                }
+                Ok(())
+            },
+        );
+        lua.set_app_data(InstructionBudget(used));
+    }
+    Ok(lua)
+}
+
+/// Call an overlay function with a fresh instruction budget.
+pub fn call_hook<'lua, A, R>(lua: &'lua Lua, func: &Function<'lua>, args: A) -> mlua::Result<R>
+where
+    A: IntoLuaMulti<'lua>,
+    R: FromLuaMulti<'lua>,
+{
+    if let Some(budget) = lua.app_data_ref::<InstructionBudget>() {
+        budget.0.set(0);
+    }
+    func.call(args)
+}
+
+/// Load overlay Lua state with a merged `overlay` table (repo overlaid by user),
+/// reporting overlays that fail to load. The state is still returned when one
+/// overlay fails, so the other one's hooks keep working.
+pub fn load_overlay_state_checked(
+    overlays: &OverlayInfo,
+    sandbox: &LuaCfg,
+) -> (Option<Lua>, Vec<OverlayDiagnostic>) {
+    let mut diagnostics = Vec::new();
     if !overlays.enabled {
-        return None;
+        return (None, diagnostics);
     }
-    let lua = Lua::new();
-    let overlay_tbl = lua.create_table().ok()?;
+    let lua = match sandboxed_state(sandbox) {
+        Ok(lua) => lua,
+        Err(e) => {
+            diagnostics.push(OverlayDiagnostic::from_error(&e, overlays));
+            return (None, diagnostics);
+        }
+    };
+    let Ok(overlay_tbl) = lua.create_table() else {
+        return (None, diagnostics);
+    };
     // Load repo, then user (user overrides repo)
-    if let Some(ref rp) = overlays.repo_path {
-        if let Some(code) = read_file_if_exists(rp) {
-            let chunk = lua.load(&code).set_name("repo_overlay");
-            if let Ok(tbl) = chunk.eval::<Table>() {
-                let _ = merge_tables(&lua, &overlay_tbl, &tbl);
-            }
+    for (path, chunk) in [
+        (&overlays.repo_path, REPO_CHUNK),
+        (&overlays.user_path, USER_CHUNK),
+    ] {
+        let Some(code) = path.as_deref().and_then(read_file_if_exists) else {
+            continue;
+        };
+        if let Some(budget) = lua.app_data_ref::<InstructionBudget>() {
+            budget.0.set(0);
         }
-    }
-    if let Some(ref up) = overlays.user_path {
-        if let Some(code) = read_file_if_exists(up) {
-            let chunk = lua.load(&code).set_name("user_overlay");
-            if let Ok(tbl) = chunk.eval::<Table>() {
-                let _ = merge_tables(&lua, &overlay_tbl, &tbl);
-            }
+        let loaded = lua
+            .load(&code)
+            .set_name(format!("={}", chunk))
+            .eval::<Table>()
+            .and_then(|tbl| merge_tables(&lua, &overlay_tbl, &tbl));
+        if let Err(e) = loaded {
+            diagnostics.push(OverlayDiagnostic::from_error(&e, overlays));
         }
     }
     // set as global 'overlay'
@@ -98,5 +279,33 @@ pub fn load_overlay_state(_cfg_path: &Option<PathBuf>, overlays: &OverlayInfo) -
         let globals = lua.globals();
         let _ = globals.set("overlay", overlay_tbl);
     }
-    Some(lua)
+    (Some(lua), diagnostics)
+}
+
+/// Load overlay Lua state, ignoring load errors (`validate` and `info` report them).
+pub fn load_overlay_state(overlays: &OverlayInfo, sandbox: &LuaCfg) -> Option<Lua> {
+    load_overlay_state_checked(overlays, sandbox).0
+}
+
+/// Evaluate a single overlay file in isolation and list the hook names it defines
+/// (top-level keys whose value is a function), sorted for stable output.
+pub fn overlay_hook_names(path: &Path, sandbox: &LuaCfg) -> Vec<String> {
+    let Some(code) = read_file_if_exists(path) else {
+        return Vec::new();
+    };
+    let Ok(lua) = sandboxed_state(sandbox) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = Vec::new();
+    if let Ok(tbl) = lua.load(&code).set_name("overlay_probe").eval::<Table>() {
+        for (k, v) in tbl.pairs::<LuaValue, LuaValue>().flatten() {
+            if let (LuaValue::String(ks), LuaValue::Function(_)) = (k, v) {
+                if let Ok(name) = ks.to_str() {
+                    names.push(name.to_string());
+                }
+            }
+        }
+    }
+    names.sort();
+    names
 }
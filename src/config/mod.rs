@@ -1,7 +1,11 @@
 pub mod defaults;
+pub mod json_schema;
 pub mod loader;
 pub mod lua;
+pub mod provenance;
+pub mod resolved;
 pub mod schema;
+pub mod search;
 pub mod template;
 
 pub use defaults::*;
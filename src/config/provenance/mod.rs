@@ -0,0 +1,324 @@
+use serde::Serialize;
+use std::env;
+use std::path::{Path, PathBuf};
+
+mod overlays;
+mod schemas;
+mod toml_lines;
+
+use super::schema::Config;
+use toml_lines::find_key_line;
+
+/// Configuration layer that supplied an effective value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Layer {
+    File,
+    Import,
+    Env,
+    Cli,
+    Default,
+    Overlay,
+}
+
+impl Layer {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Layer::File => "file",
+            Layer::Import => "import",
+            Layer::Env => "env",
+            Layer::Cli => "cli",
+            Layer::Default => "default",
+            Layer::Overlay => "overlay",
+        }
+    }
+}
+
+/// One effective config key with the layer (and file/line when known) it came from.
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: serde_json::Value,
+    pub layer: Layer,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl ConfigEntry {
+    pub(crate) fn new(
+        key: &str,
+        value: serde_json::Value,
+        layer: Layer,
+        src: Option<(String, Option<usize>)>,
+    ) -> ConfigEntry {
+        let (source, line) = match src {
+            Some((s, l)) => (Some(s), l),
+            None => (None, None),
+        };
+        ConfigEntry {
+            key: key.to_string(),
+            value,
+            layer,
+            source,
+            line,
+        }
+    }
+}
+
+/// Inputs that shaped the effective config but are not recorded on `Config` itself.
+pub struct ExplainInputs<'a> {
+    pub explicit_config: &'a Option<PathBuf>,
+    pub base_override: &'a Option<Vec<PathBuf>>,
+    pub no_lua: bool,
+}
+
+pub(crate) struct ConfigFile {
+    path: String,
+    text: String,
+    value: toml::Value,
+}
+
+impl ConfigFile {
+    pub(crate) fn load(path: &Path) -> Option<ConfigFile> {
+        let text = std::fs::read_to_string(path).ok()?;
+        let value = toml::from_str(&text).ok()?;
+        Some(ConfigFile {
+            path: path.display().to_string(),
+            text,
+            value,
+        })
+    }
+
+    fn has(&self, table: &str, key: &str) -> bool {
+        let mut cur = &self.value;
+        for part in table.split('.').filter(|p| !p.is_empty()) {
+            match cur.get(part) {
+                Some(v) => cur = v,
+                None => return false,
+            }
+        }
+        cur.get(key).is_some()
+    }
+
+    /// First candidate `(table, key)` present in the file, as an entry source.
+    fn locate(&self, candidates: &[(&str, &str)]) -> Option<(String, Option<usize>)> {
+        candidates
+            .iter()
+            .find(|(t, k)| self.has(t, k))
+            .map(|(t, k)| (self.path.clone(), find_key_line(&self.text, t, k)))
+    }
+}
+
+pub(crate) fn env_flag(name: &str) -> bool {
+    env::var(name)
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Attribute every effective config key to the layer that produced it.
+pub fn explain_config(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    inputs: &ExplainInputs,
+) -> Vec<ConfigEntry> {
+    let file = cfg_path.as_deref().and_then(ConfigFile::load);
+    let mut out: Vec<ConfigEntry> = Vec::new();
+    let mut push =
+        |key: &str, value, layer, src| out.push(ConfigEntry::new(key, value, layer, src));
+    let from_file = |candidates: &[(&str, &str)]| file.as_ref().and_then(|f| f.locate(candidates));
+    let file_or_default = |candidates: &[(&str, &str)]| match from_file(candidates) {
+        Some(src) => (Layer::File, Some(src)),
+        None => (Layer::Default, None),
+    };
+
+    // Which config file was used and why
+    let cfg_path_json = serde_json::json!(cfg_path.as_ref().map(|p| p.display().to_string()));
+    if inputs.explicit_config.is_some() {
+        push(
+            "config.path",
+            cfg_path_json,
+            Layer::Cli,
+            Some(("--config".into(), None)),
+        );
+    } else if env::var("CLI_RAG_CONFIG").is_ok() && cfg_path.is_some() {
+        push(
+            "config.path",
+            cfg_path_json,
+            Layer::Env,
+            Some(("CLI_RAG_CONFIG".into(), None)),
+        );
+    } else if let Some(p) = cfg_path {
+        push(
+            "config.path",
+            cfg_path_json,
+            Layer::File,
+            Some((p.display().to_string(), None)),
+        );
+    } else {
+        push("config.path", cfg_path_json, Layer::Default, None);
+    }
+
+    let (layer, src) = file_or_default(&[("config", "config_version"), ("", "config_version")]);
+    push(
+        "configVersion",
+        serde_json::json!(cfg.config_version),
+        layer,
+        src,
+    );
+
+    // Bases: CLI > env > file > default
+    let bases = serde_json::json!(cfg
+        .bases
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>());
+    let env_bases = env::var("CLI_RAG_FILEPATHS")
+        .map(|v| v.split(',').any(|s| !s.trim().is_empty()))
+        .unwrap_or(false);
+    if inputs.base_override.as_ref().is_some_and(|b| !b.is_empty()) {
+        push(
+            "scan.filepaths",
+            bases,
+            Layer::Cli,
+            Some(("--base".into(), None)),
+        );
+    } else if env_bases {
+        push(
+            "scan.filepaths",
+            bases,
+            Layer::Env,
+            Some(("CLI_RAG_FILEPATHS".into(), None)),
+        );
+    } else {
+        let (layer, src) = file_or_default(&[
+            ("config.scan", "filepaths"),
+            ("", "bases"),
+            ("", "filepaths"),
+        ]);
+        push("scan.filepaths", bases, layer, src);
+    }
+
+    let simple = vec![
+        (
+            "scan.indexPath",
+            serde_json::json!(cfg.index_relative),
+            vec![("config.scan", "index_path"), ("", "index_relative")],
+        ),
+        (
+            "scan.ignoreGlobs",
+            serde_json::json!(cfg.ignore_globs),
+            vec![("config.scan", "ignore_globs"), ("", "ignore_globs")],
+        ),
+        (
+            "scan.filePatterns",
+            serde_json::json!(cfg.file_patterns),
+            vec![("", "file_patterns")],
+        ),
+        (
+            "allowedStatuses",
+            serde_json::json!(cfg.allowed_statuses),
+            vec![("", "allowed_statuses")],
+        ),
+        (
+            "graph.depth",
+            serde_json::json!(cfg.defaults.depth),
+            vec![("config.graph", "depth"), ("defaults", "depth")],
+        ),
+        (
+            "graph.includeBidirectional",
+            serde_json::json!(cfg.defaults.include_bidirectional),
+            vec![
+                ("config.graph", "include_bidirectional"),
+                ("defaults", "include_bidirectional"),
+            ],
+        ),
+        (
+            "graph.ai",
+            serde_json::json!({"depth": 1, "defaultFanout": 5, "includeBidirectional": true, "neighborStyle": "metadata", "outlineLines": 2}),
+            vec![],
+        ),
+        (
+            "authoring.editor",
+            serde_json::json!(cfg.authoring.editor.as_deref().unwrap_or("nvim")),
+            vec![("config.authoring", "editor"), ("authoring", "editor")],
+        ),
+        (
+            "authoring.backgroundWatch",
+            serde_json::json!(cfg.authoring.background_watch.unwrap_or(true)),
+            vec![
+                ("config.authoring", "background_watch"),
+                ("authoring", "background_watch"),
+            ],
+        ),
+        (
+            "authoring.outputPath",
+            serde_json::json!(cfg.authoring.output_path),
+            vec![
+                ("config.authoring", "output_path"),
+                ("authoring", "output_path"),
+            ],
+        ),
+        (
+            "lua.capabilities",
+            serde_json::json!(cfg.lua.capabilities),
+            vec![("config.lua", "capabilities"), ("lua", "capabilities")],
+        ),
+        (
+            "lua.instructionLimit",
+            serde_json::json!(cfg.lua.instruction_limit),
+            vec![
+                ("config.lua", "instruction_limit"),
+                ("lua", "instruction_limit"),
+            ],
+        ),
+        (
+            "lua.memoryLimitMb",
+            serde_json::json!(cfg.lua.memory_limit_mb),
+            vec![
+                ("config.lua", "memory_limit_mb"),
+                ("lua", "memory_limit_mb"),
+            ],
+        ),
+        (
+            "templates.import",
+            serde_json::json!(cfg.import),
+            vec![("config.templates", "import"), ("", "import")],
+        ),
+    ];
+    for (key, value, candidates) in simple {
+        let (layer, src) = file_or_default(&candidates);
+        push(key, value, layer, src);
+    }
+    let mut destinations: Vec<(&String, &String)> = cfg.authoring.destinations.iter().collect();
+    destinations.sort();
+    for (schema, dest) in destinations {
+        let (layer, src) = file_or_default(&[
+            ("config.authoring.destinations", schema.as_str()),
+            ("authoring.destinations", schema.as_str()),
+        ]);
+        push(
+            &format!("authoring.destinations.{}", schema),
+            serde_json::json!(dest),
+            layer,
+            src,
+        );
+    }
+    let b = &cfg.search.boosts;
+    for (field, boost) in [
+        ("id", b.id),
+        ("title", b.title),
+        ("tags", b.tags),
+        ("headings", b.headings),
+    ] {
+        let (layer, src) =
+            file_or_default(&[("config.search.boosts", field), ("search.boosts", field)]);
+        let key = format!("search.boosts.{}", field);
+        push(&key, serde_json::json!(boost), layer, src);
+    }
+
+    schemas::schema_provenance(cfg, cfg_path, file.as_ref(), &mut out);
+    overlays::overlay_provenance(cfg, inputs, &mut out);
+    out
+}
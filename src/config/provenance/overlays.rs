@@ -0,0 +1,77 @@
+use std::path::PathBuf;
+
+use super::{env_flag, ConfigEntry, ExplainInputs, Layer};
+use crate::config::lua::overlay_hook_names;
+use crate::config::schema::Config;
+
+/// Overlay state: disabled by CLI/env, otherwise attributed to the overlay files and hooks.
+pub(super) fn overlay_provenance(cfg: &Config, inputs: &ExplainInputs, out: &mut Vec<ConfigEntry>) {
+    let mut push =
+        |key: &str, value, layer, src| out.push(ConfigEntry::new(key, value, layer, src));
+    let overlay_src = |p: &Option<PathBuf>| p.as_ref().map(|p| (p.display().to_string(), None));
+    let enabled = serde_json::json!(cfg.overlays.enabled);
+    if inputs.no_lua {
+        push(
+            "overlays.enabled",
+            enabled,
+            Layer::Cli,
+            Some(("--no-lua".into(), None)),
+        );
+    } else if env_flag("CLI_RAG_NO_LUA") {
+        push(
+            "overlays.enabled",
+            enabled,
+            Layer::Env,
+            Some(("CLI_RAG_NO_LUA".into(), None)),
+        );
+    } else if cfg.overlays.enabled {
+        let src = overlay_src(&cfg.overlays.repo_path).or(overlay_src(&cfg.overlays.user_path));
+        push("overlays.enabled", enabled, Layer::Overlay, src);
+    } else {
+        push("overlays.enabled", enabled, Layer::Default, None);
+    }
+    for (key, path) in [
+        ("overlays.repoPath", &cfg.overlays.repo_path),
+        ("overlays.userPath", &cfg.overlays.user_path),
+    ] {
+        if let Some(p) = path {
+            push(
+                key,
+                serde_json::json!(p.display().to_string()),
+                Layer::Overlay,
+                overlay_src(path),
+            );
+        }
+    }
+    if cfg.overlays.enabled {
+        let repo_hooks = cfg
+            .overlays
+            .repo_path
+            .as_deref()
+            .map(|p| overlay_hook_names(p, &cfg.lua))
+            .unwrap_or_default();
+        let user_hooks = cfg
+            .overlays
+            .user_path
+            .as_deref()
+            .map(|p| overlay_hook_names(p, &cfg.lua))
+            .unwrap_or_default();
+        let mut all: Vec<&String> = repo_hooks.iter().chain(user_hooks.iter()).collect();
+        all.sort();
+        all.dedup();
+        for hook in all {
+            // User overlay is merged last, so it wins when both define a hook
+            let path = if user_hooks.contains(hook) {
+                &cfg.overlays.user_path
+            } else {
+                &cfg.overlays.repo_path
+            };
+            push(
+                &format!("overlays.hooks.{}", hook),
+                serde_json::json!("function"),
+                Layer::Overlay,
+                overlay_src(path),
+            );
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+
+use super::toml_lines::{find_schema_key_line, schema_name_line};
+use super::{ConfigEntry, ConfigFile, Layer};
+use crate::config::loader::expand_import;
+use crate::config::schema::{Config, SchemaCfg};
+
+/// Attribute each schema key to the config file or import that defined the schema.
+pub(super) fn schema_provenance(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    file: Option<&ConfigFile>,
+    out: &mut Vec<ConfigEntry>,
+) {
+    let imports: Vec<ConfigFile> = cfg_path
+        .as_ref()
+        .and_then(|p| p.parent())
+        .map(|dir| {
+            cfg.import
+                .iter()
+                .flat_map(|patt| expand_import(dir, patt))
+                .filter_map(|p| ConfigFile::load(&p))
+                .collect()
+        })
+        .unwrap_or_default();
+    for sc in &cfg.schema {
+        let origin = file
+            .filter(|f| schema_name_line(&f.text, &sc.name).is_some())
+            .map(|f| (Layer::File, f))
+            .or_else(|| {
+                imports
+                    .iter()
+                    .find(|f| schema_name_line(&f.text, &sc.name).is_some())
+                    .map(|f| (Layer::Import, f))
+            });
+        for (key, value, table, toml_key) in schema_entries(sc) {
+            let src = origin.map(|(_, f)| {
+                let line = find_schema_key_line(&f.text, &sc.name, table, &toml_key)
+                    .or_else(|| schema_name_line(&f.text, &sc.name));
+                (f.path.clone(), line)
+            });
+            let layer = origin.map(|(l, _)| l).unwrap_or(Layer::Default);
+            out.push(ConfigEntry::new(&key, value, layer, src));
+        }
+    }
+}
+
+/// Effective per-schema keys: `(key, value, toml table, toml key)`.
+fn schema_entries(sc: &SchemaCfg) -> Vec<(String, serde_json::Value, &'static str, String)> {
+    let prefix = format!("schemas.{}", sc.name);
+    let mut out = vec![(
+        format!("{}.filePatterns", prefix),
+        serde_json::json!(sc.file_patterns),
+        "schema",
+        "file_patterns".to_string(),
+    )];
+    if !sc.required.is_empty() {
+        out.push((
+            format!("{}.required", prefix),
+            serde_json::json!(sc.required),
+            "schema",
+            "required".into(),
+        ));
+    }
+    if !sc.allowed_keys.is_empty() {
+        out.push((
+            format!("{}.allowedKeys", prefix),
+            serde_json::json!(sc.allowed_keys),
+            "schema",
+            "allowed_keys".into(),
+        ));
+    }
+    if let Some(policy) = &sc.unknown_policy {
+        out.push((
+            format!("{}.unknownPolicy", prefix),
+            serde_json::json!(policy),
+            "schema",
+            "unknown_policy".into(),
+        ));
+    }
+    if let Some(policy) = &sc.cycle_policy {
+        out.push((
+            format!("{}.cyclePolicy", prefix),
+            serde_json::json!(policy),
+            "schema",
+            "cycle_policy".into(),
+        ));
+    }
+    for (field, rule) in &sc.rules {
+        let mut value = serde_json::Map::new();
+        if let Some(t) = &rule.r#type {
+            value.insert("type".into(), serde_json::json!(t));
+        }
+        if let Some(e) = rule
+            .enum_values
+            .as_ref()
+            .or(Some(&rule.allowed).filter(|a| !a.is_empty()))
+        {
+            value.insert("enum".into(), serde_json::json!(e));
+        }
+        if let Some(r) = &rule.regex {
+            value.insert("regex".into(), serde_json::json!(r));
+        }
+        if let Some(s) = &rule.severity {
+            value.insert("severity".into(), serde_json::json!(s));
+        }
+        out.push((
+            format!("{}.rules.{}", prefix, field),
+            serde_json::Value::Object(value),
+            "schema.rules",
+            field.clone(),
+        ));
+    }
+    for (field, value) in sc.new.iter().flat_map(|n| n.defaults.iter()) {
+        out.push((
+            format!("{}.new.defaults.{}", prefix, field),
+            serde_json::to_value(value).unwrap_or(serde_json::Value::Null),
+            "schema.new.defaults",
+            field.clone(),
+        ));
+    }
+    if sc.validate.is_some() {
+        out.push((
+            format!("{}.validate", prefix),
+            serde_json::json!(true),
+            "schema",
+            "validate".into(),
+        ));
+    }
+    out
+}
@@ -0,0 +1,177 @@
+// Line lookups over raw TOML text. The `toml` crate drops spans, so provenance
+// reporting re-scans the source to point at the line that set a key.
+
+fn header_name(line: &str) -> Option<String> {
+    let line = line.split('#').next().unwrap_or("").trim();
+    let inner = if let Some(rest) = line.strip_prefix("[[") {
+        rest.strip_suffix("]]")?
+    } else if let Some(rest) = line.strip_prefix('[') {
+        rest.strip_suffix(']')?
+    } else {
+        return None;
+    };
+    Some(
+        inner
+            .split('.')
+            .map(|part| part.trim().trim_matches('"').trim_matches('\''))
+            .collect::<Vec<_>>()
+            .join("."),
+    )
+}
+
+fn assigns_key(line: &str, key: &str) -> bool {
+    let line = line.trim_start();
+    let rest = if let Some(r) = line.strip_prefix(key) {
+        r
+    } else if let Some(r) = line
+        .strip_prefix('"')
+        .and_then(|r| r.strip_prefix(key))
+        .and_then(|r| r.strip_prefix('"'))
+    {
+        r
+    } else if let Some(r) = line
+        .strip_prefix('\'')
+        .and_then(|r| r.strip_prefix(key))
+        .and_then(|r| r.strip_prefix('\''))
+    {
+        r
+    } else {
+        return false;
+    };
+    rest.trim_start().starts_with('=')
+}
+
+fn join(table: &str, key: &str) -> String {
+    if table.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", table, key)
+    }
+}
+
+fn scan_range(
+    lines: &[&str],
+    range: (usize, usize),
+    initial_table: &str,
+    table: &str,
+    key: &str,
+) -> Option<usize> {
+    let sub_header = join(table, key);
+    let mut current = initial_table.to_string();
+    for (i, raw) in lines.iter().enumerate().take(range.1).skip(range.0) {
+        if let Some(h) = header_name(raw) {
+            if h == sub_header {
+                return Some(i + 1);
+            }
+            current = h;
+            continue;
+        }
+        if current == table && assigns_key(raw, key) {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+/// Locate the 1-based line where `key` is set inside `[table]` (use "" for the root table).
+/// A sub-table header `[table.key]` also counts as the defining line.
+pub fn find_key_line(text: &str, table: &str, key: &str) -> Option<usize> {
+    let lines: Vec<&str> = text.lines().collect();
+    scan_range(&lines, (0, lines.len()), "", table, key)
+}
+
+/// Return the 0-based half-open line range of the `[[schema]]` block whose `name` matches.
+pub fn schema_block(text: &str, name: &str) -> Option<(usize, usize)> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut start: Option<usize> = None;
+    let mut matched = false;
+    let mut current = String::new();
+    for (i, raw) in lines.iter().enumerate() {
+        if let Some(h) = header_name(raw) {
+            let new_block = raw.trim_start().starts_with("[[") && h == "schema";
+            let left_schema = h != "schema" && !h.starts_with("schema.");
+            if new_block || left_schema {
+                if matched {
+                    return start.map(|s| (s, i));
+                }
+                start = if new_block { Some(i) } else { None };
+            }
+            current = h;
+            continue;
+        }
+        if start.is_some() && current == "schema" && assigns_key(raw, "name") {
+            let value = raw.split_once('=').map(|(_, v)| v).unwrap_or("");
+            let value = value.split('#').next().unwrap_or("").trim();
+            if value.trim_matches('"').trim_matches('\'') == name {
+                matched = true;
+            }
+        }
+    }
+    if matched {
+        start.map(|s| (s, lines.len()))
+    } else {
+        None
+    }
+}
+
+/// Find the 1-based line of `key` under `[table]` within the named `[[schema]]` block.
+/// `table` is the full dotted path, e.g. `schema` or `schema.rules`.
+pub fn find_schema_key_line(text: &str, name: &str, table: &str, key: &str) -> Option<usize> {
+    let range = schema_block(text, name)?;
+    let lines: Vec<&str> = text.lines().collect();
+    scan_range(&lines, range, "schema", table, key)
+}
+
+/// 1-based line of the `name = "..."` assignment for a schema block.
+pub fn schema_name_line(text: &str, name: &str) -> Option<usize> {
+    find_schema_key_line(text, name, "schema", "name")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"bases = ["notes"]
+
+[config.scan]
+index_path = ".cli-rag/index.json"
+
+[[schema]]
+name = "ADR"
+file_patterns = ["ADR-*.md"]
+
+[schema.rules.status]
+enum = ["draft"]
+
+[[schema]]
+name = "IMP"
+file_patterns = ["IMP-*.md"]
+[schema.rules]
+priority = { enum = ["low"] }
+"#;
+
+    #[test]
+    fn finds_root_and_nested_keys() {
+        assert_eq!(find_key_line(SAMPLE, "", "bases"), Some(1));
+        assert_eq!(find_key_line(SAMPLE, "config.scan", "index_path"), Some(4));
+        assert_eq!(find_key_line(SAMPLE, "config.scan", "filepaths"), None);
+    }
+
+    #[test]
+    fn scopes_lookups_to_schema_blocks() {
+        assert_eq!(schema_block(SAMPLE, "ADR"), Some((5, 12)));
+        assert_eq!(schema_name_line(SAMPLE, "IMP"), Some(14));
+        assert_eq!(
+            find_schema_key_line(SAMPLE, "ADR", "schema.rules", "status"),
+            Some(10)
+        );
+        assert_eq!(
+            find_schema_key_line(SAMPLE, "IMP", "schema.rules", "priority"),
+            Some(17)
+        );
+        assert_eq!(
+            find_schema_key_line(SAMPLE, "IMP", "schema", "file_patterns"),
+            Some(15)
+        );
+    }
+}
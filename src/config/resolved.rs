@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use super::schema::Config;
+
+/// Build the resolved config snapshot (camelCase) aligned to
+/// `contracts/v1/config/resolved_config.json`.
+pub fn resolved_config_json(cfg: &Config, project_root: &Path) -> serde_json::Value {
+    let scan = serde_json::json!({
+        "filepaths": cfg.bases.iter().map(|p| p.display().to_string()).collect::<Vec<_>>(),
+        "indexPath": project_root.join(&cfg.index_relative).display().to_string(),
+        "hashMode": "mtime",
+        "indexStrategy": "content",
+        "ignoreGlobs": cfg.ignore_globs,
+        "ignoreSymlinks": true
+    });
+    let authoring = serde_json::json!({
+        "editor": cfg
+            .authoring
+            .editor
+            .clone()
+            .unwrap_or_else(|| "nvim".to_string()),
+        "backgroundWatch": cfg.authoring.background_watch.unwrap_or(true),
+        "outputPath": cfg.authoring.output_path.clone(),
+        "destinations": cfg.authoring.destinations.clone()
+    });
+    let graph = serde_json::json!({
+        "depth": cfg.defaults.depth as i64,
+        "includeBidirectional": cfg.defaults.include_bidirectional,
+        "ai": {"depth": 1, "defaultFanout": 5, "includeBidirectional": true, "neighborStyle": "metadata", "outlineLines": 2}
+    });
+    let templates = serde_json::json!({
+        "import": cfg.import
+    });
+    let schemas: Vec<serde_json::Value> = cfg
+        .schema
+        .iter()
+        .map(|s| serde_json::json!({"name": s.name, "filePatterns": s.file_patterns}))
+        .collect();
+    let overlays = serde_json::json!({
+        "enabled": cfg.overlays.enabled,
+        "repoPath": cfg
+            .overlays
+            .repo_path
+            .as_ref()
+            .map(|p| p.display().to_string()),
+        "userPath": cfg
+            .overlays
+            .user_path
+            .as_ref()
+            .map(|p| p.display().to_string()),
+        "sandbox": {
+            "capabilities": cfg.lua.capabilities,
+            "instructionLimit": cfg.lua.instruction_limit,
+            "memoryLimitMb": cfg.lua.memory_limit_mb,
+        },
+    });
+    serde_json::json!({
+        "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+        "configVersion": cfg.config_version.as_deref().unwrap_or("0.1"),
+        "luaApiVersion": 1,
+        "projectRoot": project_root.display().to_string(),
+        "scan": scan,
+        "authoring": authoring,
+        "graph": graph,
+        "templates": templates,
+        "schemas": schemas,
+        "overlays": overlays
+    })
+}
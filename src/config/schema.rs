@@ -2,6 +2,8 @@ use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+pub use super::search::{SearchBoosts, SearchCfg, ViewCfg};
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct IdGeneratorCfg {
     #[serde(default)]
@@ -24,6 +26,9 @@ pub struct SchemaNewCfg {
     pub output_path: Option<Vec<String>>,
     #[serde(default)]
     pub template: Option<SchemaTemplateCfg>,
+    /// Front matter values seeded into new notes; strings may use template tokens.
+    #[serde(default)]
+    pub defaults: std::collections::BTreeMap<String, toml::Value>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -47,6 +52,29 @@ pub struct OverlayInfo {
     pub user_path: Option<PathBuf>,
 }
 
+/// `[config.lua]`: sandbox for overlay code. Limits of 0 disable the limit.
+#[derive(Debug, Deserialize, Clone)]
+pub struct LuaCfg {
+    /// Opt-in libraries beyond the sandbox: `io`, `os`, `require`.
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    /// VM instructions allowed per hook call (and for loading an overlay).
+    #[serde(default = "crate::config::defaults::default_lua_instruction_limit")]
+    pub instruction_limit: u64,
+    #[serde(default = "crate::config::defaults::default_lua_memory_limit_mb")]
+    pub memory_limit_mb: u64,
+}
+
+impl Default for LuaCfg {
+    fn default() -> Self {
+        LuaCfg {
+            capabilities: Vec::new(),
+            instruction_limit: crate::config::defaults::default_lua_instruction_limit(),
+            memory_limit_mb: crate::config::defaults::default_lua_memory_limit_mb(),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct AuthoringCfg {
     #[serde(default)]
@@ -93,6 +121,8 @@ pub struct SchemaRule {
     pub integer: Option<IntegerRule>,
     #[serde(default)]
     pub float: Option<FloatRule>,
+    #[serde(default)]
+    pub date: Option<DateRule>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -111,6 +141,32 @@ pub struct FloatRule {
     pub max: Option<f64>,
 }
 
+/// Date comparisons for a field. Bounds are `YYYY-MM-DD`, `today`, `today+30d`
+/// (d/w units), or the name of another date field in the same note.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct DateRule {
+    #[serde(default)]
+    pub not_before: Option<String>,
+    #[serde(default)]
+    pub not_after: Option<String>,
+    /// Treat the field as a review date: warn once it has passed.
+    #[serde(default)]
+    pub review_by: bool,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SchemaStalenessCfg {
+    pub after_days: u64,
+    /// Only notes with one of these statuses can go stale (empty = any status).
+    #[serde(default)]
+    pub statuses: Vec<String>,
+    /// Date field holding the last change; defaults to the file's mtime.
+    #[serde(default)]
+    pub field: Option<String>,
+    #[serde(default)]
+    pub severity: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Clone, Default)]
 pub struct SchemaBodyHeadingsCfg {
     #[serde(default)]
@@ -181,6 +237,21 @@ pub struct SchemaValidateCfg {
     pub body: Option<SchemaBodyValidateCfg>,
     #[serde(default)]
     pub edges: Option<SchemaEdgesCfg>,
+    #[serde(default)]
+    pub staleness: Option<SchemaStalenessCfg>,
+}
+
+/// `[schema.backlinks]`: keep a generated "Referenced by" block in each note.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SchemaBacklinksCfg {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Edge kinds to list (default: all incoming kinds).
+    #[serde(default)]
+    pub kinds: Vec<String>,
+    /// Severity of the stale-block check in `validate` (default warning).
+    #[serde(default)]
+    pub severity: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -203,6 +274,8 @@ pub struct SchemaCfg {
     pub rules: std::collections::BTreeMap<String, SchemaRule>,
     #[serde(default)]
     pub validate: Option<SchemaValidateCfg>,
+    #[serde(default)]
+    pub backlinks: Option<SchemaBacklinksCfg>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -233,6 +306,12 @@ pub struct Config {
     pub schema: Vec<SchemaCfg>,
     #[serde(default)]
     pub authoring: AuthoringCfg,
+    #[serde(default)]
+    pub lua: LuaCfg,
+    #[serde(default)]
+    pub search: SearchCfg,
+    #[serde(default)]
+    pub view: Vec<ViewCfg>,
 
     // Runtime-only overlay metadata (not part of TOML)
     #[serde(skip)]
@@ -0,0 +1,52 @@
+//! Search-related config: `[config.search]` ranking and `[[view]]` saved searches.
+
+use serde::Deserialize;
+
+/// `[config.search]`: ranking for free-text search terms.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct SearchCfg {
+    #[serde(default)]
+    pub boosts: SearchBoosts,
+}
+
+/// Score multipliers per matched field; 0 excludes the field from matching.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SearchBoosts {
+    pub id: f64,
+    pub title: f64,
+    pub tags: f64,
+    pub headings: f64,
+}
+
+impl Default for SearchBoosts {
+    fn default() -> Self {
+        SearchBoosts {
+            id: 3.0,
+            title: 2.0,
+            tags: 1.5,
+            headings: 1.0,
+        }
+    }
+}
+
+/// `[[view]]`: a saved search run with `cli-rag view <name>`.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ViewCfg {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Search query expression (`search --query` syntax); empty matches every note.
+    #[serde(default)]
+    pub query: String,
+    /// Item kinds to include (note, todo, kanban); defaults to notes.
+    #[serde(default)]
+    pub kind: Option<Vec<String>>,
+    /// Sort keys in priority order; a leading `-` sorts descending.
+    #[serde(default)]
+    pub sort: Vec<String>,
+    #[serde(default = "crate::config::defaults::default_view_columns")]
+    pub columns: Vec<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
@@ -0,0 +1,57 @@
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+use crate::config::Config;
+
+/// Ask a running daemon to answer `method`. `None` means no usable daemon
+/// (not running, disabled via `CLI_RAG_NO_DAEMON=1`, serving another config,
+/// or unreachable) and the caller should compute the result itself.
+#[cfg(unix)]
+pub fn request(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    method: &str,
+    params: Value,
+) -> Option<Result<Value>> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    if std::env::var("CLI_RAG_NO_DAEMON").is_ok_and(|v| v == "1") {
+        return None;
+    }
+    let socket = super::socket_path(cfg_path)?;
+    let stream = UnixStream::connect(socket).ok()?;
+    stream
+        .set_read_timeout(Some(Duration::from_secs(30)))
+        .ok()?;
+    let mut req = super::config_identity(cfg, cfg_path);
+    req["protocolVersion"] = json!(crate::protocol::PROTOCOL_VERSION);
+    req["method"] = json!(method);
+    req["params"] = params;
+    writeln!(&stream, "{}", req).ok()?;
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line).ok()?;
+    let reply: Value = serde_json::from_str(&line).ok()?;
+    if reply["ok"] == true {
+        return Some(Ok(reply["result"].clone()));
+    }
+    match reply["code"].as_str() {
+        Some("error") => Some(Err(anyhow!(
+            "{}",
+            reply["error"].as_str().unwrap_or("daemon error")
+        ))),
+        _ => None,
+    }
+}
+
+#[cfg(not(unix))]
+pub fn request(
+    _cfg: &Config,
+    _cfg_path: &Option<PathBuf>,
+    _method: &str,
+    _params: Value,
+) -> Option<Result<Value>> {
+    None
+}
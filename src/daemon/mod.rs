@@ -0,0 +1,124 @@
+//! `cli-rag daemon`: keep the notes warm in memory (updated by the watch
+//! machinery) and answer `search`/`get` over a Unix domain socket.
+//!
+//! Wire format: one JSON request line per connection,
+//! `{"protocolVersion", "method", "params", "config", "bases", "configHash"}`,
+//! answered by one line `{"ok": true, "result": ...}` or
+//! `{"ok": false, "code", "error"}`. Results are the same JSON bodies the CLI
+//! prints with `--format json`. The daemon exits once its config changes on disk.
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::commands::get_json::{ai_get_json, violates_full_depth};
+use crate::commands::search::collect_results;
+use crate::commands::search_query::SearchQuery;
+use crate::config::Config;
+use crate::watch::WatchState;
+
+mod client;
+#[cfg(unix)]
+mod server;
+
+pub use client::request;
+#[cfg(unix)]
+pub use server::run;
+
+/// Socket for the project whose config is `cfg_path`: `<config dir>/.cli-rag/daemon.sock`.
+pub fn socket_path(cfg_path: &Option<PathBuf>) -> Option<PathBuf> {
+    let dir = cfg_path.as_ref()?.parent()?;
+    Some(dir.join(".cli-rag").join("daemon.sock"))
+}
+
+/// Digest of the config file and the schema files it imports, as they are on
+/// disk now, so a daemon started before an edit is never asked again.
+fn config_hash(cfg: &Config, cfg_path: &Option<PathBuf>) -> Option<String> {
+    use sha2::{Digest, Sha256};
+    let path = cfg_path.as_ref()?;
+    let mut hasher = Sha256::new();
+    hasher.update(std::fs::read(path).ok()?);
+    let dir = path.parent()?;
+    for patt in &cfg.import {
+        for file in crate::config::loader::expand_import(dir, patt) {
+            hasher.update(file.to_string_lossy().as_bytes());
+            hasher.update(std::fs::read(&file).unwrap_or_default());
+        }
+    }
+    let hex: String = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    Some(format!("sha256:{}", hex))
+}
+
+/// Identifies the project a request was resolved against.
+fn config_identity(cfg: &Config, cfg_path: &Option<PathBuf>) -> Value {
+    let config = cfg_path
+        .as_ref()
+        .map(|p| p.canonicalize().unwrap_or_else(|_| p.clone()));
+    json!({
+        "config": config,
+        "bases": cfg.bases,
+        "configHash": config_hash(cfg, cfg_path),
+    })
+}
+
+#[cfg(not(unix))]
+pub fn run(_cfg: &Config, _cfg_path: &Option<PathBuf>, _debounce_ms: u64) -> Result<()> {
+    Err(anyhow!("cli-rag daemon requires Unix domain sockets"))
+}
+
+fn str_list(v: &Value) -> Option<Vec<String>> {
+    v.as_array().map(|a| {
+        a.iter()
+            .filter_map(|s| s.as_str().map(String::from))
+            .collect()
+    })
+}
+
+/// Answer one request against the in-memory state.
+fn dispatch(
+    cfg: &Config,
+    state: &Mutex<WatchState>,
+    method: &str,
+    params: &Value,
+) -> Result<Value> {
+    let docs = state.lock().unwrap_or_else(|e| e.into_inner()).docs();
+    match method {
+        "ping" => Ok(json!({"docCount": docs.len()})),
+        "search" => {
+            let q = params["query"].as_str().unwrap_or("");
+            let query = SearchQuery::parse(q).map_err(|e| anyhow!(e.render(q)))?;
+            let results = collect_results(
+                cfg,
+                &docs,
+                &query,
+                str_list(&params["kind"]),
+                str_list(&params["schema"]),
+                str_list(&params["status"]),
+                str_list(&params["tag"]),
+            );
+            Ok(json!({
+                "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+                "results": results,
+            }))
+        }
+        "get" => {
+            let id = params["id"].as_str().unwrap_or("");
+            let style = params["neighborStyle"].as_str().unwrap_or("metadata");
+            let depth = params["depth"].as_u64().unwrap_or(1) as usize;
+            let max_fanout = params["maxFanout"].as_u64().unwrap_or(5) as usize;
+            if violates_full_depth(style, depth) {
+                return Err(anyhow!(
+                    "neighborStyle=full with depth>1 (NEIGHBORS_FULL_DEPTH_GT1)"
+                ));
+            }
+            let section = params["section"].as_str();
+            ai_get_json(cfg, &docs, id, style, depth, max_fanout, section)
+        }
+        other => Err(anyhow!("unknown method '{}'", other)),
+    }
+}
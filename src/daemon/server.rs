@@ -0,0 +1,120 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::{config_identity, dispatch, socket_path};
+use crate::config::Config;
+use crate::watch::{initial_state, watch_loop, WatchArgs, WatchState};
+
+/// How long a client may take to send its request line.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Stop serving: remove the socket so clients fall back to local evaluation.
+fn shut_down(socket: &Path, reason: &str, code: i32) -> ! {
+    eprintln!("cli-rag daemon stopping: {}", reason);
+    let _ = std::fs::remove_file(socket);
+    std::process::exit(code)
+}
+
+struct Daemon<'a> {
+    cfg: &'a Config,
+    cfg_path: &'a Option<PathBuf>,
+    socket: PathBuf,
+    identity: Value,
+    state: Mutex<WatchState>,
+}
+
+fn respond(d: &Daemon, line: &str) -> Value {
+    let Ok(req) = serde_json::from_str::<Value>(line) else {
+        return json!({"ok": false, "code": "bad_request", "error": "request is not JSON"});
+    };
+    if req["config"] != d.identity["config"]
+        || req["bases"] != d.identity["bases"]
+        || req["configHash"] != d.identity["configHash"]
+    {
+        return json!({
+            "ok": false,
+            "code": "config_mismatch",
+            "error": "daemon serves a different config or bases",
+        });
+    }
+    let method = req["method"].as_str().unwrap_or("");
+    match dispatch(d.cfg, &d.state, method, &req["params"]) {
+        Ok(result) => json!({"ok": true, "result": result}),
+        Err(e) => json!({"ok": false, "code": "error", "error": e.to_string()}),
+    }
+}
+
+fn serve(d: &Daemon, stream: UnixStream) {
+    let mut line = String::new();
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let mut reader = BufReader::new(&stream);
+    if reader.read_line(&mut line).is_err() {
+        return;
+    }
+    let reply = respond(d, line.trim());
+    let _ = writeln!(&stream, "{}", reply);
+    // The in-memory state was built from the old config; answering with it
+    // would be stale, so leave and let the next client start fresh.
+    if config_identity(d.cfg, d.cfg_path)["configHash"] != d.identity["configHash"] {
+        shut_down(&d.socket, "config changed", 0);
+    }
+}
+
+pub fn run(cfg: &Config, cfg_path: &Option<PathBuf>, debounce_ms: u64) -> Result<()> {
+    let socket = socket_path(cfg_path)
+        .ok_or_else(|| anyhow!("cli-rag daemon needs a config file (run `cli-rag init`)"))?;
+    if socket.exists() {
+        if UnixStream::connect(&socket).is_ok() {
+            return Err(anyhow!(
+                "a daemon is already listening on {}",
+                socket.display()
+            ));
+        }
+        // Left behind by a daemon that did not shut down cleanly.
+        std::fs::remove_file(&socket)
+            .with_context(|| format!("removing stale socket {}", socket.display()))?;
+    }
+    if let Some(dir) = socket.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let args = WatchArgs {
+        full_rescan: false,
+        debounce_ms,
+        dry_run: false,
+        json: false,
+    };
+    let daemon = Daemon {
+        cfg,
+        cfg_path,
+        state: Mutex::new(initial_state(cfg, cfg_path, &args)?),
+        identity: config_identity(cfg, cfg_path),
+        socket,
+    };
+    let listener = UnixListener::bind(&daemon.socket)
+        .with_context(|| format!("binding {}", daemon.socket.display()))?;
+    eprintln!("cli-rag daemon listening on {}", daemon.socket.display());
+    let daemon = &daemon;
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            // Without the watcher the state would silently go stale.
+            match watch_loop(cfg, cfg_path, &args, &daemon.state) {
+                Ok(()) => shut_down(&daemon.socket, "watch stopped", 1),
+                Err(e) => shut_down(&daemon.socket, &format!("watch failed: {}", e), 1),
+            }
+        });
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    s.spawn(move || serve(daemon, stream));
+                }
+                Err(e) => eprintln!("daemon connection failed: {}", e),
+            }
+        }
+    });
+    Ok(())
+}
@@ -99,6 +99,7 @@ pub fn load_docs_from_index(base: &Path, cfg: &Config) -> Result<Vec<AdrDoc>> {
                 fm: std::collections::BTreeMap::new(),
                 mtime,
                 size,
+                headings: None,
             });
         }
     }
@@ -75,27 +75,21 @@ pub fn load_docs_unified(
                         .get("status")
                         .and_then(|v| v.as_str())
                         .map(|s| s.to_string());
-                    // populate fm_map with simple JSON→YAML conversion for strings/arrays
+                    // the index holds the full front matter; keep numbers and booleans
                     for (k, v) in obj {
-                        let yv = match v {
-                            Value::String(s) => serde_yaml::Value::from(s.clone()),
-                            Value::Array(a) => {
-                                let mut seq = Vec::new();
-                                for it in a {
-                                    if let Some(ss) = it.as_str() {
-                                        seq.push(serde_yaml::Value::from(ss.to_string()));
-                                    }
-                                }
-                                serde_yaml::Value::Sequence(seq)
-                            }
-                            _ => serde_yaml::Value::Null,
-                        };
+                        let yv = serde_yaml::to_value(v).unwrap_or(serde_yaml::Value::Null);
                         fm_map.insert(k.clone(), yv);
                     }
                     (tags, status)
                 }
                 _ => (Vec::new(), None),
             };
+            let headings = n.get("headings").and_then(|v| v.as_array()).map(|a| {
+                a.iter()
+                    .filter_map(|h| h.get("text").and_then(|t| t.as_str()))
+                    .map(|t| t.to_string())
+                    .collect()
+            });
             let id_str = id.clone().unwrap_or_default();
             out_docs.push(AdrDoc {
                 file,
@@ -110,6 +104,7 @@ pub fn load_docs_unified(
                 fm: fm_map,
                 mtime: None,
                 size: None,
+                headings,
             });
         }
         return Ok(Some(out_docs));
@@ -195,6 +190,7 @@ pub fn load_docs_unified(
             fm: std::collections::BTreeMap::new(),
             mtime,
             size,
+            headings: None,
         });
     }
     Ok(Some(docs))
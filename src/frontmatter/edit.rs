@@ -0,0 +1,188 @@
+//! Line-level edits of a front matter block. A changed key replaces only the
+//! lines of its own top-level entry, so comments, quoting, list style and the
+//! exact text of every other entry survive byte for byte.
+
+use anyhow::{anyhow, Result};
+use serde_yaml::{Mapping, Value};
+
+use super::Flavor;
+
+/// A top-level entry: its key line and continuation lines, `start..end`.
+struct Entry {
+    key: String,
+    start: usize,
+    end: usize,
+}
+
+fn unquote(key: &str) -> String {
+    let k = key.trim();
+    for q in ['"', '\''] {
+        if let Some(inner) = k.strip_prefix(q).and_then(|s| s.strip_suffix(q)) {
+            return inner.to_string();
+        }
+    }
+    k.to_string()
+}
+
+/// The key a line starts, if it is a top-level `key: ...` / `key = ...` line.
+fn key_of(line: &str, flavor: Flavor) -> Option<String> {
+    if line.is_empty() || line.starts_with([' ', '\t', '#']) {
+        return None;
+    }
+    match flavor {
+        Flavor::Yaml => {
+            if line.starts_with('-') {
+                return None;
+            }
+            let colon = line
+                .find(": ")
+                .or_else(|| line.strip_suffix(':').map(str::len))?;
+            Some(unquote(&line[..colon]))
+        }
+        Flavor::Toml => {
+            if line.starts_with('[') {
+                return None;
+            }
+            line.find('=').map(|eq| unquote(&line[..eq]))
+        }
+    }
+}
+
+fn is_table_header(line: &str) -> bool {
+    line.starts_with('[')
+}
+
+/// Name of the TOML table a `[name]` header line opens.
+fn table_name(line: &str) -> Option<String> {
+    let inner = line.trim_end().strip_prefix('[')?.strip_suffix(']')?;
+    Some(unquote(inner))
+}
+
+/// Top-level entries and the line a new entry should be inserted at. TOML
+/// entries stop at the first `[table]` header; new keys go above it.
+fn entries(lines: &[String], flavor: Flavor) -> (Vec<Entry>, usize) {
+    let limit = match flavor {
+        Flavor::Toml => lines
+            .iter()
+            .position(|l| is_table_header(l))
+            .unwrap_or(lines.len()),
+        Flavor::Yaml => lines.len(),
+    };
+    let mut out: Vec<Entry> = Vec::new();
+    let mut last_content = 0;
+    for (i, line) in lines[..limit].iter().enumerate() {
+        if let Some(key) = key_of(line, flavor) {
+            out.push(Entry {
+                key,
+                start: i,
+                end: i + 1,
+            });
+            last_content = i + 1;
+            continue;
+        }
+        let blank_or_comment = line.trim().is_empty() || line.starts_with('#');
+        if !blank_or_comment {
+            if let Some(e) = out.last_mut() {
+                e.end = i + 1;
+            }
+            last_content = i + 1;
+        }
+    }
+    (out, last_content)
+}
+
+/// `key: [a, b]` for a YAML list of scalars, matching an original flow list.
+fn yaml_flow(key: &str, value: &Value) -> Option<String> {
+    let Value::Sequence(items) = value else {
+        return None;
+    };
+    let mut parts = Vec::new();
+    for item in items {
+        let text = match item {
+            Value::String(s) if s.contains([',', '[', ']', '{', '}']) => {
+                serde_json::to_string(s).ok()?
+            }
+            Value::String(_) | Value::Number(_) | Value::Bool(_) => {
+                serde_yaml::to_string(item).ok()?.trim_end().to_string()
+            }
+            _ => return None,
+        };
+        parts.push(text);
+    }
+    let key = serde_yaml::to_string(&Value::String(key.to_string())).ok()?;
+    Some(format!("{}: [{}]", key.trim_end(), parts.join(", ")))
+}
+
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        toml::Value::String(key.to_string()).to_string()
+    }
+}
+
+/// Lines for `key = value` in `flavor`, in flow style when `old` was.
+fn render_entry(
+    flavor: Flavor,
+    key: &str,
+    value: &Value,
+    old: Option<&str>,
+) -> Result<Vec<String>> {
+    let text = match flavor {
+        Flavor::Yaml => {
+            let was_flow = old
+                .and_then(|l| l.split_once(':'))
+                .is_some_and(|(_, v)| v.trim_start().starts_with('['));
+            match yaml_flow(key, value).filter(|_| was_flow) {
+                Some(line) => line,
+                None => {
+                    let mut one = Mapping::new();
+                    one.insert(Value::String(key.to_string()), value.clone());
+                    serde_yaml::to_string(&one)?
+                }
+            }
+        }
+        Flavor::Toml => {
+            let v = toml::Value::try_from(value)
+                .map_err(|e| anyhow!("front matter '{}' cannot be written as TOML: {}", key, e))?;
+            format!("{} = {}", toml_key(key), v)
+        }
+    };
+    Ok(text.lines().map(|l| l.to_string()).collect())
+}
+
+/// Rewrite the entries of `dirty` keys in `lines` to their value in `fields`:
+/// replaced in place, removed, or appended when new.
+pub fn apply(
+    lines: &mut Vec<String>,
+    flavor: Flavor,
+    fields: &Mapping,
+    dirty: &[String],
+) -> Result<()> {
+    for key in dirty {
+        let (found, insert_at) = entries(lines, flavor);
+        let entry = found.iter().find(|e| &e.key == key);
+        match (entry, fields.get(key.as_str())) {
+            (Some(e), Some(value)) => {
+                let new = render_entry(flavor, key, value, Some(&lines[e.start]))?;
+                lines.splice(e.start..e.end, new);
+            }
+            (Some(e), None) => {
+                lines.drain(e.start..e.end);
+            }
+            (None, Some(value)) => {
+                if lines.iter().any(|l| table_name(l).as_ref() == Some(key)) {
+                    return Err(anyhow!("front matter table [{}] cannot be edited", key));
+                }
+                let new = render_entry(flavor, key, value, None)?;
+                lines.splice(insert_at..insert_at, new);
+            }
+            (None, None) => {}
+        }
+    }
+    Ok(())
+}
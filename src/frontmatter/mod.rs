@@ -0,0 +1,243 @@
+//! Read/modify/write helpers for note front matter that keep the file's flavor
+//! (`---` YAML or `+++` TOML) intact. Only the entries of keys that change are
+//! rewritten; every other line, the body and the line endings stay as they were.
+
+use anyhow::Result;
+use serde_yaml::{Mapping, Value};
+
+mod edit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flavor {
+    Yaml,
+    Toml,
+}
+
+impl Flavor {
+    fn delimiter(self) -> &'static str {
+        match self {
+            Flavor::Yaml => "---",
+            Flavor::Toml => "+++",
+        }
+    }
+}
+
+/// A note split into its front matter mapping and the untouched body.
+#[derive(Debug, Clone)]
+pub struct FrontMatterDoc {
+    pub flavor: Flavor,
+    pub body: String,
+    fields: Mapping,
+    /// Front matter lines between the delimiters, without line endings.
+    lines: Vec<String>,
+    newline: &'static str,
+    /// Whether a line break follows the closing delimiter.
+    closed_with_newline: bool,
+    original: String,
+    /// Keys changed through `insert`/`remove`, in order of first change.
+    dirty: Vec<String>,
+}
+
+impl FrontMatterDoc {
+    /// Split `content` into front matter and body. Returns `None` when the note
+    /// has no front matter block or the block is not a mapping.
+    pub fn parse(content: &str) -> Option<FrontMatterDoc> {
+        let first_end = content.find('\n')?;
+        let first = &content[..first_end];
+        let newline = if first.ends_with('\r') { "\r\n" } else { "\n" };
+        let flavor = match first.trim_end_matches('\r') {
+            "---" => Flavor::Yaml,
+            "+++" => Flavor::Toml,
+            _ => return None,
+        };
+        let delim = flavor.delimiter();
+        let mut lines = Vec::new();
+        let mut pos = first_end + 1;
+        let (body, closed_with_newline) = loop {
+            let end = content[pos..].find('\n').map(|i| pos + i);
+            let line = content[pos..end.unwrap_or(content.len())].trim_end_matches('\r');
+            if line == delim {
+                break match end {
+                    Some(e) => (&content[e + 1..], true),
+                    None => ("", false),
+                };
+            }
+            lines.push(line.to_string());
+            pos = end? + 1;
+        };
+        let fm_text = lines.join("\n");
+        let fields = match flavor {
+            Flavor::Yaml if fm_text.trim().is_empty() => Mapping::new(),
+            Flavor::Yaml => serde_yaml::from_str::<Mapping>(&fm_text).ok()?,
+            Flavor::Toml => toml::from_str::<toml::Table>(&fm_text)
+                .ok()?
+                .iter()
+                .map(|(k, v)| (Value::String(k.clone()), toml_to_yaml(v)))
+                .collect(),
+        };
+        Some(FrontMatterDoc {
+            flavor,
+            body: body.to_string(),
+            fields,
+            lines,
+            newline,
+            closed_with_newline,
+            original: content.to_string(),
+            dirty: Vec::new(),
+        })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.fields.get(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.fields.keys().filter_map(|k| k.as_str())
+    }
+
+    fn touch(&mut self, key: &str) {
+        if !self.dirty.iter().any(|k| k == key) {
+            self.dirty.push(key.to_string());
+        }
+    }
+
+    pub fn insert(&mut self, key: &str, value: Value) {
+        if self.fields.get(key) == Some(&value) {
+            return;
+        }
+        self.touch(key);
+        self.fields.insert(Value::String(key.to_string()), value);
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        let removed = self.fields.remove(key);
+        if removed.is_some() {
+            self.touch(key);
+        }
+        removed
+    }
+
+    /// Render the note back to text using the original delimiter and syntax.
+    /// Entries of changed keys are rewritten (new keys are appended); all
+    /// other text is returned exactly as parsed.
+    pub fn render(&self) -> Result<String> {
+        if self.dirty.is_empty() {
+            return Ok(self.original.clone());
+        }
+        let mut lines = self.lines.clone();
+        edit::apply(&mut lines, self.flavor, &self.fields, &self.dirty)?;
+        let delim = self.flavor.delimiter();
+        let mut out = format!("{}{}", delim, self.newline);
+        for line in &lines {
+            out.push_str(line);
+            out.push_str(self.newline);
+        }
+        out.push_str(delim);
+        if self.closed_with_newline {
+            out.push_str(self.newline);
+        }
+        out.push_str(&self.body);
+        Ok(out)
+    }
+}
+
+/// Read a front matter value as a list of strings (a lone string counts as one item).
+pub fn string_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(s) => vec![s.clone()],
+        Value::Sequence(seq) => seq
+            .iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Convert a TOML value into the YAML value model used for front matter.
+/// Datetimes become strings, the way unquoted YAML dates are read.
+pub fn toml_to_yaml(value: &toml::Value) -> Value {
+    toml_to_yaml_with(value, &|s| s.to_string())
+}
+
+/// [`toml_to_yaml`] with every string value (at any depth) passed through `text`.
+pub fn toml_to_yaml_with(value: &toml::Value, text: &dyn Fn(&str) -> String) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(text(s)),
+        toml::Value::Integer(i) => Value::Number((*i).into()),
+        toml::Value::Float(f) => Value::Number((*f).into()),
+        toml::Value::Boolean(b) => Value::Bool(*b),
+        toml::Value::Datetime(dt) => Value::String(dt.to_string()),
+        toml::Value::Array(items) => {
+            Value::Sequence(items.iter().map(|v| toml_to_yaml_with(v, text)).collect())
+        }
+        toml::Value::Table(table) => Value::Mapping(
+            table
+                .iter()
+                .map(|(k, v)| (Value::String(k.clone()), toml_to_yaml_with(v, text)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrips_yaml_and_toml_flavors() {
+        let yaml = "---\nid: ADR-001\ntags: [a]\n---\n\n# Title\n";
+        let mut doc = FrontMatterDoc::parse(yaml).unwrap();
+        assert_eq!(doc.flavor, Flavor::Yaml);
+        doc.insert("status", Value::String("draft".into()));
+        let out = doc.render().unwrap();
+        assert!(out.starts_with("---\nid: ADR-001\n"));
+        assert!(out.contains("status: draft\n---\n\n# Title\n"));
+
+        let toml_src = "+++\nid = \"ADR-002\"\ntags = [\"a\"]\n+++\nBody\n";
+        let mut doc = FrontMatterDoc::parse(toml_src).unwrap();
+        assert_eq!(doc.flavor, Flavor::Toml);
+        doc.remove("tags");
+        assert_eq!(doc.render().unwrap(), "+++\nid = \"ADR-002\"\n+++\nBody\n");
+    }
+
+    #[test]
+    fn untouched_entries_keep_their_text() {
+        let yaml = "---\r\n# owner: platform\r\nid: ADR-001 # stable\r\ntags: [a, b]\r\nstatus: Draft\r\n---\r\nBody\r\n";
+        let mut doc = FrontMatterDoc::parse(yaml).unwrap();
+        assert_eq!(doc.render().unwrap(), yaml);
+        doc.insert("status", Value::String("draft".into()));
+        doc.insert(
+            "tags",
+            Value::Sequence(vec![
+                Value::String("a".into()),
+                Value::String("c, d".into()),
+            ]),
+        );
+        assert_eq!(
+            doc.render().unwrap(),
+            "---\r\n# owner: platform\r\nid: ADR-001 # stable\r\ntags: [a, \"c, d\"]\r\nstatus: draft\r\n---\r\nBody\r\n"
+        );
+    }
+
+    #[test]
+    fn toml_datetimes_round_trip() {
+        let src = "+++\nid = \"ADR-003\"\ncreated = 2025-01-01\nkanban_status = \"todo\"\n\n[extra]\nowner = \"x\"\n+++\nBody\n";
+        let mut doc = FrontMatterDoc::parse(src).unwrap();
+        assert_eq!(
+            doc.get("created"),
+            Some(&Value::String("2025-01-01".into()))
+        );
+        doc.insert("kanban_status", Value::String("doing".into()));
+        doc.insert("tags", Value::Sequence(vec![Value::String("t".into())]));
+        assert_eq!(
+            doc.render().unwrap(),
+            "+++\nid = \"ADR-003\"\ncreated = 2025-01-01\nkanban_status = \"doing\"\ntags = [\"t\"]\n\n[extra]\nowner = \"x\"\n+++\nBody\n"
+        );
+    }
+
+    #[test]
+    fn missing_front_matter_is_none() {
+        assert!(FrontMatterDoc::parse("# Just a note\n").is_none());
+        assert!(FrontMatterDoc::parse("---\nid: x\nno closing").is_none());
+    }
+}
@@ -1,7 +1,7 @@
-use regex::Regex;
 use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 
 use crate::model::AdrDoc;
+use crate::wikilink::{read_wikilinks, LinkResolver};
 
 // Compute a dependency path between two ADR ids using BFS over
 // a bidirectional graph (depends_on edges + reverse dependents).
@@ -11,8 +11,7 @@ pub fn bfs_path(
     max_depth: usize,
     by_id: &HashMap<String, AdrDoc>,
 ) -> Option<Vec<String>> {
-    // Compile mentions regex once
-    let mention_re: Regex = Regex::new(r"\[\[([A-Za-z]+-[0-9A-Za-z_-]+)\]\]").unwrap();
+    let links = LinkResolver::new(by_id.values());
     if from == to {
         return Some(vec![from.into()]);
     }
@@ -34,14 +33,11 @@ pub fn bfs_path(
                     neighbors.insert(oid.clone());
                 }
             }
-            // mentions-based neighbors: scan for wikilinks [[ID]]
-            if let Ok(content) = std::fs::read_to_string(&doc.file) {
-                for cap in mention_re.captures_iter(&content) {
-                    if let Some(m) = cap.get(1) {
-                        let t = m.as_str().to_string();
-                        if by_id.contains_key(&t) {
-                            neighbors.insert(t);
-                        }
+            // mentions-based neighbors: wikilinks resolving to known ids
+            for link in read_wikilinks(&doc.file) {
+                if let Some(t) = links.resolve(&link.target) {
+                    if by_id.contains_key(t) {
+                        neighbors.insert(t.to_string());
                     }
                 }
             }
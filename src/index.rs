@@ -1,17 +1,201 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use regex::Regex;
 use serde_json::json;
 use std::collections::HashMap;
 use std::fs;
 //
 
 use crate::config::{build_schema_sets, Config};
+use crate::mdlink::{normalize_path, parse_markdown_links, LinkTarget};
 use crate::model::AdrDoc;
+use crate::validate::collect_note_headings;
+use crate::wikilink::{parse_wikilinks, LinkResolver};
+
+/// One note's unified index node (before `computed.degree` is known) and
+/// the edges it contributes. Watch mode caches these per file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexEntry {
+    pub id: String,
+    pub node: serde_json::Value,
+    pub last_modified: Option<String>,
+    pub edges: Vec<serde_json::Value>,
+}
+
+/// Index node and edges for `d`; `None` for notes without an id.
+pub fn index_entry(
+    d: &AdrDoc,
+    schema: String,
+    path_str: String,
+    links: &LinkResolver,
+) -> Option<IndexEntry> {
+    let content = fs::read_to_string(&d.file).unwrap_or_default();
+    index_entry_for(d, schema, path_str, &content, links)
+}
+
+/// [`index_entry`] over `content` already in hand (the note's full text).
+pub fn index_entry_for(
+    d: &AdrDoc,
+    schema: String,
+    path_str: String,
+    content: &str,
+    links: &LinkResolver,
+) -> Option<IndexEntry> {
+    let id = d.id.clone()?;
+    // frontmatter map (keys only or values if present)
+    let mut fm_obj = serde_json::Map::new();
+    for (k, v) in &d.fm {
+        // Try to round-trip serde_yaml::Value → serde_json::Value; fallback to null
+        let jv = match serde_json::to_value(v) {
+            Ok(val) => val,
+            Err(_) => serde_json::Value::Null,
+        };
+        fm_obj.insert(k.clone(), jv);
+    }
+    // lastModified from file metadata if available
+    let last_modified = fs::metadata(&d.file)
+        .and_then(|md| md.modified())
+        .ok()
+        .map(|st| {
+            let dt: DateTime<Utc> = st.into();
+            dt.to_rfc3339()
+        });
+    let headings: Vec<serde_json::Value> = collect_note_headings(content)
+        .into_iter()
+        .map(|h| json!({"level": h.level, "text": h.text, "anchor": h.anchor, "line": h.line}))
+        .collect();
+    let mut node = json!({
+        "id": id,
+        "schema": schema,
+        "title": d.title,
+        "path": path_str,
+        "frontmatter": serde_json::Value::Object(fm_obj),
+        "headings": headings,
+    });
+
+    let mut edges: Vec<serde_json::Value> = Vec::new();
+    for dep in &d.depends_on {
+        edges.push(json!({"from": id, "to": dep, "kind": "depends_on"}));
+    }
+    for s in &d.supersedes {
+        edges.push(json!({"from": id, "to": s, "kind": "supersedes"}));
+    }
+    for sb in &d.superseded_by {
+        edges.push(json!({"from": id, "to": sb, "kind": "superseded_by"}));
+    }
+
+    // Wikilink mentions that resolve to a note id, with their locations
+    let mut seen_on_line: std::collections::HashSet<(String, usize)> =
+        std::collections::HashSet::new();
+    for link in parse_wikilinks(content) {
+        let Some(target) = links.resolve(&link.target) else {
+            continue;
+        };
+        // dedupe same target on same line
+        if link.is_self() || !seen_on_line.insert((target.to_string(), link.line)) {
+            continue;
+        }
+        let mut edge = json!({
+            "from": &id,
+            "to": target,
+            "kind": "mentions",
+            "locations": [{"path": path_str, "line": link.line}]
+        });
+        if let Some(anchor) = link.anchor {
+            edge["anchor"] = json!(anchor);
+        }
+        if let Some(alias) = link.alias {
+            edge["alias"] = json!(alias);
+        }
+        if link.embed {
+            edge["embed"] = json!(true);
+        }
+        edges.push(edge);
+    }
+    let (link_edges, references) = markdown_links(d, &id, &path_str, content, links);
+    edges.extend(link_edges);
+    if !references.is_empty() {
+        node["references"] = json!(references);
+    }
+    Some(IndexEntry {
+        id,
+        node,
+        last_modified,
+        edges,
+    })
+}
+
+/// `links` edges for Markdown links to note files, and external URLs as
+/// `references` entries.
+fn markdown_links(
+    d: &AdrDoc,
+    id: &str,
+    path_str: &str,
+    content: &str,
+    links: &LinkResolver,
+) -> (Vec<serde_json::Value>, Vec<serde_json::Value>) {
+    let (mut edges, mut references) = (Vec::new(), Vec::new());
+    let mut seen_on_line: std::collections::HashSet<(String, usize)> =
+        std::collections::HashSet::new();
+    for link in parse_markdown_links(content) {
+        match link.target(&normalize_path(&d.file)) {
+            LinkTarget::Url(url) if url.starts_with("http://") || url.starts_with("https://") => {
+                references.push(json!({"url": url, "line": link.line}));
+            }
+            LinkTarget::File { path, anchor } => {
+                let Some(target) = links.id_at(&path) else {
+                    continue;
+                };
+                if target == id || !seen_on_line.insert((target.to_string(), link.line)) {
+                    continue;
+                }
+                let mut edge = json!({
+                    "from": id,
+                    "to": target,
+                    "kind": "links",
+                    "locations": [{"path": path_str, "line": link.line}]
+                });
+                if let Some(anchor) = anchor {
+                    edge["anchor"] = json!(anchor);
+                }
+                edges.push(edge);
+            }
+            _ => {}
+        }
+    }
+    (edges, references)
+}
+
+/// Index entries for `docs`, with paths relative to the config directory.
+/// Mentions resolve through `links`, which may cover more notes than `docs`.
+pub fn index_entries(
+    cfg: &Config,
+    docs: &[AdrDoc],
+    dir: &std::path::Path,
+    links: &LinkResolver,
+) -> Vec<IndexEntry> {
+    let schema_sets = build_schema_sets(cfg);
+    docs.iter()
+        .filter_map(|d| {
+            let fname = d.file.file_name().and_then(|s| s.to_str()).unwrap_or("");
+            let schema = schema_sets
+                .iter()
+                .find(|(_, set)| set.is_match(fname))
+                .map(|(sc, _)| sc.name.clone())
+                .unwrap_or_else(|| "UNKNOWN".into());
+            let path_str = d
+                .file
+                .strip_prefix(dir)
+                .unwrap_or(&d.file)
+                .to_string_lossy()
+                .to_string();
+            index_entry(d, schema, path_str, links)
+        })
+        .collect()
+}
 
 pub fn write_indexes(
     cfg: &Config,
-    docs: &Vec<AdrDoc>,
+    docs: &[AdrDoc],
     _force: bool,
     _auto_write: bool,
     config_dir: Option<&std::path::Path>,
@@ -24,143 +208,42 @@ pub fn write_indexes(
             return Ok(());
         }
     };
+    let entries = index_entries(cfg, docs, dir, &LinkResolver::new(docs));
+    write_unified_index(cfg, dir, &entries)
+}
 
-    // Precompute schema by filename matcher
-    let schema_sets = build_schema_sets(cfg);
-    let fname_schema = |path: &std::path::Path| -> String {
-        let fname = path.file_name().and_then(|s| s.to_str()).unwrap_or("");
-        for (sc, set) in &schema_sets {
-            if set.is_match(fname) {
-                return sc.name.clone();
-            }
-        }
-        "UNKNOWN".into()
-    };
-
-    // Helper for relative path string under project root
-    let rel_to_root = |path: &std::path::Path| -> String {
-        path.strip_prefix(dir)
-            .unwrap_or(path)
-            .to_string_lossy()
-            .to_string()
-    };
-
-    // Collect node info, edges, and degree
-    struct NodeInfo {
-        id: String,
-        schema: String,
-        title: String,
-        path: String,
-        frontmatter: serde_json::Value,
-        last_modified: Option<String>,
-    }
-    let mut nodes_info: Vec<NodeInfo> = Vec::new();
-    // Edges (FM-derived and mentions)
-    let mut edges: Vec<serde_json::Value> = Vec::new();
+/// Serialize `entries` as the unified index under `dir`, computing node degrees.
+pub fn write_unified_index(
+    cfg: &Config,
+    dir: &std::path::Path,
+    entries: &[IndexEntry],
+) -> Result<()> {
     let mut degree: HashMap<String, usize> = HashMap::new();
-
-    // Mentions regex: [[ID]] where ID has at least one dash
-    let mention_re: Regex = Regex::new(r"\[\[([A-Za-z]+-[0-9A-Za-z_-]+)\]\]").unwrap();
-
-    for d in docs {
-        let id = match &d.id {
-            Some(s) => s.clone(),
-            None => continue, // skip unnamed docs for contract nodes
-        };
-        let schema = fname_schema(&d.file);
-        let path_str = rel_to_root(&d.file);
-        // frontmatter map (keys only or values if present)
-        let mut fm_obj = serde_json::Map::new();
-        for (k, v) in &d.fm {
-            // Try to round-trip serde_yaml::Value → serde_json::Value; fallback to null
-            let jv = match serde_json::to_value(v) {
-                Ok(val) => val,
-                Err(_) => serde_json::Value::Null,
-            };
-            fm_obj.insert(k.clone(), jv);
-        }
-        let frontmatter = serde_json::Value::Object(fm_obj);
-        // lastModified from file metadata if available
-        let last_modified = fs::metadata(&d.file)
-            .and_then(|md| md.modified())
-            .ok()
-            .map(|st| {
-                let dt: DateTime<Utc> = st.into();
-                dt.to_rfc3339()
-            });
-        nodes_info.push(NodeInfo {
-            id: id.clone(),
-            schema,
-            title: d.title.clone(),
-            path: path_str.clone(),
-            frontmatter,
-            last_modified,
-        });
-
-        // depends_on edges
-        if let Some(from) = &d.id {
-            for dep in &d.depends_on {
-                edges.push(json!({"from": from, "to": dep, "kind": "depends_on"}));
-                *degree.entry(from.clone()).or_default() += 1;
-                *degree.entry(dep.clone()).or_default() += 1;
-            }
-            for s in &d.supersedes {
-                edges.push(json!({"from": from, "to": s, "kind": "supersedes"}));
-                *degree.entry(from.clone()).or_default() += 1;
-                *degree.entry(s.clone()).or_default() += 1;
-            }
-            for sb in &d.superseded_by {
-                edges.push(json!({"from": from, "to": sb, "kind": "superseded_by"}));
-                *degree.entry(from.clone()).or_default() += 1;
-                *degree.entry(sb.clone()).or_default() += 1;
-            }
-        }
-
-        // Scan file content for wikilink mentions and record locations
-        if let Ok(content) = fs::read_to_string(&d.file) {
-            let mut seen_on_line: std::collections::HashSet<(String, u32)> =
-                std::collections::HashSet::new();
-            for (i, line) in content.lines().enumerate() {
-                for cap in mention_re.captures_iter(line) {
-                    if let Some(m) = cap.get(1) {
-                        let target = m.as_str().to_string();
-                        let line_no = (i + 1) as u32;
-                        // dedupe same target on same line
-                        if seen_on_line.insert((target.clone(), line_no)) {
-                            edges.push(json!({
-                                "from": &id,
-                                "to": target,
-                                "kind": "mentions",
-                                "locations": [{"path": path_str, "line": line_no}]
-                            }));
-                            *degree.entry(id.clone()).or_default() += 1;
-                            *degree.entry(target).or_default() += 1;
-                        }
-                    }
+    let mut edges: Vec<serde_json::Value> = Vec::new();
+    for e in entries {
+        for edge in &e.edges {
+            for end in ["from", "to"] {
+                if let Some(id) = edge[end].as_str() {
+                    *degree.entry(id.to_string()).or_default() += 1;
                 }
             }
+            edges.push(edge.clone());
         }
     }
 
-    // Root object
     // Build nodes JSON with computed fields (degree, lastModified)
-    let nodes: Vec<serde_json::Value> = nodes_info
-        .into_iter()
-        .map(|n| {
+    let nodes: Vec<serde_json::Value> = entries
+        .iter()
+        .map(|e| {
             let mut computed = serde_json::Map::new();
-            let deg = *degree.get(&n.id).unwrap_or(&0);
+            let deg = *degree.get(&e.id).unwrap_or(&0);
             computed.insert("degree".into(), json!(deg));
-            if let Some(ts) = n.last_modified.clone() {
+            if let Some(ts) = e.last_modified.clone() {
                 computed.insert("lastModified".into(), json!(ts));
             }
-            json!({
-                "id": n.id,
-                "schema": n.schema,
-                "title": n.title,
-                "path": n.path,
-                "frontmatter": n.frontmatter,
-                "computed": computed,
-            })
+            let mut node = e.node.clone();
+            node["computed"] = serde_json::Value::Object(computed);
+            node
         })
         .collect();
 
@@ -1,11 +1,16 @@
+pub mod backlinks;
 pub mod cli;
 pub mod commands;
 pub mod config;
+pub mod daemon;
 pub mod discovery;
+pub mod frontmatter;
 pub mod graph;
 pub mod index;
+pub mod mdlink;
 pub mod model;
 pub mod protocol;
 pub mod util;
 pub mod validate;
 pub mod watch;
+pub mod wikilink;
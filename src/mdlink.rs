@@ -0,0 +1,254 @@
+//! Markdown links for the graph: inline `[text](dest)` and reference-style
+//! `[text][label]`, `[label][]` and `[label]` links outside code. Images and
+//! wikilinks are not Markdown links here.
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use crate::wikilink::prose_lines;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MdLink {
+    /// Destination as written, percent-decoded.
+    pub dest: String,
+    /// 1-based line number of the link (not of a reference definition).
+    pub line: usize,
+}
+
+/// Where a Markdown link points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// `scheme:...` destinations such as `https://` or `mailto:`.
+    Url(String),
+    /// `#anchor` within the same note.
+    Anchor(String),
+    /// A file relative to the linking note, normalized, with its `#anchor`.
+    File {
+        path: PathBuf,
+        anchor: Option<String>,
+    },
+}
+
+impl MdLink {
+    /// Classify the destination; relative paths resolve against `from`'s directory.
+    pub fn target(&self, from: &Path) -> LinkTarget {
+        let dest = self.dest.as_str();
+        if is_url(dest) {
+            return LinkTarget::Url(dest.to_string());
+        }
+        if let Some(anchor) = dest.strip_prefix('#') {
+            return LinkTarget::Anchor(anchor.to_string());
+        }
+        let (file, anchor) = match dest.split_once('#') {
+            Some((f, a)) => (f, Some(a.to_string()).filter(|a| !a.is_empty())),
+            None => (dest, None),
+        };
+        let file = file.split('?').next().unwrap_or(file);
+        let dir = from.parent().unwrap_or(Path::new(""));
+        LinkTarget::File {
+            path: normalize_path(&dir.join(file)),
+            anchor,
+        }
+    }
+}
+
+fn is_url(dest: &str) -> bool {
+    match dest.split_once(':') {
+        // A single letter is a Windows drive, not a scheme.
+        Some((scheme, _)) => {
+            scheme.len() > 1
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+.-".contains(c))
+        }
+        None => false,
+    }
+}
+
+/// Absolute, lexically normalized form of `path` (`.` and `..` folded,
+/// symlinks untouched), so links and note files compare without touching disk.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let abs = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    };
+    let mut out = PathBuf::new();
+    for c in abs.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                out.push(b);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// `[label]: dest "title"` with at most three spaces of indent.
+fn reference_definition(line: &str) -> Option<(String, String)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    let rest = line[indent..].strip_prefix('[').filter(|_| indent <= 3)?;
+    let (label, rest) = rest.split_once("]:")?;
+    let dest = rest.split_whitespace().next()?;
+    let dest = dest.trim_start_matches('<').trim_end_matches('>');
+    (!label.trim().is_empty()).then(|| (label.trim().to_lowercase(), dest.to_string()))
+}
+
+/// Index of the `close` matching the `open` at `start`, counting nesting.
+fn matching(bytes: &[u8], start: usize, open: u8, close: u8) -> Option<usize> {
+    let mut depth = 0;
+    for (j, b) in bytes.iter().enumerate().skip(start) {
+        if *b == open && (j == 0 || bytes[j - 1] != b'\\') {
+            depth += 1;
+        } else if *b == close && (j == 0 || bytes[j - 1] != b'\\') {
+            depth -= 1;
+            if depth == 0 {
+                return Some(j);
+            }
+        }
+    }
+    None
+}
+
+/// Every Markdown link in `content`, in document order.
+pub fn parse_markdown_links(content: &str) -> Vec<MdLink> {
+    let lines = prose_lines(content);
+    let defs: HashMap<String, String> = lines
+        .iter()
+        .filter_map(|(_, l)| reference_definition(l))
+        .collect();
+    let mut out = Vec::new();
+    for (line_no, line) in &lines {
+        if reference_definition(line).is_some() {
+            continue;
+        }
+        let bytes = line.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i..].starts_with(b"[[") {
+                i = line[i..].find("]]").map(|n| i + n + 2).unwrap_or(i + 2);
+                continue;
+            }
+            if bytes[i] != b'[' || (i > 0 && bytes[i - 1] == b'\\') {
+                i += 1;
+                continue;
+            }
+            let Some(close) = matching(bytes, i, b'[', b']') else {
+                i += 1;
+                continue;
+            };
+            let image = i > 0 && bytes[i - 1] == b'!';
+            let text = &line[i + 1..close];
+            let mut next = close + 1;
+            let dest = match bytes.get(close + 1) {
+                Some(b'(') => matching(bytes, close + 1, b'(', b')').map(|end| {
+                    next = end + 1;
+                    let inner = line[close + 2..end].trim();
+                    match inner.strip_prefix('<') {
+                        Some(rest) => rest.split('>').next().unwrap_or("").to_string(),
+                        None => inner.split_whitespace().next().unwrap_or("").to_string(),
+                    }
+                }),
+                Some(b'[') => line[close + 2..].find(']').and_then(|n| {
+                    next = close + 3 + n;
+                    let label = &line[close + 2..close + 2 + n];
+                    let label = if label.trim().is_empty() { text } else { label };
+                    defs.get(&label.trim().to_lowercase()).cloned()
+                }),
+                _ => defs.get(&text.trim().to_lowercase()).cloned(),
+            };
+            match dest.filter(|d| !d.is_empty()) {
+                Some(dest) if !image => out.push(MdLink {
+                    dest: percent_decode(&dest),
+                    line: *line_no,
+                }),
+                _ => {}
+            }
+            i = next.max(i + 1);
+        }
+    }
+    out
+}
+
+/// Markdown links of the file at `path`; unreadable files have none.
+pub fn read_markdown_links(path: &Path) -> Vec<MdLink> {
+    std::fs::read_to_string(path)
+        .map(|c| parse_markdown_links(&c))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dests(content: &str) -> Vec<(String, usize)> {
+        parse_markdown_links(content)
+            .into_iter()
+            .map(|l| (l.dest, l.line))
+            .collect()
+    }
+
+    #[test]
+    fn inline_and_reference_links() {
+        let content = "See [four](../ADR/ADR-004.md#decision \"t\") and [five][5].\n\
+                       Also [six] and [5][] but not ![img](a.png) or [[ADR-007]].\n\
+                       \n[5]: <ADR-005%20draft.md>\n[six]: https://example.com/x\n";
+        assert_eq!(
+            dests(content),
+            vec![
+                ("../ADR/ADR-004.md#decision".into(), 1),
+                ("ADR-005 draft.md".into(), 1),
+                ("https://example.com/x".into(), 2),
+                ("ADR-005 draft.md".into(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_code_and_classifies_targets() {
+        assert!(dests("`[a](b.md)`\n```\n[c](d.md)\n```\n").is_empty());
+        let link = |dest: &str| MdLink {
+            dest: dest.into(),
+            line: 1,
+        };
+        let from = Path::new("/notes/adr/ADR-001.md");
+        assert_eq!(
+            link("../x/./y.md#h").target(from),
+            LinkTarget::File {
+                path: PathBuf::from("/notes/x/y.md"),
+                anchor: Some("h".into())
+            }
+        );
+        assert_eq!(
+            link("mailto:a@b.c").target(from),
+            LinkTarget::Url("mailto:a@b.c".into())
+        );
+        assert_eq!(link("#top").target(from), LinkTarget::Anchor("top".into()));
+    }
+}
@@ -38,6 +38,10 @@ pub struct AdrDoc {
     pub fm: BTreeMap<String, serde_yaml::Value>,
     pub mtime: Option<u64>,
     pub size: Option<u64>,
+    /// Heading texts of the body, when known from parsing or the unified
+    /// index; `None` means they have to be read from the file.
+    #[serde(skip)]
+    pub headings: Option<Vec<String>>,
 }
 
 impl AdrDoc {
@@ -89,8 +93,8 @@ pub fn parse_front_matter_and_title(content: &str, path: &Path) -> AdrDoc {
                 }
                 if let Ok(tval) = toml::from_str::<toml::Value>(fm_text) {
                     if let Some(table) = tval.as_table() {
-                        for (k, _v) in table.iter() {
-                            fm_map.insert(k.clone(), serde_yaml::Value::Null);
+                        for (k, v) in table.iter() {
+                            fm_map.insert(k.clone(), crate::frontmatter::toml_to_yaml(v));
                         }
                     }
                 }
@@ -133,9 +137,18 @@ pub fn parse_front_matter_and_title(content: &str, path: &Path) -> AdrDoc {
         fm: fm_map,
         mtime: file_mtime(path).ok(),
         size: file_size(path).ok(),
+        headings: Some(heading_texts(content)),
     }
 }
 
+/// Heading texts outside front matter and fenced code, in document order.
+pub fn heading_texts(content: &str) -> Vec<String> {
+    crate::validate::collect_note_headings(content)
+        .into_iter()
+        .map(|h| h.text)
+        .collect()
+}
+
 pub fn file_mtime(p: &Path) -> Result<u64> {
     let md = fs::metadata(p)?;
     let m = md.modified()?;
@@ -189,6 +202,9 @@ tags = ["x"]
 status = "accepted"
 groups = ["G1"]
 depends_on = ["ADR-100"]
+priority = 3
+reviewed = 2024-05-01
+owner = { team = "core", oncall = true }
 +++
 
 # ADR-200: TOML Sample
@@ -203,6 +219,10 @@ Body here.
         assert_eq!(doc.tags, vec!["x"]);
         assert_eq!(doc.groups, vec!["G1".to_string()]);
         assert_eq!(doc.depends_on, vec!["ADR-100".to_string()]);
+        assert_eq!(doc.fm["priority"].as_i64(), Some(3));
+        assert_eq!(doc.fm["reviewed"].as_str(), Some("2024-05-01"));
+        assert_eq!(doc.fm["owner"]["team"].as_str(), Some("core"));
+        assert_eq!(doc.fm["owner"]["oncall"].as_bool(), Some(true));
     }
 
     #[test]
@@ -0,0 +1,90 @@
+/// Minimal unified diff (3 lines of context) between two texts, line based.
+///
+/// Returns an empty string when the texts are identical.
+pub fn unified_diff(label: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // LCS table over lines; notes are small enough for O(n*m).
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    // Edit script: (tag, old_idx, new_idx, line)
+    let mut ops: Vec<(char, usize, usize, &str)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((' ', i, j, a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', i, j, a[i]));
+            i += 1;
+        } else {
+            ops.push(('+', i, j, b[j]));
+            j += 1;
+        }
+    }
+
+    const CONTEXT: usize = 3;
+    let mut out = format!("--- a/{}\n+++ b/{}\n", label, label);
+    let changed: Vec<usize> = (0..ops.len()).filter(|&k| ops[k].0 != ' ').collect();
+    let mut k = 0;
+    while k < changed.len() {
+        let start = changed[k].saturating_sub(CONTEXT);
+        let mut end = changed[k];
+        while k < changed.len() && changed[k] <= end + 2 * CONTEXT {
+            end = changed[k];
+            k += 1;
+        }
+        let end = (end + CONTEXT + 1).min(ops.len());
+        let hunk = &ops[start..end];
+        let old_len = hunk.iter().filter(|o| o.0 != '+').count();
+        let new_len = hunk.iter().filter(|o| o.0 != '-').count();
+        let old_start = hunk[0].1 + usize::from(old_len > 0);
+        let new_start = hunk[0].2 + usize::from(new_len > 0);
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            old_start, old_len, new_start, new_len
+        ));
+        for (tag, _, _, line) in hunk {
+            out.push(*tag);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::unified_diff;
+
+    #[test]
+    fn reports_changed_lines_with_context() {
+        let old = "---\nid: A\ntags:\n- x\n- x\n---\nbody\n";
+        let new = "---\nid: A\ntags:\n- x\n---\nbody\n";
+        let d = unified_diff("A.md", old, new);
+        assert!(d.starts_with("--- a/A.md\n+++ b/A.md\n@@ -"));
+        assert_eq!(
+            d.lines()
+                .filter(|l| l.starts_with('-') && !l.starts_with("---"))
+                .count(),
+            1
+        );
+        assert!(!d
+            .lines()
+            .any(|l| l.starts_with('+') && !l.starts_with("+++")));
+        assert!(unified_diff("A.md", old, old).is_empty());
+    }
+}
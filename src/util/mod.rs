@@ -1,3 +1,7 @@
+mod diff;
+
+pub use diff::unified_diff;
+
 use anyhow::{anyhow, Result};
 use std::borrow::Cow;
 use std::path::Path;
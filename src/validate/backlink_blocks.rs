@@ -0,0 +1,38 @@
+use crate::backlinks::plan_blocks_with;
+use crate::config::Config;
+use crate::index::IndexEntry;
+use crate::model::AdrDoc;
+
+/// Flag notes in `scope` whose generated backlinks block is missing, outdated
+/// or left over; incoming edges are taken from the `entries` of all `docs`.
+/// Only the notes in scope are read.
+pub fn check_stale_blocks<'a>(
+    cfg: &Config,
+    docs: &[AdrDoc],
+    entries: impl FnOnce() -> &'a [IndexEntry],
+    scope: &[AdrDoc],
+    errors: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) {
+    for planned in plan_blocks_with(cfg, docs, entries) {
+        if !scope.iter().any(|d| d.file == planned.doc.file) {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(&planned.doc.file) else {
+            continue;
+        };
+        let msg = match planned.apply(&content) {
+            Ok(after) if after == content => continue,
+            Ok(_) => format!(
+                "{}: backlinks block is out of date (run `cli-rag backlinks sync`)",
+                planned.doc.display_path()
+            ),
+            Err(e) => format!("{}: {}", planned.doc.display_path(), e),
+        };
+        match super::severity_rank_from_str(planned.cfg.severity.as_deref()) {
+            2 => errors.push(msg),
+            1 => warnings.push(msg),
+            _ => {}
+        }
+    }
+}
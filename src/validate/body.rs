@@ -91,24 +91,24 @@ pub fn apply_body_validation(
                 continue;
             }
         };
-        let actual_sections = collect_note_headings(&content);
+        let actual_sections: Vec<(String, usize)> = collect_note_headings(&content)
+            .into_iter()
+            .filter(|h| h.level == 2)
+            .map(|h| (h.text, h.end_line - h.line))
+            .collect();
         let actual_names: Vec<String> = actual_sections.iter().map(|(n, _)| n.clone()).collect();
 
         match heading_policy {
-            "exact" => {
-                if actual_names != expected_names {
-                    push_with_severity(
-                        format!(
-                            "{}: headings do not match template (expected {:?}, found {:?})",
-                            doc_path,
-                            expected_names,
-                            actual_names
-                        ),
-                        heading_severity,
-                        errors,
-                        warnings,
-                    );
-                }
+            "exact" if actual_names != expected_names => {
+                push_with_severity(
+                    format!(
+                        "{}: headings do not match template (expected {:?}, found {:?})",
+                        doc_path, expected_names, actual_names
+                    ),
+                    heading_severity,
+                    errors,
+                    warnings,
+                );
             }
             "missing_only" => {
                 let actual_set: BTreeSet<&String> = actual_names.iter().collect();
@@ -207,44 +207,84 @@ fn resolve_template_note(
     load_repo_template(cfg_path, schema).ok().flatten()
 }
 
-fn collect_note_headings(content: &str) -> Vec<(String, usize)> {
-    let mut body = content.replace("\r\n", "\n");
-    if body.starts_with("---\n") || body.starts_with("+++\n") {
-        let delim = if body.starts_with("---\n") {
-            "---"
-        } else {
-            "+++"
-        };
-        let start = 4;
-        let needle = format!("\n{}\n", delim);
-        if let Some(end) = body[start..].find(&needle).map(|i| start + i) {
-            let body_start = end + needle.len();
-            body = body[body_start..].to_string();
+/// An ATX heading with its anchor and 1-based file lines; the section runs
+/// from `line` to `end_line` (before the next heading of the same or a higher level).
+#[derive(Debug, Clone, PartialEq)]
+pub struct NoteHeading {
+    pub level: usize,
+    pub text: String,
+    pub anchor: String,
+    pub line: usize,
+    pub end_line: usize,
+}
+
+/// GitHub-style heading slug: lowercase, spaces to `-`, punctuation dropped.
+pub fn heading_anchor(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_whitespace() => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Headings outside front matter and fenced code, in document order.
+/// Repeated anchors get `-1`, `-2`, ... suffixes.
+pub fn collect_note_headings(content: &str) -> Vec<NoteHeading> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut start = 0;
+    if let Some(delim) = lines
+        .first()
+        .map(|l| l.trim_end())
+        .filter(|l| *l == "---" || *l == "+++")
+    {
+        if let Some(end) = lines.iter().skip(1).position(|l| l.trim_end() == delim) {
+            start = end + 2;
         }
     }
-    let mut sections: Vec<(String, usize)> = Vec::new();
-    let mut current_name: Option<String> = None;
-    let mut current_lines: usize = 0;
+    let mut headings: Vec<NoteHeading> = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
     let mut in_code_block = false;
-    for line in body.lines() {
+    for (i, line) in lines.iter().enumerate().skip(start) {
         let trimmed = line.trim_start();
-        if trimmed.starts_with("```") {
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
             in_code_block = !in_code_block;
         }
-        if !in_code_block && trimmed.starts_with("## ") {
-            if let Some(name) = current_name.take() {
-                sections.push((name, current_lines));
-            }
-            current_name = Some(trimmed.trim_start_matches("## ").trim().to_string());
-            current_lines = 0;
-        } else if current_name.is_some() {
-            current_lines += 1;
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if in_code_block || level == 0 || level > 6 || !trimmed[level..].starts_with(' ') {
+            continue;
         }
+        let text = trimmed[level..]
+            .trim()
+            .trim_end_matches('#')
+            .trim()
+            .to_string();
+        let base = heading_anchor(&text);
+        let n = seen.entry(base.clone()).or_insert(0);
+        let anchor = if *n == 0 {
+            base
+        } else {
+            format!("{}-{}", base, n)
+        };
+        *n += 1;
+        headings.push(NoteHeading {
+            level,
+            text,
+            anchor,
+            line: i + 1,
+            end_line: lines.len(),
+        });
     }
-    if let Some(name) = current_name.take() {
-        sections.push((name, current_lines));
+    for i in 0..headings.len() {
+        let level = headings[i].level;
+        if let Some(next) = headings[i + 1..].iter().find(|h| h.level <= level) {
+            headings[i].end_line = next.line - 1;
+        }
     }
-    sections
+    headings
 }
 
 fn push_with_severity(
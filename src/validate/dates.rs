@@ -0,0 +1,297 @@
+//! Date-aware checks: `[schema.rules.<field>.date]` bounds, field ordering,
+//! review dates, and `[schema.validate.staleness]`.
+//!
+//! Findings feed both `validate` (as diagnostics) and `review-queue`.
+
+use chrono::{DateTime, NaiveDate};
+use regex::Regex;
+use std::path::PathBuf;
+
+use crate::config::{build_schema_sets, Config, SchemaCfg, SchemaRule};
+use crate::model::AdrDoc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateIssue {
+    /// Outside a `not_before`/`not_after` bound (E226/W226), including dates
+    /// too far ahead of a relative `not_after`.
+    Range,
+    /// Behind a `not_before` bound relative to today, e.g. an `expires` date
+    /// that has passed (E226/W226, listed as overdue).
+    Expired,
+    /// Earlier/later than another date field (E227/W227).
+    Order,
+    /// Review date has passed (W228).
+    Review,
+    /// No change for longer than the schema's staleness window (W229).
+    Stale,
+}
+
+impl DateIssue {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DateIssue::Range => "range",
+            DateIssue::Expired => "expired",
+            DateIssue::Order => "order",
+            DateIssue::Review => "review",
+            DateIssue::Stale => "stale",
+        }
+    }
+
+    /// Whether the note belongs in `review-queue`.
+    pub fn is_overdue(self) -> bool {
+        matches!(
+            self,
+            DateIssue::Expired | DateIssue::Review | DateIssue::Stale
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DateFinding {
+    pub file: PathBuf,
+    pub id: Option<String>,
+    pub title: String,
+    pub schema: String,
+    pub issue: DateIssue,
+    pub field: String,
+    /// The date that triggered the finding (due date, last change, ...).
+    pub date: NaiveDate,
+    /// Days past due; 0 for range/order findings.
+    pub days_overdue: i64,
+    pub is_error: bool,
+    pub message: String,
+}
+
+enum Bound {
+    Date {
+        date: NaiveDate,
+        relative: bool,
+    },
+    Field(String),
+    /// A `today±N` offset beyond the representable dates.
+    Invalid,
+}
+
+fn parse_bound(spec: &str, today: NaiveDate) -> Bound {
+    let spec = spec.trim();
+    let re = Regex::new(r"^today(?:([+-])(\d+)([dw]))?$").unwrap();
+    if let Some(caps) = re.captures(spec) {
+        let n: Option<i64> = match caps.get(2) {
+            Some(m) => m.as_str().parse().ok(),
+            None => Some(0),
+        };
+        let per = if caps.get(3).map(|m| m.as_str()) == Some("w") {
+            7
+        } else {
+            1
+        };
+        let sign = if caps.get(1).map(|m| m.as_str()) == Some("-") {
+            -1
+        } else {
+            1
+        };
+        let date = n
+            .and_then(|n| n.checked_mul(per * sign))
+            .and_then(chrono::Duration::try_days)
+            .and_then(|d| today.checked_add_signed(d));
+        return match date {
+            Some(date) => Bound::Date {
+                date,
+                relative: true,
+            },
+            None => Bound::Invalid,
+        };
+    }
+    match NaiveDate::parse_from_str(spec, "%Y-%m-%d") {
+        Ok(date) => Bound::Date {
+            date,
+            relative: false,
+        },
+        Err(_) => Bound::Field(spec.to_string()),
+    }
+}
+
+/// Parse a front matter date with the rule's `format`, falling back to
+/// `YYYY-MM-DD` and RFC 3339 timestamps.
+pub fn parse_date(value: &serde_yaml::Value, format: Option<&str>) -> Option<NaiveDate> {
+    let s = value.as_str()?.trim();
+    if let Some(fmt) = format {
+        return NaiveDate::parse_from_str(s, fmt).ok();
+    }
+    NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .ok()
+        .or_else(|| DateTime::parse_from_rfc3339(s).ok().map(|d| d.date_naive()))
+}
+
+fn finding(
+    doc: &AdrDoc,
+    sc: &SchemaCfg,
+    field: &str,
+    date: NaiveDate,
+    (issue, days_overdue, message): (DateIssue, i64, String),
+    is_error: bool,
+) -> DateFinding {
+    DateFinding {
+        file: doc.file.clone(),
+        id: doc.id.clone(),
+        title: doc.title.clone(),
+        schema: sc.name.clone(),
+        issue,
+        field: field.to_string(),
+        date,
+        days_overdue,
+        is_error,
+        message: format!("{}: {}", doc.display_path(), message),
+    }
+}
+
+fn check_rule(
+    doc: &AdrDoc,
+    sc: &SchemaCfg,
+    field: &str,
+    rule: &SchemaRule,
+    today: NaiveDate,
+    out: &mut Vec<DateFinding>,
+) {
+    let Some(date_rule) = &rule.date else { return };
+    let Some(value) = doc
+        .fm
+        .get(field)
+        .and_then(|v| parse_date(v, rule.format.as_deref()))
+    else {
+        return;
+    };
+    let sev_err = rule.severity.as_deref().unwrap_or("error") == "error";
+    let bounds = [
+        (date_rule.not_before.as_deref(), true),
+        (date_rule.not_after.as_deref(), false),
+    ];
+    for (spec, lower) in bounds {
+        let Some(spec) = spec else { continue };
+        let (limit, relative, other) = match parse_bound(spec, today) {
+            Bound::Date { date, relative } => (date, relative, None),
+            Bound::Invalid => {
+                let msg = format!("'{}' date bound '{}' is out of range", field, spec);
+                let detail = (DateIssue::Range, 0, msg);
+                out.push(finding(doc, sc, field, value, detail, true));
+                continue;
+            }
+            Bound::Field(name) => {
+                let other_rule = sc.rules.get(&name).and_then(|r| r.format.as_deref());
+                match doc.fm.get(&name).and_then(|v| parse_date(v, other_rule)) {
+                    Some(d) => (d, false, Some(name)),
+                    None => continue,
+                }
+            }
+        };
+        let violated = if lower { value < limit } else { value > limit };
+        if !violated {
+            continue;
+        }
+        let word = if lower { "before" } else { "after" };
+        let detail = match other {
+            Some(name) => (
+                DateIssue::Order,
+                0,
+                format!(
+                    "'{}' ({}) must not be {} '{}' ({})",
+                    field, value, word, name, limit
+                ),
+            ),
+            None if relative && lower => (
+                DateIssue::Expired,
+                (limit - value).num_days(),
+                format!("'{}' expired on {} (limit {})", field, value, limit),
+            ),
+            None => (
+                DateIssue::Range,
+                0,
+                format!(
+                    "'{}' date {} is {} {} ({})",
+                    field, value, word, limit, spec
+                ),
+            ),
+        };
+        out.push(finding(doc, sc, field, value, detail, sev_err));
+    }
+    if date_rule.review_by && value < today {
+        let days = (today - value).num_days();
+        let msg = format!(
+            "review date '{}' {} has passed ({} days overdue)",
+            field, value, days
+        );
+        let is_error = rule.severity.as_deref() == Some("error");
+        let detail = (DateIssue::Review, days, msg);
+        out.push(finding(doc, sc, field, value, detail, is_error));
+    }
+}
+
+fn check_staleness(doc: &AdrDoc, sc: &SchemaCfg, today: NaiveDate, out: &mut Vec<DateFinding>) {
+    let Some(stale) = sc.validate.as_ref().and_then(|v| v.staleness.as_ref()) else {
+        return;
+    };
+    let status = doc.status.as_deref().unwrap_or("");
+    if !stale.statuses.is_empty() && !stale.statuses.iter().any(|s| s == status) {
+        return;
+    }
+    let (field, last) = match &stale.field {
+        Some(f) => {
+            let format = sc.rules.get(f).and_then(|r| r.format.as_deref());
+            (f.clone(), doc.fm.get(f).and_then(|v| parse_date(v, format)))
+        }
+        // docs loaded from the unified index carry no mtime; ask the file
+        None => (
+            "mtime".to_string(),
+            doc.mtime
+                .or_else(|| crate::model::file_mtime(&doc.file).ok())
+                .and_then(|secs| DateTime::from_timestamp(secs as i64, 0))
+                .map(|d| d.date_naive()),
+        ),
+    };
+    let Some(last) = last else { return };
+    let age = (today - last).num_days();
+    if age <= stale.after_days as i64 {
+        return;
+    }
+    let msg = format!(
+        "stale: status '{}' unchanged for {} days (limit {})",
+        status, age, stale.after_days
+    );
+    let is_error = stale.severity.as_deref() == Some("error");
+    let overdue = age - stale.after_days as i64;
+    let detail = (DateIssue::Stale, overdue, msg);
+    out.push(finding(doc, sc, &field, last, detail, is_error));
+}
+
+/// Evaluate every date rule for `docs` against `today`.
+pub fn date_findings(cfg: &Config, docs: &[AdrDoc], today: NaiveDate) -> Vec<DateFinding> {
+    let schema_sets = build_schema_sets(cfg);
+    let mut out = Vec::new();
+    for doc in docs {
+        let fname = doc.file.file_name().and_then(|s| s.to_str()).unwrap_or("");
+        let Some((sc, _)) = schema_sets.iter().find(|(_, set)| set.is_match(fname)) else {
+            continue;
+        };
+        for (field, rule) in &sc.rules {
+            check_rule(doc, sc, field, rule, today, &mut out);
+        }
+        check_staleness(doc, sc, today, &mut out);
+    }
+    out
+}
+
+pub(crate) fn apply_date_validation(
+    cfg: &Config,
+    docs: &[AdrDoc],
+    errors: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) {
+    let today = chrono::Local::now().date_naive();
+    for f in date_findings(cfg, docs, today) {
+        if f.is_error {
+            errors.push(f.message);
+        } else {
+            warnings.push(f.message);
+        }
+    }
+}
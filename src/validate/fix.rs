@@ -0,0 +1,274 @@
+//! Mechanical fixes for common validation findings (`validate --fix`).
+//!
+//! Each fix edits the parsed front matter only; bodies are written back untouched
+//! and the original YAML/TOML flavor is kept. Missing required keys are only
+//! filled when the schema declares a `[schema.new.defaults]` value for them.
+
+use anyhow::{Context, Result};
+use serde_yaml::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use super::schema_rules::RESERVED_KEYS;
+use crate::commands::new_defaults::render_schema_defaults;
+use crate::commands::new_helpers::TemplateVars;
+use crate::config::{build_schema_sets, Config, SchemaCfg};
+use crate::frontmatter::{string_list, FrontMatterDoc};
+use crate::model::AdrDoc;
+
+/// Planned edits for one note.
+#[derive(Debug, Clone)]
+pub struct FileFix {
+    pub path: PathBuf,
+    pub applied: Vec<String>,
+    pub before: String,
+    pub after: String,
+}
+
+struct Pending {
+    path: PathBuf,
+    before: String,
+    fm: FrontMatterDoc,
+    applied: Vec<String>,
+}
+
+const LIST_KEYS: [&str; 3] = ["tags", "groups", "depends_on"];
+
+fn fix_list_shapes(fm: &mut FrontMatterDoc, sc: Option<&SchemaCfg>, applied: &mut Vec<String>) {
+    let mut keys: Vec<String> = LIST_KEYS.iter().map(|s| s.to_string()).collect();
+    if let Some(sc) = sc {
+        for (field, rule) in &sc.rules {
+            if rule.r#type.as_deref() == Some("array") || rule.min_items.is_some() {
+                keys.push(field.clone());
+            }
+        }
+    }
+    for key in keys {
+        if let Some(Value::String(s)) = fm.get(&key).cloned() {
+            fm.insert(&key, Value::Sequence(vec![Value::String(s)]));
+            applied.push(format!("'{}' converted to a list", key));
+        }
+    }
+}
+
+fn fix_duplicate_tags(fm: &mut FrontMatterDoc, applied: &mut Vec<String>) {
+    let Some(Value::Sequence(seq)) = fm.get("tags").cloned() else {
+        return;
+    };
+    let mut seen: Vec<Value> = Vec::new();
+    let mut dups: BTreeSet<String> = BTreeSet::new();
+    for v in seq {
+        if seen.contains(&v) {
+            dups.insert(v.as_str().unwrap_or_default().to_string());
+        } else {
+            seen.push(v);
+        }
+    }
+    if !dups.is_empty() {
+        fm.insert("tags", Value::Sequence(seen));
+        let dups: Vec<String> = dups.into_iter().collect();
+        applied.push(format!("removed duplicate tags: {}", dups.join(", ")));
+    }
+}
+
+fn canonical_case(value: &str, allowed: &[String]) -> Option<String> {
+    if allowed.iter().any(|a| a == value) {
+        return None;
+    }
+    allowed
+        .iter()
+        .find(|a| a.eq_ignore_ascii_case(value))
+        .cloned()
+}
+
+fn fix_enum_casing(
+    fm: &mut FrontMatterDoc,
+    cfg: &Config,
+    sc: Option<&SchemaCfg>,
+    applied: &mut Vec<String>,
+) {
+    let mut enums: Vec<(String, Vec<String>)> = Vec::new();
+    if let Some(sc) = sc {
+        for (field, rule) in &sc.rules {
+            if let Some(values) = rule.enum_values.clone() {
+                enums.push((field.clone(), values));
+            } else if !rule.allowed.is_empty() {
+                enums.push((field.clone(), rule.allowed.clone()));
+            }
+        }
+    }
+    if !enums.iter().any(|(f, _)| f == "status") {
+        enums.push(("status".into(), cfg.allowed_statuses.clone()));
+    }
+    for (field, allowed) in enums {
+        let fixed = match fm.get(&field) {
+            Some(Value::String(s)) => canonical_case(s, &allowed)
+                .map(|c| (vec![format!("'{}' -> '{}'", s, c)], Value::String(c))),
+            Some(Value::Sequence(seq)) => {
+                let mut notes = Vec::new();
+                let items = seq
+                    .iter()
+                    .map(
+                        |v| match v.as_str().and_then(|s| canonical_case(s, &allowed)) {
+                            Some(c) => {
+                                notes.push(format!("'{}' -> '{}'", v.as_str().unwrap_or(""), c));
+                                Value::String(c)
+                            }
+                            None => v.clone(),
+                        },
+                    )
+                    .collect();
+                (!notes.is_empty()).then(|| (notes, Value::Sequence(items)))
+            }
+            _ => None,
+        };
+        if let Some((notes, value)) = fixed {
+            fm.insert(&field, value);
+            applied.push(format!("'{}' case fixed: {}", field, notes.join(", ")));
+        }
+    }
+}
+
+fn fix_unknown_keys(fm: &mut FrontMatterDoc, sc: Option<&SchemaCfg>, applied: &mut Vec<String>) {
+    let Some(sc) = sc else { return };
+    // Under "warn" the keys are user data the schema merely tolerates.
+    if sc.unknown_policy.as_deref() != Some("error") {
+        return;
+    }
+    let known: BTreeSet<&str> = RESERVED_KEYS
+        .iter()
+        .copied()
+        .chain(sc.rules.keys().map(|s| s.as_str()))
+        .chain(sc.required.iter().map(|s| s.as_str()))
+        .chain(sc.allowed_keys.iter().map(|s| s.as_str()))
+        .collect();
+    let unknown: Vec<String> = fm
+        .keys()
+        .filter(|k| !known.contains(k))
+        .map(|k| k.to_string())
+        .collect();
+    for key in &unknown {
+        fm.remove(key);
+    }
+    if !unknown.is_empty() {
+        applied.push(format!("removed unknown keys: {}", unknown.join(", ")));
+    }
+}
+
+/// When A `supersedes` B, make sure B lists A in `superseded_by`.
+fn fix_reciprocal_supersedes(notes: &mut [Pending]) {
+    let mut index_of: HashMap<String, usize> = HashMap::new();
+    for (i, n) in notes.iter().enumerate() {
+        if let Some(id) = n.fm.get("id").and_then(|v| v.as_str()) {
+            index_of.entry(id.to_string()).or_insert(i);
+        }
+    }
+    let mut wanted: Vec<(usize, String)> = Vec::new();
+    for n in notes.iter() {
+        let Some(id) = n.fm.get("id").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        for target in n.fm.get("supersedes").map(string_list).unwrap_or_default() {
+            if let Some(&t) = index_of.get(&target) {
+                wanted.push((t, id.to_string()));
+            }
+        }
+    }
+    for (t, superseder) in wanted {
+        let Pending { fm, applied, .. } = &mut notes[t];
+        let mut current = fm.get("superseded_by").map(string_list).unwrap_or_default();
+        if current.contains(&superseder) {
+            continue;
+        }
+        current.push(superseder.clone());
+        fm.insert(
+            "superseded_by",
+            Value::Sequence(current.into_iter().map(Value::String).collect()),
+        );
+        applied.push(format!("added '{}' to superseded_by", superseder));
+    }
+}
+
+fn fix_missing_defaults(
+    fm: &mut FrontMatterDoc,
+    doc: &AdrDoc,
+    sc: Option<&SchemaCfg>,
+    applied: &mut Vec<String>,
+) {
+    let Some(sc) = sc else { return };
+    let filename = doc.file.file_name().and_then(|s| s.to_str()).unwrap_or("");
+    let vars = TemplateVars {
+        id: doc.id.as_deref().unwrap_or(""),
+        title: &doc.title,
+        schema: &sc.name,
+        filename,
+    };
+    let defaults = render_schema_defaults(Some(sc), &vars);
+    for key in &sc.required {
+        if fm.get(key).is_some() {
+            continue;
+        }
+        if let Some(value) = defaults.get(key.as_str()) {
+            fm.insert(key, value.clone());
+            applied.push(format!(
+                "added missing required key '{}' from schema default",
+                key
+            ));
+        }
+    }
+}
+
+/// Compute safe front matter fixes for `docs` without writing anything.
+pub fn plan_fixes(cfg: &Config, docs: &[AdrDoc]) -> Result<Vec<FileFix>> {
+    let schema_sets = build_schema_sets(cfg);
+    let mut notes: Vec<Pending> = Vec::new();
+    for d in docs {
+        let before = std::fs::read_to_string(&d.file)
+            .with_context(|| format!("reading {}", d.file.display()))?;
+        let Some(mut fm) = FrontMatterDoc::parse(&before) else {
+            continue;
+        };
+        let fname = d.file.file_name().and_then(|s| s.to_str()).unwrap_or("");
+        let sc = schema_sets
+            .iter()
+            .find(|(_, set)| set.is_match(fname))
+            .map(|(sc, _)| sc);
+        let mut applied = Vec::new();
+        fix_list_shapes(&mut fm, sc, &mut applied);
+        fix_duplicate_tags(&mut fm, &mut applied);
+        fix_enum_casing(&mut fm, cfg, sc, &mut applied);
+        fix_unknown_keys(&mut fm, sc, &mut applied);
+        fix_missing_defaults(&mut fm, d, sc, &mut applied);
+        notes.push(Pending {
+            path: d.file.clone(),
+            before,
+            fm,
+            applied,
+        });
+    }
+    fix_reciprocal_supersedes(&mut notes);
+
+    let mut out = Vec::new();
+    for n in notes {
+        if n.applied.is_empty() {
+            continue;
+        }
+        let after = n.fm.render()?;
+        out.push(FileFix {
+            path: n.path,
+            applied: n.applied,
+            before: n.before,
+            after,
+        });
+    }
+    Ok(out)
+}
+
+/// Write planned fixes back to disk.
+pub fn write_fixes(fixes: &[FileFix]) -> Result<()> {
+    for f in fixes {
+        std::fs::write(&f.path, &f.after)
+            .with_context(|| format!("writing {}", f.path.display()))?;
+    }
+    Ok(())
+}
@@ -15,6 +15,13 @@ pub fn build_id_map(docs: &Vec<AdrDoc>, errors: &mut Vec<String>) -> HashMap<Str
     id_to_docs
 }
 
+/// `missing id` for each of `docs` without one.
+pub fn check_missing_ids(docs: &[AdrDoc], errors: &mut Vec<String>) {
+    for d in docs.iter().filter(|d| d.id.is_none()) {
+        errors.push(format!("{}: missing id", d.display_path()));
+    }
+}
+
 // Detect duplicates and conflicts across docs sharing the same id.
 pub fn detect_dups_conflicts(id_to_docs: &HashMap<String, Vec<AdrDoc>>, errors: &mut Vec<String>) {
     for (id, lst) in id_to_docs {
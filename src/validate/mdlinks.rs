@@ -0,0 +1,25 @@
+use crate::mdlink::{normalize_path, read_markdown_links, LinkTarget};
+use crate::model::AdrDoc;
+
+/// Warn (W235) for relative Markdown links whose file does not exist.
+/// Root-relative (`/docs/x.md`) links depend on a site layout and are skipped.
+pub fn warn_broken_links(docs: &[AdrDoc], warnings: &mut Vec<String>) {
+    for doc in docs {
+        let from = normalize_path(&doc.file);
+        for link in read_markdown_links(&doc.file) {
+            if link.dest.starts_with('/') {
+                continue;
+            }
+            if let LinkTarget::File { path, .. } = link.target(&from) {
+                if !path.exists() {
+                    warnings.push(format!(
+                        "{}: broken link '{}' (line {})",
+                        doc.display_path(),
+                        link.dest,
+                        link.line
+                    ));
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
 use crate::model::AdrDoc;
 
@@ -21,3 +21,25 @@ pub fn check_references(docs: &Vec<AdrDoc>, id_set: &BTreeSet<String>, errors: &
         }
     }
 }
+
+/// Warn when A supersedes B but B does not list A in `superseded_by`.
+pub fn check_reciprocal_supersedes(
+    docs: &[AdrDoc],
+    id_to_docs: &HashMap<String, Vec<AdrDoc>>,
+    warnings: &mut Vec<String>,
+) {
+    for d in docs {
+        let Some(id) = &d.id else { continue };
+        for s in &d.supersedes {
+            if let Some(target) = id_to_docs.get(s).and_then(|v| v.first()) {
+                if !target.superseded_by.iter().any(|b| b == id) {
+                    warnings.push(format!(
+                        "{}: missing reciprocal superseded_by entry for '{}'",
+                        target.display_path(),
+                        id
+                    ));
+                }
+            }
+        }
+    }
+}
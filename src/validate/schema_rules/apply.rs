@@ -3,6 +3,17 @@ use crate::config::{Config, SchemaCfg};
 use crate::model::AdrDoc;
 use std::collections::{BTreeSet, HashMap};
 
+/// Front matter keys every schema understands regardless of `allowed_keys`.
+pub(crate) const RESERVED_KEYS: [&str; 7] = [
+    "id",
+    "tags",
+    "status",
+    "groups",
+    "depends_on",
+    "supersedes",
+    "superseded_by",
+];
+
 pub fn apply_schema_validation(
     cfg: &Config,
     docs: &Vec<AdrDoc>,
@@ -11,18 +22,7 @@ pub fn apply_schema_validation(
     errors: &mut Vec<String>,
     warnings: &mut Vec<String>,
 ) {
-    let reserved: BTreeSet<String> = [
-        "id",
-        "tags",
-        "status",
-        "groups",
-        "depends_on",
-        "supersedes",
-        "superseded_by",
-    ]
-    .into_iter()
-    .map(|s| s.to_string())
-    .collect();
+    let reserved: BTreeSet<String> = RESERVED_KEYS.iter().map(|s| s.to_string()).collect();
 
     for doc in docs {
         if doc.fm.is_empty() {
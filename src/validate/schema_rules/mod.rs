@@ -3,3 +3,4 @@ mod field_rules;
 mod value_checks;
 
 pub use apply::apply_schema_validation;
+pub(crate) use apply::RESERVED_KEYS;
@@ -0,0 +1,128 @@
+//! The validation check list, shared by `validate` and watch mode. Per-note
+//! checks report only for the notes in scope; repo-wide checks (duplicate
+//! ids, cycles, isolation) always cover every note.
+
+use std::cell::OnceCell;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::{
+    backlink_blocks, body, dates, ids, isolation, mdlinks, refs, rules, schema_match, schema_rules,
+    wikilinks, ValidationReport,
+};
+use crate::config::Config;
+use crate::index::{index_entries, IndexEntry};
+use crate::model::AdrDoc;
+use crate::wikilink::LinkResolver;
+
+pub struct ScopedReport {
+    /// Findings of per-note checks, limited to notes in scope.
+    pub notes: ValidationReport,
+    /// Duplicate ids, cycles and isolation across all notes.
+    pub graph: ValidationReport,
+}
+
+fn report(
+    errors: Vec<String>,
+    warnings: Vec<String>,
+    doc_count: usize,
+    id_count: usize,
+) -> ValidationReport {
+    ValidationReport {
+        ok: errors.is_empty(),
+        errors,
+        warnings,
+        doc_count,
+        id_count,
+    }
+}
+
+/// Validate the notes of `docs` whose file is in `scope`. `entries` are the
+/// index entries of every note, which watch mode keeps up to date; the
+/// wikilink policy and backlinks checks read them instead of every file.
+pub fn validate_scoped(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    docs: &Vec<AdrDoc>,
+    entries: &[IndexEntry],
+    scope: &HashSet<PathBuf>,
+) -> ScopedReport {
+    let scoped: Vec<AdrDoc> = docs
+        .iter()
+        .filter(|d| scope.contains(&d.file))
+        .cloned()
+        .collect();
+    check_all(cfg, cfg_path, docs, Some(entries), &scoped)
+}
+
+/// Every check, per-note ones over `scope`. Without cached `entries`, they
+/// are built from `docs` the first time a check needs them.
+pub(super) fn check_all(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    docs: &Vec<AdrDoc>,
+    entries: Option<&[IndexEntry]>,
+    scope: &Vec<AdrDoc>,
+) -> ScopedReport {
+    let links = LinkResolver::new(docs);
+    let built: OnceCell<Vec<IndexEntry>> = OnceCell::new();
+    let entries = || {
+        entries.unwrap_or_else(|| {
+            built.get_or_init(|| index_entries(cfg, docs, Path::new(""), &links))
+        })
+    };
+    let (mut errors, mut warnings) = (Vec::new(), Vec::new());
+    let id_to_docs = ids::build_id_map(docs, &mut Vec::new());
+    ids::check_missing_ids(scope, &mut errors);
+    let doc_schema = schema_match::compute_doc_schema(cfg, docs);
+    for (path, names) in schema_match::compute_file_schema_matches(cfg, scope) {
+        if names.len() > 1 {
+            errors.push(format!(
+                "{}: multiple schema matches: [{}]",
+                path.display(),
+                names.join(", ")
+            ));
+        }
+    }
+    rules::check_statuses(cfg, scope, &doc_schema, &mut errors);
+    let id_set: BTreeSet<String> = id_to_docs.keys().cloned().collect();
+    refs::check_references(scope, &id_set, &mut errors);
+    refs::check_reciprocal_supersedes(scope, &id_to_docs, &mut warnings);
+    schema_rules::apply_schema_validation(
+        cfg,
+        scope,
+        &doc_schema,
+        &id_to_docs,
+        &mut errors,
+        &mut warnings,
+    );
+    body::apply_body_validation(
+        cfg,
+        cfg_path,
+        scope,
+        &doc_schema,
+        &mut errors,
+        &mut warnings,
+    );
+    dates::apply_date_validation(cfg, scope, &mut errors, &mut warnings);
+    wikilinks::apply_wikilink_policy(cfg, scope, entries, &doc_schema, &mut errors, &mut warnings);
+    wikilinks::warn_unresolved(scope, &links, &mut warnings);
+    mdlinks::warn_broken_links(scope, &mut warnings);
+    backlink_blocks::check_stale_blocks(cfg, docs, entries, scope, &mut errors, &mut warnings);
+
+    let (mut graph_errors, mut graph_warnings) = (Vec::new(), Vec::new());
+    ids::detect_dups_conflicts(&id_to_docs, &mut graph_errors);
+    super::check_cycles(
+        cfg,
+        &id_to_docs,
+        &doc_schema,
+        &mut graph_errors,
+        &mut graph_warnings,
+    );
+    isolation::warn_isolated(docs, &id_to_docs, &mut graph_warnings);
+
+    ScopedReport {
+        notes: report(errors, warnings, scope.len(), id_to_docs.len()),
+        graph: report(graph_errors, graph_warnings, docs.len(), id_to_docs.len()),
+    }
+}
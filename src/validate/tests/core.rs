@@ -14,6 +14,9 @@ fn test_validate_docs_invalid_status_and_refs_and_duplicates() {
         defaults: default_defaults(),
         schema: Vec::new(),
         authoring: crate::config::schema::AuthoringCfg::default(),
+        lua: crate::config::schema::LuaCfg::default(),
+        search: crate::config::schema::SearchCfg::default(),
+        view: Vec::new(),
         overlays: crate::config::schema::OverlayInfo::default(),
     };
 
@@ -30,6 +33,7 @@ fn test_validate_docs_invalid_status_and_refs_and_duplicates() {
         fm: BTreeMap::new(),
         mtime: None,
         size: None,
+        headings: None,
     };
     let d2 = AdrDoc {
         file: PathBuf::from("A1.md"),
@@ -44,6 +48,7 @@ fn test_validate_docs_invalid_status_and_refs_and_duplicates() {
         fm: BTreeMap::new(),
         mtime: None,
         size: None,
+        headings: None,
     };
     let d3 = AdrDoc {
         file: PathBuf::from("A2.md"),
@@ -58,6 +63,7 @@ fn test_validate_docs_invalid_status_and_refs_and_duplicates() {
         fm: BTreeMap::new(),
         mtime: None,
         size: None,
+        headings: None,
     };
     let docs = vec![d1, d2, d3];
     let cfg_path: Option<PathBuf> = None;
@@ -88,6 +94,7 @@ fn test_schema_required_unknown_and_refers_to_types() {
             globs: None,
             integer: None,
             float: None,
+            date: None,
         },
     );
     let sc_adr = SchemaCfg {
@@ -101,6 +108,7 @@ fn test_schema_required_unknown_and_refers_to_types() {
         allowed_keys: vec![],
         rules,
         validate: None,
+        backlinks: None,
     };
     let sc_imp = SchemaCfg {
         name: "IMP".into(),
@@ -113,6 +121,7 @@ fn test_schema_required_unknown_and_refers_to_types() {
         allowed_keys: vec![],
         rules: BTreeMap::new(),
         validate: None,
+        backlinks: None,
     };
     let cfg = Config {
         config_version: Some(crate::config::defaults::default_config_version()),
@@ -126,6 +135,9 @@ fn test_schema_required_unknown_and_refers_to_types() {
         defaults: default_defaults(),
         schema: vec![sc_adr, sc_imp],
         authoring: crate::config::schema::AuthoringCfg::default(),
+        lua: crate::config::schema::LuaCfg::default(),
+        search: crate::config::schema::SearchCfg::default(),
+        view: Vec::new(),
         overlays: crate::config::schema::OverlayInfo::default(),
     };
 
@@ -148,6 +160,7 @@ fn test_schema_required_unknown_and_refers_to_types() {
         fm,
         mtime: None,
         size: None,
+        headings: None,
     };
     let d2 = AdrDoc {
         file: PathBuf::from("IMP-002.md"),
@@ -162,6 +175,7 @@ fn test_schema_required_unknown_and_refers_to_types() {
         fm: BTreeMap::new(),
         mtime: None,
         size: None,
+        headings: None,
     };
 
     let cfg_path: Option<PathBuf> = None;
@@ -188,6 +202,9 @@ fn test_warn_on_isolated_adrs() {
         defaults: default_defaults(),
         schema: Vec::new(),
         authoring: crate::config::schema::AuthoringCfg::default(),
+        lua: crate::config::schema::LuaCfg::default(),
+        search: crate::config::schema::SearchCfg::default(),
+        view: Vec::new(),
         overlays: crate::config::schema::OverlayInfo::default(),
     };
     let d = AdrDoc {
@@ -203,6 +220,7 @@ fn test_warn_on_isolated_adrs() {
         fm: BTreeMap::new(),
         mtime: None,
         size: None,
+        headings: None,
     };
     let cfg_path: Option<PathBuf> = None;
     let report = validate_docs(&cfg, &cfg_path, &vec![d]);
@@ -27,6 +27,7 @@ fn test_cross_schema_disallowed() {
         severity: Some("error".into()),
         body: None,
         edges: Some(edges),
+        staleness: None,
     };
 
     let sc_imp = SchemaCfg {
@@ -40,6 +41,7 @@ fn test_cross_schema_disallowed() {
         allowed_keys: vec!["depends_on".into()],
         rules: BTreeMap::new(),
         validate: Some(validate_imp),
+        backlinks: None,
     };
 
     let sc_log = SchemaCfg {
@@ -53,6 +55,7 @@ fn test_cross_schema_disallowed() {
         allowed_keys: Vec::new(),
         rules: BTreeMap::new(),
         validate: None,
+        backlinks: None,
     };
 
     let cfg = Config {
@@ -67,6 +70,9 @@ fn test_cross_schema_disallowed() {
         defaults: default_defaults(),
         schema: vec![sc_imp, sc_log],
         authoring: crate::config::schema::AuthoringCfg::default(),
+        lua: crate::config::schema::LuaCfg::default(),
+        search: crate::config::schema::SearchCfg::default(),
+        view: Vec::new(),
         overlays: crate::config::schema::OverlayInfo::default(),
     };
 
@@ -89,6 +95,7 @@ fn test_cross_schema_disallowed() {
         fm: fm_imp,
         mtime: None,
         size: None,
+        headings: None,
     };
 
     let log = AdrDoc {
@@ -104,6 +111,7 @@ fn test_cross_schema_disallowed() {
         fm: BTreeMap::new(),
         mtime: None,
         size: None,
+        headings: None,
     };
 
     let report = validate_docs(&cfg, &None, &vec![imp, log]);
@@ -129,6 +137,7 @@ fn test_cycle_policy_override_to_error() {
         severity: Some("warning".into()),
         body: None,
         edges: Some(edges),
+        staleness: None,
     };
 
     let sc = SchemaCfg {
@@ -142,6 +151,7 @@ fn test_cycle_policy_override_to_error() {
         allowed_keys: vec!["depends_on".into()],
         rules: BTreeMap::new(),
         validate: Some(validate_cfg),
+        backlinks: None,
     };
 
     let cfg = Config {
@@ -156,6 +166,9 @@ fn test_cycle_policy_override_to_error() {
         defaults: default_defaults(),
         schema: vec![sc],
         authoring: crate::config::schema::AuthoringCfg::default(),
+        lua: crate::config::schema::LuaCfg::default(),
+        search: crate::config::schema::SearchCfg::default(),
+        view: Vec::new(),
         overlays: crate::config::schema::OverlayInfo::default(),
     };
 
@@ -183,6 +196,7 @@ fn test_cycle_policy_override_to_error() {
         fm: doc_a_fm,
         mtime: None,
         size: None,
+        headings: None,
     };
     let doc_b = AdrDoc {
         file: PathBuf::from("IMP-002.md"),
@@ -197,6 +211,7 @@ fn test_cycle_policy_override_to_error() {
         fm: doc_b_fm,
         mtime: None,
         size: None,
+        headings: None,
     };
 
     let report = validate_docs(&cfg, &None, &vec![doc_a, doc_b]);
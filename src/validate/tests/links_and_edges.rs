@@ -71,6 +71,7 @@ fn test_required_edges_missing_values() {
         severity: Some("error".into()),
         body: None,
         edges: Some(edges),
+        staleness: None,
     };
 
     let sc_imp = SchemaCfg {
@@ -84,6 +85,7 @@ fn test_required_edges_missing_values() {
         allowed_keys: vec!["implements".into()],
         rules: BTreeMap::new(),
         validate: Some(validate_cfg),
+        backlinks: None,
     };
 
     let cfg = Config {
@@ -98,6 +100,9 @@ fn test_required_edges_missing_values() {
         defaults: default_defaults(),
         schema: vec![sc_imp],
         authoring: crate::config::schema::AuthoringCfg::default(),
+        lua: crate::config::schema::LuaCfg::default(),
+        search: crate::config::schema::SearchCfg::default(),
+        view: Vec::new(),
         overlays: crate::config::schema::OverlayInfo::default(),
     };
 
@@ -116,6 +121,7 @@ fn test_required_edges_missing_values() {
         fm: doc_fm,
         mtime: None,
         size: None,
+        headings: None,
     };
 
     let docs = vec![doc.clone()];
@@ -147,3 +153,40 @@ fn test_required_edges_missing_values() {
         report.errors
     );
 }
+
+#[test]
+fn test_scoped_wikilink_policy_counts_from_cached_entries() {
+    let dir = tempdir().unwrap();
+    let file_a = dir.path().join("AI-IMP-001.md");
+    let file_b = dir.path().join("AI-IMP-002.md");
+    std::fs::write(&file_a, "# One\n\nNo links.\n").unwrap();
+    std::fs::write(&file_b, "# Two\n\nSee [[AI-IMP-001]].\n").unwrap();
+
+    let schema = build_schema_with_wikilinks(
+        SchemaWikilinksCfg {
+            min_outgoing: None,
+            min_incoming: Some(1),
+            severity: Some("error".into()),
+        },
+        None,
+    );
+    let cfg = build_base_config(schema);
+    let docs = vec![
+        make_doc(&file_a, "AI-IMP-001"),
+        make_doc(&file_b, "AI-IMP-002"),
+    ];
+    let links = crate::wikilink::LinkResolver::new(&docs);
+    let entries = crate::index::index_entries(&cfg, &docs, dir.path(), &links);
+    // Notes outside the scope are not read again: their cached entries count.
+    std::fs::remove_file(&file_b).unwrap();
+    let scope = std::collections::HashSet::from([file_a.clone()]);
+    let report = validate_scoped(&cfg, &None, &docs, &entries, &scope);
+    assert!(report.notes.ok, "{:?}", report.notes.errors);
+    assert_eq!(report.notes.doc_count, 1);
+
+    let report = validate_docs(&cfg, &None, &docs);
+    assert!(report
+        .errors
+        .iter()
+        .any(|m| m.starts_with(&format!("{}: wikilinks incoming", file_a.display()))));
+}
@@ -23,6 +23,9 @@ fn build_base_config(schema: SchemaCfg) -> Config {
         defaults: default_defaults(),
         schema: vec![schema],
         authoring: crate::config::schema::AuthoringCfg::default(),
+        lua: crate::config::schema::LuaCfg::default(),
+        search: crate::config::schema::SearchCfg::default(),
+        view: Vec::new(),
         overlays: crate::config::schema::OverlayInfo::default(),
     }
 }
@@ -49,7 +52,9 @@ fn build_schema_with_wikilinks(
                 wikilinks: Some(wikilinks),
                 kinds: BTreeMap::<String, EdgeKindPolicy>::new(),
             }),
+            staleness: None,
         }),
+        backlinks: None,
     }
 }
 
@@ -67,6 +72,7 @@ fn make_doc(path: &Path, id: &str) -> AdrDoc {
         fm: BTreeMap::new(),
         mtime: None,
         size: None,
+        headings: None,
     }
 }
 
@@ -1,17 +1,24 @@
+use super::body::{collect_note_headings, heading_anchor, NoteHeading};
 use crate::config::{Config, SchemaCfg};
+use crate::index::IndexEntry;
 use crate::model::AdrDoc;
-use regex::Regex;
+use crate::wikilink::{read_wikilinks, LinkResolver};
 use std::collections::{BTreeSet, HashMap};
 use std::fs;
+use std::path::PathBuf;
 
-pub fn apply_wikilink_policy(
+/// Enforce `[schema.validate.edges.wikilinks]` minimums for the notes in
+/// `scope`. Unique targets and referrers are counted from the `mentions`
+/// edges in `entries`, the index entries of every note.
+pub fn apply_wikilink_policy<'a>(
     cfg: &Config,
-    docs: &[AdrDoc],
+    scope: &[AdrDoc],
+    entries: impl FnOnce() -> &'a [IndexEntry],
     doc_schema: &HashMap<String, String>,
     errors: &mut Vec<String>,
     warnings: &mut Vec<String>,
 ) {
-    if docs.is_empty() {
+    if scope.is_empty() {
         return;
     }
 
@@ -31,46 +38,26 @@ pub fn apply_wikilink_policy(
         return;
     }
 
-    let mention_re = Regex::new(r"\[\[([A-Za-z]+-[0-9A-Za-z_-]+)\]\]").unwrap();
     let mut outgoing: HashMap<String, BTreeSet<String>> = HashMap::new();
     let mut incoming: HashMap<String, BTreeSet<String>> = HashMap::new();
 
-    for doc in docs {
-        let doc_id = match &doc.id {
-            Some(id) => id.clone(),
-            None => continue,
-        };
-        let doc_path = doc.display_path();
-        let content = match fs::read_to_string(&doc.file) {
-            Ok(c) => c,
-            Err(err) => {
-                warnings.push(format!(
-                    "{}: unable to read file for wikilink scan: {}",
-                    doc_path,
-                    err
-                ));
-                outgoing.insert(doc_id.clone(), BTreeSet::new());
-                continue;
-            }
-        };
-        let mut targets: BTreeSet<String> = BTreeSet::new();
-        for caps in mention_re.captures_iter(&content) {
-            if let Some(m) = caps.get(1) {
-                targets.insert(m.as_str().to_string());
-            }
+    for entry in entries() {
+        let targets: BTreeSet<String> = entry
+            .edges
+            .iter()
+            .filter(|e| e["kind"] == "mentions")
+            .filter_map(|e| e["to"].as_str().map(|s| s.to_string()))
+            .collect();
+        for target in &targets {
+            incoming
+                .entry(target.clone())
+                .or_default()
+                .insert(entry.id.clone());
         }
-        if !targets.is_empty() {
-            for target in &targets {
-                incoming
-                    .entry(target.clone())
-                    .or_default()
-                    .insert(doc_id.clone());
-            }
-        }
-        outgoing.insert(doc_id, targets);
+        outgoing.insert(entry.id.clone(), targets);
     }
 
-    for doc in docs {
+    for doc in scope {
         let doc_id = match &doc.id {
             Some(id) => id,
             None => continue,
@@ -144,6 +131,56 @@ pub fn apply_wikilink_policy(
     }
 }
 
+/// Warn for wikilinks in `docs` that name no note known to `links` (W233)
+/// or whose `#anchor` is not a heading of the target (W234).
+pub fn warn_unresolved(docs: &[AdrDoc], links: &LinkResolver, warnings: &mut Vec<String>) {
+    let mut headings: HashMap<PathBuf, Vec<NoteHeading>> = HashMap::new();
+    for doc in docs {
+        for link in read_wikilinks(&doc.file) {
+            if link.is_attachment() {
+                continue;
+            }
+            let target = if link.is_self() {
+                Some(doc.file.as_path())
+            } else {
+                links.resolve_path(&link.target)
+            };
+            let Some(target) = target else {
+                warnings.push(format!(
+                    "{}: unresolved wikilink '{}' (line {})",
+                    doc.display_path(),
+                    link.target,
+                    link.line
+                ));
+                continue;
+            };
+            // Block references (`#^id`) are not headings.
+            let Some(anchor) = link.anchor.filter(|a| !a.starts_with('^')) else {
+                continue;
+            };
+            let known = headings.entry(target.to_path_buf()).or_insert_with(|| {
+                fs::read_to_string(target)
+                    .map(|c| collect_note_headings(&c))
+                    .unwrap_or_default()
+            });
+            // Nested anchors (`#Parent#Child`) name the innermost heading.
+            let wanted = heading_anchor(anchor.rsplit('#').next().unwrap_or(&anchor));
+            if !known
+                .iter()
+                .any(|h| h.anchor == wanted || heading_anchor(&h.text) == wanted)
+            {
+                warnings.push(format!(
+                    "{}: wikilink anchor '#{}' not found in {} (line {})",
+                    doc.display_path(),
+                    anchor,
+                    crate::util::normalize_display_path(target),
+                    link.line
+                ));
+            }
+        }
+    }
+}
+
 fn resolve_wikilinks_cfg(
     schema_cfg: &SchemaCfg,
 ) -> Option<&crate::config::schema::SchemaWikilinksCfg> {
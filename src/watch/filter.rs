@@ -0,0 +1,61 @@
+//! Maps raw filesystem event paths to note files using the scan rules.
+
+use anyhow::Result;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+
+/// Decides which filesystem paths are notes, mirroring the scan rules.
+pub(super) struct NoteFilter {
+    bases: Vec<(PathBuf, PathBuf)>,
+    patterns: GlobSet,
+    ignore: GlobSet,
+}
+
+impl NoteFilter {
+    pub(super) fn new(cfg: &Config) -> Result<Self> {
+        let build = |pats: &[String]| -> Result<GlobSet> {
+            let mut b = GlobSetBuilder::new();
+            for p in pats {
+                b.add(Glob::new(p)?);
+            }
+            Ok(b.build()?)
+        };
+        // notify reports canonical paths; notes are keyed under the configured base.
+        let bases = cfg
+            .bases
+            .iter()
+            .map(|b| (b.canonicalize().unwrap_or_else(|_| b.clone()), b.clone()))
+            .collect();
+        Ok(NoteFilter {
+            bases,
+            patterns: build(&cfg.file_patterns)?,
+            ignore: build(&cfg.ignore_globs)?,
+        })
+    }
+
+    /// The note key for an event path, if it lies under a base.
+    pub(super) fn key(&self, path: &Path) -> Option<PathBuf> {
+        self.bases.iter().find_map(|(canon, base)| {
+            path.strip_prefix(canon)
+                .or_else(|_| path.strip_prefix(base))
+                .ok()
+                .map(|rel| base.join(rel))
+        })
+    }
+
+    pub(super) fn is_note(&self, key: &Path) -> bool {
+        let Some(rel) = self
+            .bases
+            .iter()
+            .find_map(|(_, base)| key.strip_prefix(base).ok())
+        else {
+            return false;
+        };
+        let name = key.file_name().map(Path::new).unwrap_or(rel);
+        (self.patterns.is_match(rel) || self.patterns.is_match(name))
+            && !self.ignore.is_match(key)
+            && key.is_file()
+    }
+}
@@ -0,0 +1,180 @@
+use anyhow::Result;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::discovery::incremental_collect_docs;
+use crate::index::write_unified_index;
+
+mod filter;
+mod state;
+
+pub use state::WatchState;
+
+pub struct WatchArgs {
+    pub full_rescan: bool,
+    pub debounce_ms: u64,
+    pub dry_run: bool,
+    pub json: bool,
+}
+
+// Helper to emit NDJSON event envelopes
+fn emit(event: &str, payload: serde_json::Value) {
+    let mut obj = serde_json::json!({
+        "event": event,
+        "protocolVersion": crate::protocol::PROTOCOL_VERSION,
+    });
+    if let Some(map) = obj.as_object_mut() {
+        if let Some(add) = payload.as_object() {
+            for (k, v) in add.iter() {
+                map.insert(k.clone(), v.clone());
+            }
+        }
+    }
+    println!("{}", obj);
+    // Ensure each event is flushed right away
+    use std::io::Write;
+    let _ = std::io::stdout().flush();
+}
+
+/// Report the validation state, then write the index when it is clean.
+fn finish_pass(
+    cfg: &Config,
+    cfg_dir: Option<&Path>,
+    state: &WatchState,
+    args: &WatchArgs,
+    revalidated: Option<usize>,
+) -> Result<()> {
+    let docs = state.docs();
+    if args.json {
+        let mut payload = serde_json::json!({"ok": state.ok(), "docCount": docs.len()});
+        if let Some(n) = revalidated {
+            payload["revalidated"] = serde_json::json!(n);
+        }
+        emit("validated", payload);
+    }
+    if state.ok() && !args.dry_run {
+        if let Some(dir) = cfg_dir {
+            write_unified_index(cfg, dir, &state.entries())?;
+        }
+        if args.json {
+            // Per-base index written events
+            for base in &cfg.bases {
+                let count = docs.iter().filter(|d| d.file.starts_with(base)).count();
+                let path = base.join(&cfg.index_relative);
+                emit(
+                    "index_written",
+                    serde_json::json!({
+                        "path": path.display().to_string(),
+                        "count": count
+                    }),
+                );
+            }
+            // Unified index event if cfg_dir is present
+            if let Some(dir) = cfg_dir {
+                let path = dir.join(&cfg.index_relative);
+                emit(
+                    "index_written",
+                    serde_json::json!({
+                        "path": path.display().to_string(),
+                        "count": docs.len()
+                    }),
+                );
+            }
+        }
+    }
+    let (errors, warnings) = state.issues();
+    if !errors.is_empty() {
+        eprintln!("Validation failed:");
+        for e in &errors {
+            eprintln!(" - {}", e);
+        }
+    }
+    if !warnings.is_empty() {
+        eprintln!("Warnings:");
+        for w in &warnings {
+            eprintln!(" - {}", w);
+        }
+    }
+    Ok(())
+}
+
+fn collect(res: notify::Result<Event>, paths: &mut BTreeSet<PathBuf>) {
+    if let Ok(ev) = res {
+        paths.extend(ev.paths);
+    }
+}
+
+/// Scan all bases, validate, and report the initial pass.
+pub fn initial_state(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    args: &WatchArgs,
+) -> Result<WatchState> {
+    let cfg_dir = cfg_path.as_ref().and_then(|p| p.parent());
+    let docs = incremental_collect_docs(cfg, args.full_rescan)?;
+    let state = WatchState::new(cfg, cfg_path, cfg_dir.unwrap_or(Path::new("")), docs)?;
+    finish_pass(cfg, cfg_dir, &state, args, None)?;
+    Ok(state)
+}
+
+/// Apply filesystem changes to `state` until the watcher channel closes.
+pub fn watch_loop(
+    cfg: &Config,
+    cfg_path: &Option<PathBuf>,
+    args: &WatchArgs,
+    state: &Mutex<WatchState>,
+) -> Result<()> {
+    let cfg_dir = cfg_path.as_ref().and_then(|p| p.parent());
+    let (tx, rx) = std::sync::mpsc::channel::<notify::Result<Event>>();
+    let mut _watchers: Vec<RecommendedWatcher> = Vec::new();
+    for base in &cfg.bases {
+        let txc = tx.clone();
+        let mut w = notify::recommended_watcher(move |res| {
+            let _ = txc.send(res);
+        })?;
+        w.watch(base, RecursiveMode::Recursive)?;
+        _watchers.push(w);
+    }
+    let debounce = Duration::from_millis(args.debounce_ms);
+    let mut paths: BTreeSet<PathBuf> = BTreeSet::new();
+    loop {
+        // Wait for an event, then debounce and drain the burst
+        match rx.recv() {
+            Ok(res) => collect(res, &mut paths),
+            Err(_) => return Ok(()),
+        }
+        std::thread::sleep(debounce);
+        while let Ok(res) = rx.try_recv() {
+            collect(res, &mut paths);
+        }
+        let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+        let changed = state.changed_notes(&std::mem::take(&mut paths));
+        let (events, revalidated) = state.apply(cfg, cfg_path, &changed);
+        if events.is_empty() {
+            continue;
+        }
+        if args.json {
+            for mut ev in events {
+                let name = ev["event"].as_str().unwrap_or("").to_string();
+                if let Some(map) = ev.as_object_mut() {
+                    map.remove("event");
+                }
+                emit(&name, ev);
+            }
+        }
+        finish_pass(cfg, cfg_dir, &state, args, Some(revalidated))?;
+    }
+}
+
+pub fn run_watch(cfg: &Config, cfg_path: &Option<PathBuf>, args: WatchArgs) -> Result<()> {
+    if args.json {
+        // NDJSON handshake first line
+        emit("watch_start", serde_json::json!({}));
+    }
+    let state = Mutex::new(initial_state(cfg, cfg_path, &args)?);
+    watch_loop(cfg, cfg_path, &args, &state)
+}
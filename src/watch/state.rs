@@ -0,0 +1,329 @@
+//! In-memory state for `watch`: parsed notes, cached index entries and the
+//! current diagnostics, updated per changed file instead of per rescan.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::commands::search_gtd::fnv1a_64;
+use crate::config::Config;
+use crate::index::{index_entries, IndexEntry};
+use crate::model::{parse_front_matter_and_title, AdrDoc};
+use crate::validate::validate_scoped;
+use crate::wikilink::{link_keys, LinkResolver};
+
+use super::filter::NoteFilter;
+
+/// (severity, message)
+type Issue = (&'static str, String);
+
+pub struct WatchState {
+    filter: NoteFilter,
+    root: PathBuf,
+    docs: BTreeMap<PathBuf, AdrDoc>,
+    hashes: BTreeMap<PathBuf, u64>,
+    entries: BTreeMap<PathBuf, IndexEntry>,
+    /// Resolver lookup keys of each note's wikilinks.
+    link_keys: BTreeMap<PathBuf, BTreeSet<String>>,
+    links: LinkResolver,
+    note_issues: BTreeMap<PathBuf, BTreeSet<Issue>>,
+    graph_issues: BTreeSet<Issue>,
+}
+
+fn content_hash(path: &Path) -> u64 {
+    std::fs::read_to_string(path)
+        .map(|c| fnv1a_64(&c))
+        .unwrap_or(0)
+}
+
+fn event(name: &str, payload: Value) -> Value {
+    let mut v = payload;
+    v["event"] = json!(name);
+    v
+}
+
+fn edge_keys(entry: Option<&IndexEntry>) -> BTreeSet<(String, String, String)> {
+    entry
+        .map(|e| {
+            e.edges
+                .iter()
+                .map(|edge| {
+                    let s = |k: &str| edge[k].as_str().unwrap_or("").to_string();
+                    (s("from"), s("to"), s("kind"))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn issues_json(issues: &BTreeSet<Issue>) -> Vec<Value> {
+    issues
+        .iter()
+        .map(|(severity, message)| json!({"severity": severity, "message": message}))
+        .collect()
+}
+
+impl WatchState {
+    pub fn new(
+        cfg: &Config,
+        cfg_path: &Option<PathBuf>,
+        root: &Path,
+        docs: Vec<AdrDoc>,
+    ) -> Result<Self> {
+        let mut state = WatchState {
+            filter: NoteFilter::new(cfg)?,
+            root: root.to_path_buf(),
+            docs: BTreeMap::new(),
+            hashes: BTreeMap::new(),
+            entries: BTreeMap::new(),
+            link_keys: BTreeMap::new(),
+            links: LinkResolver::new(&docs),
+            note_issues: BTreeMap::new(),
+            graph_issues: BTreeSet::new(),
+        };
+        for d in docs {
+            state.hashes.insert(d.file.clone(), content_hash(&d.file));
+            state.link_keys.insert(d.file.clone(), link_keys(&d.file));
+            state.docs.insert(d.file.clone(), d);
+        }
+        let paths: BTreeSet<PathBuf> = state.docs.keys().cloned().collect();
+        state.reindex(cfg, &paths);
+        let all: HashSet<PathBuf> = state.docs.keys().cloned().collect();
+        state.revalidate(cfg, cfg_path, &all);
+        Ok(state)
+    }
+
+    pub fn docs(&self) -> Vec<AdrDoc> {
+        self.docs.values().cloned().collect()
+    }
+
+    pub fn entries(&self) -> Vec<IndexEntry> {
+        self.entries.values().cloned().collect()
+    }
+
+    /// Current errors and warnings across all notes.
+    pub fn issues(&self) -> (Vec<String>, Vec<String>) {
+        let (mut errors, mut warnings) = (Vec::new(), Vec::new());
+        let all = self
+            .note_issues
+            .values()
+            .flatten()
+            .chain(&self.graph_issues);
+        for (severity, msg) in all {
+            match *severity {
+                "error" => errors.push(msg.clone()),
+                _ => warnings.push(msg.clone()),
+            }
+        }
+        (errors, warnings)
+    }
+
+    pub fn ok(&self) -> bool {
+        self.issues().0.is_empty()
+    }
+
+    /// Note keys affected by raw event paths: files, removed files and
+    /// directories, and notes inside created or renamed directories.
+    pub fn changed_notes(&self, paths: &BTreeSet<PathBuf>) -> BTreeSet<PathBuf> {
+        let mut out = BTreeSet::new();
+        for p in paths {
+            let Some(key) = self.filter.key(p) else {
+                continue;
+            };
+            if key.is_dir() {
+                for e in walkdir::WalkDir::new(&key)
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                {
+                    if self.filter.is_note(e.path()) {
+                        out.insert(e.path().to_path_buf());
+                    }
+                }
+            } else if key.exists() && (self.filter.is_note(&key) || self.docs.contains_key(&key)) {
+                out.insert(key.clone());
+            }
+            if !key.is_file() {
+                out.extend(self.docs.keys().filter(|d| d.starts_with(&key)).cloned());
+            }
+        }
+        out
+    }
+
+    /// Reparse `changed` notes, revalidate them and their graph neighbors, and
+    /// return the NDJSON events describing what changed. `revalidated` counts
+    /// the notes whose per-note checks ran.
+    pub fn apply(
+        &mut self,
+        cfg: &Config,
+        cfg_path: &Option<PathBuf>,
+        changed: &BTreeSet<PathBuf>,
+    ) -> (Vec<Value>, usize) {
+        let mut events = Vec::new();
+        let mut touched_ids: BTreeSet<String> = BTreeSet::new();
+        let mut scope: HashSet<PathBuf> = HashSet::new();
+        let mut reindex: BTreeSet<PathBuf> = BTreeSet::new();
+        for path in changed {
+            let old_doc = self.docs.get(path).cloned();
+            let exists = path.is_file() && self.filter.is_note(path);
+            let hash = if exists { content_hash(path) } else { 0 };
+            if exists && self.hashes.get(path) == Some(&hash) {
+                continue;
+            }
+            let new_doc = exists.then(|| {
+                let content = std::fs::read_to_string(path).unwrap_or_default();
+                parse_front_matter_and_title(&content, path)
+            });
+            let name = match (&old_doc, &new_doc) {
+                (None, Some(_)) => "note_added",
+                (Some(_), Some(_)) => "note_changed",
+                (Some(_), None) => "note_removed",
+                (None, None) => continue,
+            };
+            let doc = new_doc.as_ref().or(old_doc.as_ref()).cloned().unwrap();
+            let mut payload = json!({"path": doc.display_path(), "id": doc.id});
+            if new_doc.is_some() {
+                payload["title"] = json!(doc.title);
+            }
+            events.push(event(name, payload));
+            touched_ids.extend(old_doc.and_then(|d| d.id));
+            touched_ids.extend(new_doc.as_ref().and_then(|d| d.id.clone()));
+            if let Some(d) = new_doc {
+                self.hashes.insert(path.clone(), hash);
+                self.link_keys.insert(path.clone(), link_keys(path));
+                self.docs.insert(path.clone(), d);
+                scope.insert(path.clone());
+            } else {
+                self.hashes.remove(path);
+                self.link_keys.remove(path);
+                self.docs.remove(path);
+                self.note_issues.remove(path);
+            }
+            reindex.insert(path.clone());
+        }
+        if events.is_empty() {
+            return (events, 0);
+        }
+        // Links resolve by id, file name and title, so added, removed or
+        // renamed notes can retarget wikilinks in unchanged notes.
+        let links = LinkResolver::new(self.docs.values());
+        let retargeted = self.links.changed_keys(&links);
+        self.links = links;
+        for (path, keys) in &self.link_keys {
+            if !keys.is_disjoint(&retargeted) {
+                reindex.insert(path.clone());
+                scope.insert(path.clone());
+            }
+        }
+        for (from, to, kind, added) in self.reindex(cfg, &reindex) {
+            touched_ids.insert(to.clone());
+            let name = if added { "edge_added" } else { "edge_removed" };
+            events.push(event(name, json!({"from": from, "to": to, "kind": kind})));
+        }
+        // Neighbors: notes with those ids, and notes whose edges point at them.
+        for (path, doc) in &self.docs {
+            let is_target = doc.id.as_ref().is_some_and(|id| touched_ids.contains(id));
+            let links_in = self.entries.get(path).is_some_and(|e| {
+                e.edges.iter().any(|edge| {
+                    edge["to"]
+                        .as_str()
+                        .is_some_and(|to| touched_ids.contains(to))
+                })
+            });
+            if is_target || links_in {
+                scope.insert(path.clone());
+            }
+        }
+        let before = self.by_note();
+        self.revalidate(cfg, cfg_path, &scope);
+        let after = self.by_note();
+        let empty = BTreeSet::new();
+        let keys: BTreeSet<&Option<String>> = before.keys().chain(after.keys()).collect();
+        for key in keys {
+            let (old, new) = (
+                before.get(key).unwrap_or(&empty),
+                after.get(key).unwrap_or(&empty),
+            );
+            if old != new {
+                events.push(event(
+                    "diagnostics",
+                    json!({
+                        "path": key,
+                        "added": issues_json(&new.difference(old).cloned().collect()),
+                        "removed": issues_json(&old.difference(new).cloned().collect()),
+                    }),
+                ));
+            }
+        }
+        (events, scope.len())
+    }
+
+    /// Rebuild the index entries of `paths`, returning the edges removed and
+    /// added as `(from, to, kind, added)`.
+    fn reindex(
+        &mut self,
+        cfg: &Config,
+        paths: &BTreeSet<PathBuf>,
+    ) -> Vec<(String, String, String, bool)> {
+        let mut out = Vec::new();
+        for path in paths {
+            let old_entry = self.entries.remove(path);
+            let new_entry = self.docs.get(path).and_then(|d| {
+                index_entries(cfg, std::slice::from_ref(d), &self.root, &self.links).pop()
+            });
+            let (before, after) = (edge_keys(old_entry.as_ref()), edge_keys(new_entry.as_ref()));
+            for (from, to, kind) in before.difference(&after) {
+                out.push((from.clone(), to.clone(), kind.clone(), false));
+            }
+            for (from, to, kind) in after.difference(&before) {
+                out.push((from.clone(), to.clone(), kind.clone(), true));
+            }
+            if let Some(e) = new_entry {
+                self.entries.insert(path.clone(), e);
+            }
+        }
+        out
+    }
+
+    fn revalidate(&mut self, cfg: &Config, cfg_path: &Option<PathBuf>, scope: &HashSet<PathBuf>) {
+        let (docs, entries) = (self.docs(), self.entries());
+        let report = validate_scoped(cfg, cfg_path, &docs, &entries, scope);
+        let tag = |r: &crate::validate::ValidationReport| -> Vec<Issue> {
+            let errs = r.errors.iter().map(|m| ("error", m.clone()));
+            errs.chain(r.warnings.iter().map(|m| ("warning", m.clone())))
+                .collect()
+        };
+        for path in scope.iter().chain([&PathBuf::new()]) {
+            self.note_issues.remove(path);
+        }
+        for issue in tag(&report.notes) {
+            let owner = self
+                .docs
+                .iter()
+                .find(|(_, d)| issue.1.starts_with(&format!("{}: ", d.display_path())))
+                .map(|(p, _)| p.clone())
+                .unwrap_or_default();
+            self.note_issues.entry(owner).or_default().insert(issue);
+        }
+        self.graph_issues = tag(&report.graph).into_iter().collect();
+    }
+
+    /// All current issues keyed by the display path they are reported for.
+    fn by_note(&self) -> BTreeMap<Option<String>, BTreeSet<Issue>> {
+        let paths: Vec<String> = self.docs.values().map(|d| d.display_path()).collect();
+        let mut out: BTreeMap<Option<String>, BTreeSet<Issue>> = BTreeMap::new();
+        for issue in self
+            .note_issues
+            .values()
+            .flatten()
+            .chain(&self.graph_issues)
+        {
+            let owner = paths
+                .iter()
+                .find(|p| issue.1.starts_with(&format!("{}: ", p)))
+                .cloned();
+            out.entry(owner).or_default().insert(issue.clone());
+        }
+        out
+    }
+}
@@ -0,0 +1,241 @@
+//! Wikilink parsing and resolution shared by the index, graph traversal,
+//! validation and Lua: `[[target]]`, `[[target|alias]]`, `[[target#Heading]]`
+//! and `![[embeds]]`. Fenced code blocks, inline code and TOML front matter
+//! are skipped.
+
+use std::path::Path;
+
+use crate::backlinks::{BLOCK_END, BLOCK_START};
+
+mod resolver;
+
+pub use resolver::{link_keys, LinkResolver};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WikiLink {
+    /// Target as written, without anchor or alias; empty for `[[#Heading]]`.
+    pub target: String,
+    pub anchor: Option<String>,
+    pub alias: Option<String>,
+    pub embed: bool,
+    /// 1-based line number.
+    pub line: usize,
+}
+
+impl WikiLink {
+    /// `[[#Heading]]` points into the note containing it.
+    pub fn is_self(&self) -> bool {
+        self.target.is_empty()
+    }
+
+    /// Embeds of non-note files such as `![[diagram.png]]`.
+    pub fn is_attachment(&self) -> bool {
+        self.embed
+            && self.target.rsplit_once('.').is_some_and(|(_, ext)| {
+                (1..=5).contains(&ext.len())
+                    && ext.chars().all(|c| c.is_ascii_alphanumeric())
+                    && ext.chars().any(|c| c.is_ascii_alphabetic())
+                    && !ext.eq_ignore_ascii_case("md")
+            })
+    }
+}
+
+/// Every wikilink in `content`, in document order.
+pub fn parse_wikilinks(content: &str) -> Vec<WikiLink> {
+    let mut out = Vec::new();
+    for (line_no, line) in prose_lines(content) {
+        scan_line(&line, line_no, &mut out);
+    }
+    out
+}
+
+/// Wikilinks of the file at `path`; unreadable files have none.
+pub fn read_wikilinks(path: &Path) -> Vec<WikiLink> {
+    std::fs::read_to_string(path)
+        .map(|c| parse_wikilinks(&c))
+        .unwrap_or_default()
+}
+
+/// Lines that may hold links, with 1-based numbers: fenced code blocks, TOML
+/// front matter and generated backlinks blocks are dropped and inline code
+/// spans blanked out.
+pub(crate) fn prose_lines(content: &str) -> Vec<(usize, String)> {
+    let mut out = Vec::new();
+    let mut fence: Option<(char, usize)> = None;
+    let mut generated = false;
+    let mut toml_fm = content.starts_with("+++\n") || content.starts_with("+++\r\n");
+    for (i, line) in content.lines().enumerate() {
+        if toml_fm {
+            toml_fm = i == 0 || line.trim_end() != "+++";
+            continue;
+        }
+        let trimmed = line.trim_start();
+        if fence.is_none() && (generated || trimmed.trim_end() == BLOCK_START) {
+            generated = trimmed.trim_end() != BLOCK_END;
+            continue;
+        }
+        if track_fence(&mut fence, trimmed) {
+            continue;
+        }
+        if fence.is_none() {
+            out.push((i + 1, blank_inline_code(line)));
+        }
+    }
+    out
+}
+
+/// Open or close the code fence `fence` with `trimmed` (a line without its
+/// indent); true when the line is a fence line.
+pub(crate) fn track_fence(fence: &mut Option<(char, usize)>, trimmed: &str) -> bool {
+    let Some((c, n)) = fence_marker(trimmed) else {
+        return false;
+    };
+    match *fence {
+        None => *fence = Some((c, n)),
+        Some((open, len))
+            if open == c && n >= len && trimmed.trim_start_matches(c).trim().is_empty() =>
+        {
+            *fence = None
+        }
+        _ => {}
+    }
+    true
+}
+
+fn fence_marker(line: &str) -> Option<(char, usize)> {
+    let c = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let n = line.chars().take_while(|x| *x == c).count();
+    (n >= 3).then_some((c, n))
+}
+
+/// `line` with each closed inline code span replaced by spaces.
+fn blank_inline_code(line: &str) -> String {
+    let bytes = line.as_bytes();
+    let mut out = String::with_capacity(line.len());
+    let (mut i, mut copied) = (0, 0);
+    while i < bytes.len() {
+        if bytes[i] != b'`' {
+            i += 1;
+            continue;
+        }
+        let run = bytes[i..].iter().take_while(|b| **b == b'`').count();
+        match closing_backticks(&bytes[i + run..], run) {
+            Some(end) => {
+                let stop = i + run + end + run;
+                out.push_str(&line[copied..i]);
+                out.push_str(&" ".repeat(stop - i));
+                i = stop;
+                copied = stop;
+            }
+            None => i += run,
+        }
+    }
+    out.push_str(&line[copied..]);
+    out
+}
+
+fn scan_line(line: &str, line_no: usize, out: &mut Vec<WikiLink>) {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = i > 0 && bytes[i - 1] == b'\\';
+        if bytes[i..].starts_with(b"[[") && !escaped {
+            if let Some(len) = line[i + 2..].find("]]") {
+                let embed = i > 0 && bytes[i - 1] == b'!';
+                if let Some(link) = parse_inner(&line[i + 2..i + 2 + len], line_no, embed) {
+                    out.push(link);
+                    i += len + 4;
+                    continue;
+                }
+            }
+        }
+        i += 1;
+    }
+}
+
+fn closing_backticks(rest: &[u8], run: usize) -> Option<usize> {
+    let mut j = 0;
+    while j < rest.len() {
+        if rest[j] == b'`' {
+            let n = rest[j..].iter().take_while(|b| **b == b'`').count();
+            if n == run {
+                return Some(j);
+            }
+            j += n;
+        } else {
+            j += 1;
+        }
+    }
+    None
+}
+
+fn non_empty(s: &str) -> Option<String> {
+    Some(s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn parse_inner(inner: &str, line: usize, embed: bool) -> Option<WikiLink> {
+    if inner.contains('[') {
+        return None;
+    }
+    // `\|` separates the alias inside Markdown tables.
+    let (dest, alias) = match inner.split_once('|') {
+        Some((d, a)) => (d.strip_suffix('\\').unwrap_or(d), non_empty(a)),
+        None => (inner, None),
+    };
+    let (target, anchor) = match dest.split_once('#') {
+        Some((t, a)) => (t, non_empty(a)),
+        None => (dest, None),
+    };
+    let target = target.trim().to_string();
+    if target.is_empty() && anchor.is_none() {
+        return None;
+    }
+    Some(WikiLink {
+        target,
+        anchor,
+        alias,
+        embed,
+        line,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_alias_anchor_and_embed() {
+        let links =
+            parse_wikilinks("See [[ADR-010#Decision|the Lua escape hatch]] and ![[ADR-002]].");
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].target, "ADR-010");
+        assert_eq!(links[0].anchor.as_deref(), Some("Decision"));
+        assert_eq!(links[0].alias.as_deref(), Some("the Lua escape hatch"));
+        assert!(!links[0].embed);
+        assert!(links[1].embed);
+    }
+
+    #[test]
+    fn skips_code_and_escapes() {
+        let content = "a `[[ADR-001]]` b\n```\n[[ADR-002]]\n```\n\\[[ADR-003]] [[ADR-004]]\n";
+        let links = parse_wikilinks(content);
+        let targets: Vec<_> = links.iter().map(|l| (l.target.as_str(), l.line)).collect();
+        assert_eq!(targets, vec![("ADR-004", 5)]);
+    }
+
+    #[test]
+    fn table_pipe_and_self_anchor() {
+        let links = parse_wikilinks("| [[ADR-001\\|one]] | [[#Context]] |");
+        assert_eq!(links[0].target, "ADR-001");
+        assert_eq!(links[0].alias.as_deref(), Some("one"));
+        assert!(links[1].is_self());
+        assert_eq!(links[1].anchor.as_deref(), Some("Context"));
+    }
+
+    #[test]
+    fn attachments_are_not_notes() {
+        let links = parse_wikilinks("![[diagram.png]] ![[ADR-003d-v1.2-locked]]");
+        assert!(links[0].is_attachment());
+        assert!(!links[1].is_attachment());
+    }
+}
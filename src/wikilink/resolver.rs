@@ -0,0 +1,138 @@
+//! Resolution of wikilink targets and Markdown link paths to notes.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+use super::read_wikilinks;
+use crate::mdlink::{normalize_path, read_markdown_links, LinkTarget};
+use crate::model::AdrDoc;
+
+/// Resolver keys of every wikilink and Markdown file link in the note at
+/// `path`, to find notes whose links a changed resolver may retarget.
+pub fn link_keys(path: &Path) -> BTreeSet<String> {
+    let from = normalize_path(path);
+    let files = read_markdown_links(path)
+        .into_iter()
+        .filter_map(|l| match l.target(&from) {
+            LinkTarget::File { path, .. } => Some(LinkResolver::file_key(&path)),
+            _ => None,
+        });
+    read_wikilinks(path)
+        .iter()
+        .map(|l| LinkResolver::key(&l.target))
+        .chain(files)
+        .collect()
+}
+
+/// Maps link targets to notes, case-insensitively: by id first, then file
+/// name stem, then title or a front matter `aliases` entry. Targets may carry
+/// a directory prefix or `.md` extension.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinkResolver {
+    /// Lowercased key -> id (None for notes without one) and file of the note.
+    keys: HashMap<String, (Option<String>, PathBuf)>,
+    /// Normalized note file -> id, for Markdown links by path.
+    files: HashMap<PathBuf, Option<String>>,
+}
+
+/// Keys whose values differ between `a` and `b`, or that only one has.
+fn diff_keys<K: Clone + Eq + std::hash::Hash, V: PartialEq>(
+    a: &HashMap<K, V>,
+    b: &HashMap<K, V>,
+) -> Vec<K> {
+    let changed = a.iter().filter(|(k, v)| b.get(*k) != Some(*v));
+    let added = b.keys().filter(|k| !a.contains_key(*k));
+    changed.map(|(k, _)| k).chain(added).cloned().collect()
+}
+
+fn link_key(target: &str) -> String {
+    let name = target.trim().rsplit(['/', '\\']).next().unwrap_or("");
+    let lower = name.to_lowercase();
+    match lower.strip_suffix(".md") {
+        Some(stem) => stem.to_string(),
+        None => lower,
+    }
+}
+
+impl LinkResolver {
+    pub fn new<'a>(docs: impl IntoIterator<Item = &'a AdrDoc>) -> Self {
+        let mut docs: Vec<&AdrDoc> = docs.into_iter().collect();
+        docs.sort_by(|a, b| a.file.cmp(&b.file));
+        let mut keys: HashMap<String, (Option<String>, PathBuf)> = HashMap::new();
+        let note = |d: &AdrDoc| (d.id.clone(), d.file.clone());
+        for d in &docs {
+            if let Some(id) = &d.id {
+                keys.entry(id.to_lowercase()).or_insert_with(|| note(d));
+            }
+        }
+        for d in &docs {
+            if let Some(stem) = d.file.file_stem().and_then(|s| s.to_str()) {
+                keys.entry(stem.to_lowercase()).or_insert_with(|| note(d));
+            }
+        }
+        for d in &docs {
+            let aliases =
+                d.fm.get("aliases")
+                    .map(crate::frontmatter::string_list)
+                    .unwrap_or_default();
+            for name in std::iter::once(&d.title).chain(&aliases) {
+                let key = link_key(name);
+                if !key.is_empty() {
+                    keys.entry(key).or_insert_with(|| note(d));
+                }
+            }
+        }
+        let files = docs
+            .iter()
+            .map(|d| (normalize_path(&d.file), d.id.clone()))
+            .collect();
+        LinkResolver { keys, files }
+    }
+
+    /// Lookup key for `target`: its file name without `.md`, lowercased.
+    pub fn key(target: &str) -> String {
+        link_key(target)
+    }
+
+    /// Lookup key of a Markdown link to `path` (already normalized).
+    pub fn file_key(path: &Path) -> String {
+        path.to_string_lossy().into_owned()
+    }
+
+    /// Keys (see `key` and `file_key`) that resolve differently (or only) in
+    /// one of the two resolvers.
+    pub fn changed_keys(&self, other: &LinkResolver) -> BTreeSet<String> {
+        let files = diff_keys(&self.files, &other.files);
+        diff_keys(&self.keys, &other.keys)
+            .into_iter()
+            .chain(files.iter().map(|p| Self::file_key(p)))
+            .collect()
+    }
+
+    /// Whether the normalized `path` is a note file.
+    pub fn is_note_file(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+
+    /// Id of the note at the normalized `path`.
+    pub fn id_at(&self, path: &Path) -> Option<&str> {
+        self.files.get(path).and_then(|id| id.as_deref())
+    }
+
+    /// Id of the note `target` refers to.
+    pub fn resolve(&self, target: &str) -> Option<&str> {
+        self.keys
+            .get(&link_key(target))
+            .and_then(|(id, _)| id.as_deref())
+    }
+
+    /// File of the note `target` refers to, with or without an id.
+    pub fn resolve_path(&self, target: &str) -> Option<&Path> {
+        self.keys.get(&link_key(target)).map(|(_, p)| p.as_path())
+    }
+
+    /// Whether `target` names any note, with or without an id.
+    pub fn is_known(&self, target: &str) -> bool {
+        self.keys.contains_key(&link_key(target))
+    }
+}
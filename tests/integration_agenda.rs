@@ -0,0 +1,169 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::process::Command;
+
+fn day(n: i64) -> String {
+    (chrono::Local::now().date_naive() + chrono::Duration::days(n))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn fixture() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.create_dir_all().unwrap();
+    let body = format!(
+        "[@TODO:due={}:rank=low] Renew certs\n\
+         [@TODO:due={}:rank=urgent] Rotate keys\n\
+         [@TODO:scheduled={}:repeat=weekly] Weekly review\n\
+         [@TODO:due={}:done={}] Already done\n\
+         [@TODO:due={}] Far away\n\
+         [@TODO] No date\n",
+        day(-1),
+        day(-3),
+        day(0),
+        day(-2),
+        day(-2),
+        day(30)
+    );
+    base.child("ADR-001.md")
+        .write_str(&format!("---\nid: ADR-001\n---\n\n# Ops\n\n{}", body))
+        .unwrap();
+    base.child("ADR-002.md")
+        .write_str(&format!(
+            "---\nid: ADR-002\nkanban_status: doing\ndue_date: {}\nrank: high\n---\n\n# Ship v2\n",
+            day(2)
+        ))
+        .unwrap();
+    base.child("ADR-003.md")
+        .write_str(&format!(
+            "---\nid: ADR-003\nkanban_status: done\ndue_date: {}\n---\n\n# Finished\n",
+            day(1)
+        ))
+        .unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(&format!("bases = [\n  '{}'\n]\n", base.path().display()))
+        .unwrap();
+    temp
+}
+
+fn agenda(temp: &assert_fs::TempDir, args: &[&str]) -> Value {
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["--format", "json", "agenda"])
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&out).unwrap()
+}
+
+fn texts(group: &Value) -> Vec<&str> {
+    group["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["text"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn agenda_groups_by_day_with_overdue_priority_and_recurrence() {
+    let temp = fixture();
+    let v = agenda(&temp, &["--days", "8"]);
+    assert_eq!(v["today"], day(0));
+    let groups = v["groups"].as_array().unwrap();
+    let dates: Vec<&str> = groups.iter().map(|g| g["date"].as_str().unwrap()).collect();
+    assert_eq!(dates, vec![day(0), day(2), day(7)]);
+    // Today: overdue items ordered by priority, then the scheduled repeat.
+    assert_eq!(
+        texts(&groups[0]),
+        vec!["Rotate keys", "Renew certs", "Weekly review"]
+    );
+    let rotate = &groups[0]["items"][0];
+    assert_eq!(rotate["daysOverdue"], 3);
+    assert_eq!(rotate["type"], "deadline");
+    assert_eq!(rotate["priorityScore"], 10);
+    assert_eq!(rotate["date"], day(-3));
+    assert_eq!(rotate["line"], 8);
+    let weekly = &groups[0]["items"][2];
+    assert_eq!(weekly["type"], "scheduled");
+    assert_eq!(weekly["repeat"], "weekly");
+    // Kanban card on its due date; the done card is skipped.
+    assert_eq!(texts(&groups[1]), vec!["Ship v2"]);
+    assert_eq!(groups[1]["items"][0]["kind"], "kanban");
+    assert_eq!(groups[1]["items"][0]["kanbanStatus"], "doing");
+    assert_eq!(texts(&groups[2]), vec!["Weekly review"]);
+
+    let v = agenda(&temp, &["--overdue"]);
+    let groups = v["groups"].as_array().unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(texts(&groups[0]), vec!["Rotate keys", "Renew certs"]);
+}
+
+#[test]
+fn agenda_plain_output_marks_overdue_items() {
+    let temp = fixture();
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["agenda", "--days", "3"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "deadline -3d   P10 ADR-001    Rotate keys",
+        ))
+        .stdout(predicate::str::contains("Weekly review").count(1));
+}
+
+#[test]
+fn agenda_survives_bad_repeats_and_shows_old_daily_repeats() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.child("ADR-001.md")
+        .write_str(&format!(
+            "---\nid: ADR-001\n---\n\n# Ops\n\n\
+             [@TODO:due={}:repeat=5é] Odd unit\n\
+             [@TODO:due={}:repeat=999999999w] Huge step\n\
+             [@TODO:scheduled=1990-01-01:repeat=daily] Old habit\n",
+            day(1),
+            day(1)
+        ))
+        .unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(&format!("bases = [\n  '{}'\n]\n", base.path().display()))
+        .unwrap();
+    let v = agenda(&temp, &["--days", "3"]);
+    let groups = v["groups"].as_array().unwrap();
+    let dates: Vec<&str> = groups.iter().map(|g| g["date"].as_str().unwrap()).collect();
+    assert_eq!(dates, vec![day(0), day(1), day(2)]);
+    assert_eq!(
+        texts(&groups[1]),
+        vec!["Odd unit", "Huge step", "Old habit"]
+    );
+    assert_eq!(texts(&groups[2]), vec!["Old habit"]);
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["agenda", "--days", "4000000000"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--days 4000000000 reaches past the last supported date",
+        ));
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["search", "--format", "json", "--kind", "todo", "-q", ""])
+        .assert()
+        .success();
+}
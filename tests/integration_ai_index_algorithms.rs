@@ -0,0 +1,128 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::Value;
+use std::process::Command;
+
+/// ADR-001..004 form a 4-clique, ADR-005..007 a triangle, and a single
+/// mention bridges ADR-004 to ADR-005.
+fn corpus(schema_extra: &str) -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    let note = |n: u32, deps: &str, body: &str| {
+        base.child(format!("ADR-00{}.md", n))
+            .write_str(&format!(
+                "---\nid: ADR-00{}\ndepends_on: [{}]\n---\n\n# Note {}\n\n{}\n",
+                n, deps, n, body
+            ))
+            .unwrap();
+    };
+    note(1, "ADR-002, ADR-003, ADR-004", "");
+    note(2, "ADR-003, ADR-004", "");
+    note(3, "ADR-004", "");
+    note(4, "", "Bridges to [[ADR-005]].");
+    note(5, "ADR-006, ADR-007", "");
+    note(6, "ADR-007", "");
+    note(7, "", "");
+    temp.child(".cli-rag.toml")
+        .write_str(&format!(
+            "bases = [\n  '{}'\n]\n\n[[schema]]\nname = \"ADR\"\nfile_patterns = [\"ADR-*.md\"]\n{}",
+            base.path().display(),
+            schema_extra
+        ))
+        .unwrap();
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .env("CLI_RAG_NO_DAEMON", "1")
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["validate", "--format", "json"])
+        .assert()
+        .success();
+    temp
+}
+
+fn plan(temp: &assert_fs::TempDir, args: &[&str]) -> Value {
+    let out = temp.child("plan.json");
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["ai", "index", "plan", "--no-labels", "--output"])
+        .arg(out.path())
+        .args(args)
+        .assert()
+        .success();
+    serde_json::from_str(&std::fs::read_to_string(out.path()).unwrap()).unwrap()
+}
+
+fn members(plan: &Value) -> Vec<Vec<String>> {
+    plan["clusters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| {
+            c["members"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|m| m.as_str().unwrap().to_string())
+                .collect()
+        })
+        .collect()
+}
+
+fn ids(range: std::ops::RangeInclusive<u32>) -> Vec<String> {
+    range.map(|n| format!("ADR-00{}", n)).collect()
+}
+
+#[test]
+fn modularity_algorithms_split_the_bridged_component() {
+    let temp = corpus("");
+    let components = plan(&temp, &[]);
+    assert_eq!(components["params"]["algorithm"], "components");
+    assert_eq!(members(&components), vec![ids(1..=7)]);
+
+    for algorithm in ["louvain", "leiden"] {
+        let p = plan(&temp, &["--algorithm", algorithm]);
+        assert_eq!(p["params"]["algorithm"], algorithm);
+        assert_eq!(p["params"]["resolution"], 1.0);
+        assert_eq!(members(&p), vec![ids(1..=4), ids(5..=7)], "{}", algorithm);
+        assert!(p["clusters"][0].get("level").is_none());
+        // Stable ids: derived from members, identical across runs.
+        let again = plan(&temp, &["--algorithm", algorithm]);
+        assert_eq!(again["clusters"], p["clusters"]);
+    }
+    let louvain = plan(&temp, &["--algorithm", "louvain"]);
+    let id = louvain["clusters"][0]["clusterId"].as_str().unwrap();
+    assert!(id.starts_with("c_") && id.len() == 10, "{}", id);
+    assert_ne!(id, louvain["clusters"][1]["clusterId"].as_str().unwrap());
+
+    let lpa = plan(&temp, &["--algorithm", "label-propagation"]);
+    let covered: usize = members(&lpa).iter().map(|m| m.len()).sum();
+    assert_eq!(covered, 7);
+}
+
+#[test]
+fn kcore_hierarchy_nests_denser_cores_under_their_parent() {
+    let temp = corpus("");
+    let flat = plan(&temp, &["--algorithm", "kcore"]);
+    assert_eq!(members(&flat), vec![ids(1..=7)]);
+
+    let tree = plan(&temp, &["--algorithm", "kcore", "--hierarchical"]);
+    assert_eq!(tree["params"]["hierarchical"], true);
+    assert_eq!(members(&tree), vec![ids(1..=7), ids(1..=4)]);
+    let clusters = tree["clusters"].as_array().unwrap();
+    assert_eq!(clusters[0]["level"], 0);
+    assert_eq!(clusters[0]["parentId"], Value::Null);
+    assert_eq!(clusters[1]["level"], 1);
+    assert_eq!(clusters[1]["parentId"], clusters[0]["clusterId"]);
+
+    let core3 = plan(&temp, &["--algorithm", "kcore", "--min-core", "3"]);
+    assert_eq!(members(&core3), vec![ids(1..=4)]);
+}
+
+#[test]
+fn edge_kind_weights_come_from_schema_policy() {
+    let temp = corpus("[schema.validate.edges.mentions]\nweight = 0\n");
+    assert_eq!(members(&plan(&temp, &[])), vec![ids(1..=4), ids(5..=7)]);
+}
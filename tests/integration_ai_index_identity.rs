@@ -0,0 +1,237 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::{json, Value};
+use std::process::Command;
+
+fn note(temp: &assert_fs::TempDir, n: u32, deps: &str) {
+    temp.child(format!("notes/ADR-00{}.md", n))
+        .write_str(&format!(
+            "---\nid: ADR-00{}\ndepends_on: [{}]\n---\n\n# Note {}\n\nSession tokens and caching.\n",
+            n, deps, n
+        ))
+        .unwrap();
+}
+
+fn validate(temp: &assert_fs::TempDir) {
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .env("CLI_RAG_NO_DAEMON", "1")
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["validate", "--format", "json"])
+        .assert()
+        .success();
+}
+
+/// ADR-001..003 and ADR-005..007 form two chains.
+fn corpus() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(&format!(
+            "bases = [\n  '{}'\n]\n",
+            temp.child("notes").path().display()
+        ))
+        .unwrap();
+    note(&temp, 1, "ADR-002");
+    note(&temp, 2, "ADR-003");
+    note(&temp, 3, "");
+    note(&temp, 5, "ADR-006");
+    note(&temp, 6, "ADR-007");
+    note(&temp, 7, "");
+    validate(&temp);
+    temp
+}
+
+fn plan(temp: &assert_fs::TempDir) -> Value {
+    let out = temp.child("plan.json");
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["ai", "index", "plan", "--output"])
+        .arg(out.path())
+        .assert()
+        .success();
+    serde_json::from_str(&std::fs::read_to_string(out.path()).unwrap()).unwrap()
+}
+
+fn apply(temp: &assert_fs::TempDir, extra: &[&str]) -> Value {
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["ai", "index", "apply", "--from"])
+        .arg(temp.child("plan.json").path())
+        .args(extra)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&out).unwrap()
+}
+
+fn cache(temp: &assert_fs::TempDir) -> Value {
+    let path = temp.child(".cli-rag/cache/ai-index.json");
+    serde_json::from_str(&std::fs::read_to_string(path.path()).unwrap()).unwrap()
+}
+
+fn write_cache(temp: &assert_fs::TempDir, cache: &Value) {
+    temp.child(".cli-rag/cache/ai-index.json")
+        .write_str(&serde_json::to_string_pretty(cache).unwrap())
+        .unwrap();
+}
+
+fn cluster_with<'a>(v: &'a Value, member: &str) -> &'a Value {
+    v["clusters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["members"].as_array().unwrap().contains(&json!(member)))
+        .unwrap()
+}
+
+#[test]
+fn stable_cluster_keeps_id_and_curated_label() {
+    let temp = corpus();
+    let first = plan(&temp);
+    assert!(first["clusters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|c| c["status"] == "new"));
+    apply(&temp, &[]);
+    let first_id = cluster_with(&first, "ADR-001")["clusterId"].clone();
+
+    // Curate the label in the cache, then grow the cluster by one note.
+    let mut c = cache(&temp);
+    for entry in c["clusters"].as_array_mut().unwrap() {
+        assert_eq!(entry["labelSource"], "tfidf");
+        if entry["clusterId"] == first_id {
+            entry["label"] = json!("Session handling");
+            entry["labelSource"] = json!("manual");
+        }
+    }
+    write_cache(&temp, &c);
+    note(&temp, 4, "ADR-001");
+    validate(&temp);
+
+    let second = plan(&temp);
+    let grown = cluster_with(&second, "ADR-001");
+    assert_eq!(grown["status"], "stable");
+    assert_eq!(grown["clusterId"], first_id);
+    assert_eq!(grown["label"], "Session handling");
+    assert_eq!(grown["labelSource"], "manual");
+    assert_eq!(grown["previous"][0]["jaccard"], json!(0.75));
+    let other = cluster_with(&second, "ADR-005");
+    assert_eq!(other["status"], "stable");
+    assert_eq!(other["labelSource"], "tfidf");
+    assert_eq!(second["dissolved"], json!([]));
+
+    let report = apply(&temp, &[]);
+    assert_eq!(report["clustersRetained"], 0);
+    assert_eq!(report["clustersReplaced"], 2);
+    let c = cache(&temp);
+    assert_eq!(c["clusters"].as_array().unwrap().len(), 2);
+    let kept = cluster_with(&c, "ADR-004");
+    assert_eq!(kept["label"], "Session handling");
+    assert_eq!(kept["labelSource"], "manual");
+}
+
+#[test]
+fn merged_split_and_dissolved_clusters_are_reported() {
+    let temp = corpus();
+    plan(&temp);
+    apply(&temp, &[]);
+    let ids: Vec<Value> = cache(&temp)["clusters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["clusterId"].clone())
+        .collect();
+
+    // Bridge the two chains: one cluster overlapping both cached ones.
+    note(&temp, 3, "ADR-005");
+    validate(&temp);
+    let merged = plan(&temp);
+    let both = cluster_with(&merged, "ADR-001");
+    assert_eq!(both["status"], "merged");
+    assert_eq!(both["previous"].as_array().unwrap().len(), 2);
+    assert!(!ids.contains(&both["clusterId"]));
+    let report = apply(&temp, &[]);
+    assert_eq!(report["clustersReplaced"], 2);
+    assert_eq!(cache(&temp)["clusters"].as_array().unwrap().len(), 1);
+
+    // Break the bridge again: both halves split off the merged cluster.
+    note(&temp, 3, "");
+    validate(&temp);
+    let split = plan(&temp);
+    assert!(split["clusters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|c| c["status"] == "split"));
+    apply(&temp, &[]);
+
+    // Unlink ADR-005..007: their cluster falls below the minimum size.
+    note(&temp, 5, "");
+    note(&temp, 6, "");
+    validate(&temp);
+    let dissolved = plan(&temp);
+    assert_eq!(dissolved["clusters"].as_array().unwrap().len(), 1);
+    let gone = dissolved["dissolved"].as_array().unwrap();
+    assert_eq!(gone.len(), 1);
+    assert_eq!(gone[0]["members"], json!(["ADR-005", "ADR-006", "ADR-007"]));
+    apply(&temp, &[]);
+    assert_eq!(cache(&temp)["clusters"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn apply_merges_into_cache_unless_replace() {
+    let temp = corpus();
+    write_cache(
+        &temp,
+        &json!({"version": 1, "clusters": [{
+            "clusterId": "c_elsewhere",
+            "label": "Other notes",
+            "summary": "",
+            "members": ["IMP-001", "IMP-002"],
+            "tags": [],
+        }]}),
+    );
+    let p = plan(&temp);
+    assert_eq!(p["dissolved"], json!([]));
+
+    let report = apply(&temp, &["--dry-run"]);
+    assert_eq!(report["clustersRetained"], 1);
+    let report = apply(&temp, &[]);
+    assert_eq!(report["clustersRetained"], 1);
+    assert_eq!(report["clustersReplaced"], 0);
+    let c = cache(&temp);
+    assert_eq!(c["clusters"].as_array().unwrap().len(), 3);
+    assert_eq!(cluster_with(&c, "IMP-001")["label"], "Other notes");
+
+    let report = apply(&temp, &["--replace"]);
+    assert_eq!(report["clustersRetained"], 0);
+    assert_eq!(report["clustersReplaced"], 3);
+    assert_eq!(cache(&temp)["clusters"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn edited_plan_label_is_cached_as_manual() {
+    let temp = corpus();
+    let mut p = plan(&temp);
+    p["clusters"][0]["label"] = json!("Hand written");
+    temp.child("plan.json")
+        .write_str(&serde_json::to_string_pretty(&p).unwrap())
+        .unwrap();
+    apply(&temp, &[]);
+    let c = cache(&temp);
+    let sources: Vec<&str> = c["clusters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["labelSource"].as_str().unwrap())
+        .collect();
+    assert_eq!(sources, vec!["manual", "tfidf"]);
+}
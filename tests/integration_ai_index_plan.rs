@@ -137,3 +137,151 @@ fn ai_index_plan_aliases_removed() {
     assert!(!plan_path.path().exists());
     temp.close().unwrap();
 }
+
+fn labeled_corpus() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    let note = |id: &str, deps: &str, title: &str, body: &str| {
+        base.child(format!("{}.md", id))
+            .write_str(&format!(
+                "---\nid: {}\ndepends_on: [{}]\n---\n\n# {}\n\n{}\n",
+                id, deps, title, body
+            ))
+            .unwrap();
+    };
+    note(
+        "ADR-001",
+        "ADR-002",
+        "Cache invalidation",
+        "Cache entries expire; the cache keys hash inputs.",
+    );
+    note(
+        "ADR-002",
+        "",
+        "Cache storage",
+        "Store cache blobs on disk.\n\n```\ncache cache cache\n```",
+    );
+    note(
+        "ADR-003",
+        "ADR-004",
+        "Auth tokens",
+        "Tokens rotate daily; auth uses signed tokens.",
+    );
+    note("ADR-004", "", "Auth sessions", "Sessions wrap tokens.");
+    temp.child(".cli-rag.toml")
+        .write_str(&format!("bases = [\n  '{}'\n]\n", base.path().display()))
+        .unwrap();
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .env("CLI_RAG_NO_DAEMON", "1")
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["validate", "--format", "json"])
+        .assert()
+        .success();
+    temp
+}
+
+fn plan(temp: &assert_fs::TempDir, extra: &[&str]) -> serde_json::Value {
+    let out_path = temp.child("plan.json");
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["ai", "index", "plan", "--min-cluster-size", "2", "--output"])
+        .arg(out_path.path())
+        .args(extra)
+        .assert()
+        .success();
+    serde_json::from_str(&std::fs::read_to_string(out_path.path()).unwrap()).unwrap()
+}
+
+#[test]
+fn ai_index_plan_proposes_tfidf_labels_offline() {
+    let temp = labeled_corpus();
+    let p = plan(&temp, &[]);
+    assert_eq!(p["params"]["labels"], "tfidf");
+    let clusters = p["clusters"].as_array().unwrap();
+    assert_eq!(clusters.len(), 2);
+    let cache = &clusters[0];
+    assert_eq!(cache["labelSource"], "tfidf");
+    assert_eq!(cache["terms"][0]["term"], "cache");
+    assert!(cache["label"].as_str().unwrap().starts_with("cache, "));
+    assert_eq!(cache["tags"][0], "cache");
+    let summary = cache["summary"].as_str().unwrap();
+    assert!(summary.starts_with("2 notes about cache, "), "{}", summary);
+    assert!(
+        summary.contains("central: Cache invalidation (ADR-001)"),
+        "{}",
+        summary
+    );
+    let auth: Vec<&str> = clusters[1]["terms"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["term"].as_str().unwrap())
+        .collect();
+    assert!(
+        auth.contains(&"tokens") && !auth.contains(&"cache"),
+        "{:?}",
+        auth
+    );
+    // Deterministic: a second plan proposes the same labels.
+    let again = plan(&temp, &[]);
+    assert_eq!(again["clusters"], p["clusters"]);
+
+    let bare = plan(&temp, &["--no-labels"]);
+    assert_eq!(bare["params"]["labels"], "none");
+    assert_eq!(bare["clusters"][0]["label"], "");
+    assert_eq!(bare["clusters"][0]["labelSource"], "none");
+}
+
+#[cfg(unix)]
+#[test]
+fn ai_index_plan_label_command_refines_labels() {
+    let temp = labeled_corpus();
+    temp.child("label.sh")
+        .write_str(concat!(
+            "input=$(cat)\n",
+            "case \"$input\" in *'\"terms\"'*'\"cache\"'*) ;; *) exit 3 ;; esac\n",
+            "printf '{\"clusters\": [{\"clusterId\": \"%s\", \"label\": \"Caching\", \"tags\": [\"caching\"]}]}' \"$1\"\n",
+        ))
+        .unwrap();
+    let first = plan(&temp, &[])["clusters"][0]["clusterId"].clone();
+    let cmd = format!(
+        "sh {} {}",
+        temp.child("label.sh").path().display(),
+        first.as_str().unwrap()
+    );
+    let p = plan(&temp, &["--label-command", &cmd]);
+    let c0 = &p["clusters"][0];
+    assert_eq!(c0["label"], "Caching");
+    assert_eq!(c0["tags"], serde_json::json!(["caching"]));
+    assert_eq!(c0["labelSource"], "command");
+    assert!(c0["summary"]
+        .as_str()
+        .unwrap()
+        .starts_with("2 notes about cache"));
+    assert_eq!(p["clusters"][1]["labelSource"], "tfidf");
+    assert!(p["sourceIndexHash"]
+        .as_str()
+        .unwrap()
+        .starts_with("sha256:"));
+
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["ai", "index", "plan", "--output"])
+        .arg(temp.child("bad.json").path())
+        .args([
+            "--label-command",
+            "echo '{\"clusters\": [{\"clusterId\": \"c_9999\"}]}'",
+        ])
+        .assert()
+        .failure()
+        .stderr(contains(
+            "label command returned unknown clusterId 'c_9999'",
+        ));
+    assert!(!temp.child("bad.json").path().exists());
+}
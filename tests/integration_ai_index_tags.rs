@@ -0,0 +1,185 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::{json, Value};
+use std::process::Command;
+
+fn cli(temp: &assert_fs::TempDir, args: &[&str]) -> Value {
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .env("CLI_RAG_NO_DAEMON", "1")
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    serde_json::from_slice(&out).unwrap_or(Value::Null)
+}
+
+/// Plan with a single tag per cluster so the front matter is predictable.
+fn plan_with_tag(temp: &assert_fs::TempDir, tag: &str) -> Value {
+    let plan = temp.child("plan.json");
+    cli(temp, &["validate", "--format", "json"]);
+    let output = plan.path().to_str().unwrap();
+    cli(
+        temp,
+        &[
+            "ai",
+            "index",
+            "plan",
+            "--min-cluster-size",
+            "2",
+            "--output",
+            output,
+        ],
+    );
+    let mut p: Value =
+        serde_json::from_str(&std::fs::read_to_string(plan.path()).unwrap()).unwrap();
+    for c in p["clusters"].as_array_mut().unwrap() {
+        c["tags"] = json!([tag]);
+    }
+    plan.write_str(&serde_json::to_string_pretty(&p).unwrap())
+        .unwrap();
+    p
+}
+
+fn apply(temp: &assert_fs::TempDir, extra: &[&str]) -> Value {
+    let plan = temp.child("plan.json");
+    let mut args = vec![
+        "ai",
+        "index",
+        "apply",
+        "--from",
+        plan.path().to_str().unwrap(),
+        "--write-frontmatter",
+    ];
+    args.extend_from_slice(extra);
+    cli(temp, &args)
+}
+
+fn read(temp: &assert_fs::TempDir, name: &str) -> String {
+    std::fs::read_to_string(temp.child("notes").child(name).path()).unwrap()
+}
+
+#[test]
+fn cluster_tags_are_added_removed_and_keep_flavor() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let notes = temp.child("notes");
+    temp.child(".cli-rag.toml")
+        .write_str(&format!("bases = [\n  '{}'\n]\n", notes.path().display()))
+        .unwrap();
+    // No tags key yet.
+    notes
+        .child("ADR-001.md")
+        .write_str("---\nid: ADR-001\nstatus: draft\n---\n\n# One\n")
+        .unwrap();
+    // TOML front matter with a hand-written tag.
+    notes
+        .child("ADR-002.md")
+        .write_str(
+            "+++\nid = \"ADR-002\"\ntags = [\"keep\"]\ndepends_on = [\"ADR-001\"]\n+++\n\n# Two\n",
+        )
+        .unwrap();
+    // Left its cluster earlier; the stale cluster tag must go.
+    notes
+        .child("ADR-003.md")
+        .write_str("---\nid: ADR-003\ntags: [manual, cluster/stale]\n---\n\n# Three\n")
+        .unwrap();
+    plan_with_tag(&temp, "Auth Flow");
+
+    let report = apply(&temp, &["--dry-run"]);
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 3);
+    assert_eq!(files[0]["id"], "ADR-001");
+    assert_eq!(files[0]["added"], json!(["cluster/auth-flow"]));
+    assert!(files[0]["diff"]
+        .as_str()
+        .unwrap()
+        .contains("+tags:\n+- cluster/auth-flow"));
+    assert_eq!(files[2]["removed"], json!(["cluster/stale"]));
+    assert_eq!(report["membersTagged"], 3);
+    assert!(!read(&temp, "ADR-001.md").contains("cluster/"));
+
+    let report = apply(&temp, &[]);
+    assert!(report["files"][0].get("diff").is_none());
+    assert_eq!(
+        read(&temp, "ADR-001.md"),
+        "---\nid: ADR-001\nstatus: draft\ntags:\n- cluster/auth-flow\n---\n\n# One\n"
+    );
+    let two = read(&temp, "ADR-002.md");
+    assert!(two.starts_with("+++\nid = \"ADR-002\"\n"));
+    assert!(two.contains("tags = [\"keep\", \"cluster/auth-flow\"]"));
+    assert!(read(&temp, "ADR-003.md").contains("tags: [manual]\n---"));
+
+    // Applying again changes nothing.
+    assert_eq!(apply(&temp, &[])["files"], json!([]));
+
+    // ADR-002 leaves the cluster: the cluster dissolves and its tags go.
+    notes
+        .child("ADR-002.md")
+        .write_str(&two.replace("depends_on = [\"ADR-001\"]\n", ""))
+        .unwrap();
+    let p = plan_with_tag(&temp, "Auth Flow");
+    assert_eq!(p["dissolved"].as_array().unwrap().len(), 1);
+    let report = apply(&temp, &[]);
+    assert_eq!(report["membersTagged"], 2);
+    assert!(!read(&temp, "ADR-001.md").contains("cluster/"));
+    assert!(read(&temp, "ADR-002.md").contains("tags = [\"keep\"]"));
+}
+
+#[test]
+fn unusable_tags_are_reported_not_fatal() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let notes = temp.child("notes");
+    temp.child(".cli-rag.toml")
+        .write_str(&format!("bases = [\n  '{}'\n]\n", notes.path().display()))
+        .unwrap();
+    notes
+        .child("ADR-001.md")
+        .write_str("---\nid: ADR-001\n---\n\n# One\n")
+        .unwrap();
+    notes
+        .child("ADR-002.md")
+        .write_str("---\r\nid: ADR-002\r\ndepends_on: [ADR-001]\r\n---\r\n\r\n# Two\r\n")
+        .unwrap();
+    plan_with_tag(&temp, "auth");
+    // Edited after planning into a shape apply cannot merge into.
+    notes
+        .child("ADR-001.md")
+        .write_str("---\nid: ADR-001\ntags:\n  area: auth\n---\n\n# One\n")
+        .unwrap();
+
+    let report = apply(&temp, &[]);
+    let warnings = report["warnings"].as_array().unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].as_str().unwrap().contains("tags is not a list"));
+    assert_eq!(report["membersTagged"], 1);
+    assert!(read(&temp, "ADR-001.md").contains("area: auth"));
+    assert!(read(&temp, "ADR-002.md").contains("tags:\r\n- cluster/auth\r\n---\r\n"));
+}
+
+#[test]
+fn only_the_tags_entry_is_rewritten() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let notes = temp.child("notes");
+    temp.child(".cli-rag.toml")
+        .write_str(&format!("bases = [\n  '{}'\n]\n", notes.path().display()))
+        .unwrap();
+    let yaml = "---\n# Owned by the platform team\nid: ADR-001   # keep this id\ntags: [a, b]\nstatus: \"draft\"\n---\n\n# One\n";
+    let toml = "+++\nid = \"ADR-002\"\ncreated = 2025-01-01\ndepends_on = [\"ADR-001\"] # parent\n+++\n\n# Two\n";
+    notes.child("ADR-001.md").write_str(yaml).unwrap();
+    notes.child("ADR-002.md").write_str(toml).unwrap();
+    plan_with_tag(&temp, "auth");
+
+    apply(&temp, &[]);
+    assert_eq!(
+        read(&temp, "ADR-001.md"),
+        yaml.replace("tags: [a, b]", "tags: [a, b, cluster/auth]")
+    );
+    assert_eq!(
+        read(&temp, "ADR-002.md"),
+        toml.replace("# parent\n", "# parent\ntags = [\"cluster/auth\"]\n")
+    );
+}
@@ -0,0 +1,179 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::process::Command;
+
+fn fixture() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let adr = temp.child("notes/adr");
+    adr.child("ADR-001.md")
+        .write_str("---\nid: ADR-001\n---\n\n# Storage\n\nUse SQLite.\n")
+        .unwrap();
+    adr.child("ADR-002.md")
+        .write_str(concat!(
+            "---\nid: ADR-002\ndepends_on: [ADR-001]\nsupersedes: [ADR-003]\n---\n\n",
+            "# Caching\n\nBuilds on [[ADR-001]].\n",
+        ))
+        .unwrap();
+    adr.child("ADR-003.md")
+        .write_str("---\nid: ADR-003\n---\n\n# Old caching\n")
+        .unwrap();
+    temp.child("notes/imp/IMP-001.md")
+        .write_str("---\nid: IMP-001\n---\n\n# Rollout\n\nSee [storage](../adr/ADR-001.md).\n")
+        .unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(concat!(
+            "bases = [\"notes\"]\n\n",
+            "[[schema]]\nname = \"ADR\"\nfile_patterns = [\"ADR-*.md\"]\nunknown_policy = \"ignore\"\n",
+            "[schema.backlinks]\nenabled = true\n\n",
+            "[[schema]]\nname = \"IMP\"\nfile_patterns = [\"IMP-*.md\"]\nunknown_policy = \"ignore\"\n",
+        ))
+        .unwrap();
+    temp
+}
+
+fn cli(temp: &assert_fs::TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("cli-rag").unwrap();
+    cmd.env("CLI_RAG_NO_DAEMON", "1")
+        .current_dir(temp.path())
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path());
+    cmd
+}
+
+fn stale(temp: &assert_fs::TempDir) -> Vec<String> {
+    let out = cli(temp)
+        .args(["validate", "--format", "json"])
+        .output()
+        .unwrap();
+    let report: Value = serde_json::from_slice(&out.stdout).unwrap();
+    let mut msgs: Vec<String> = report["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|d| d["code"] == "W236")
+        .map(|d| d["msg"].as_str().unwrap().to_string())
+        .collect();
+    msgs.sort();
+    msgs
+}
+
+#[test]
+fn sync_writes_grouped_blocks_idempotently() {
+    let temp = fixture();
+    let before = stale(&temp);
+    assert_eq!(before.len(), 2, "{:?}", before);
+    assert!(
+        before[0].contains("ADR-001.md: backlinks block is out of date"),
+        "{:?}",
+        before
+    );
+
+    cli(&temp)
+        .args(["backlinks", "sync", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+Dependents:"))
+        .stdout(predicate::str::contains("Would update 2 of 3 note(s)"));
+    assert!(
+        !std::fs::read_to_string(temp.child("notes/adr/ADR-001.md").path())
+            .unwrap()
+            .contains("cli-rag:backlinks")
+    );
+
+    cli(&temp).args(["backlinks", "sync"]).assert().success();
+    let one = std::fs::read_to_string(temp.child("notes/adr/ADR-001.md").path()).unwrap();
+    assert_eq!(
+        one,
+        concat!(
+            "---\nid: ADR-001\n---\n\n# Storage\n\nUse SQLite.\n\n",
+            "<!-- cli-rag:backlinks -->\n**Referenced by**\n\n",
+            "Dependents:\n- [ADR-002](ADR-002.md): Caching\n\n",
+            "Mentioned by:\n- [ADR-002](ADR-002.md): Caching\n\n",
+            "Linked from:\n- [IMP-001](../imp/IMP-001.md): Rollout\n",
+            "<!-- /cli-rag:backlinks -->\n",
+        )
+    );
+    let three = std::fs::read_to_string(temp.child("notes/adr/ADR-003.md").path()).unwrap();
+    assert!(three.contains("Superseded by:\n- [ADR-002](ADR-002.md): Caching\n"));
+    assert!(stale(&temp).is_empty());
+
+    // Generated links do not become edges, so a second sync changes nothing.
+    let out = cli(&temp)
+        .args(["--format", "json", "backlinks", "sync"])
+        .output()
+        .unwrap();
+    let v: Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v["notes"], 3);
+    assert_eq!(v["updated"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn validate_flags_blocks_left_behind_by_graph_changes() {
+    let temp = fixture();
+    cli(&temp).args(["backlinks", "sync"]).assert().success();
+    temp.child("notes/adr/ADR-002.md")
+        .write_str("---\nid: ADR-002\n---\n\n# Caching\n")
+        .unwrap();
+    temp.child("notes/imp/IMP-001.md")
+        .write_str("---\nid: IMP-001\n---\n\n# Rollout\n")
+        .unwrap();
+    let msgs = stale(&temp);
+    assert_eq!(msgs.len(), 2, "{:?}", msgs);
+
+    cli(&temp).args(["backlinks", "sync"]).assert().success();
+    let one = std::fs::read_to_string(temp.child("notes/adr/ADR-001.md").path()).unwrap();
+    assert_eq!(one, "---\nid: ADR-001\n---\n\n# Storage\n\nUse SQLite.\n");
+    assert!(stale(&temp).is_empty());
+}
+
+#[test]
+fn unterminated_blocks_are_refused_and_fenced_markers_ignored() {
+    let temp = fixture();
+    // The end marker was deleted by hand: syncing must not eat the prose below.
+    let broken = concat!(
+        "---\nid: ADR-001\n---\n\n# Storage\n\n<!-- cli-rag:backlinks -->\n",
+        "**Referenced by**\n\nUse SQLite.\n\n## Notes\n\nKeep this.\n",
+    );
+    temp.child("notes/adr/ADR-001.md")
+        .write_str(broken)
+        .unwrap();
+    // A marker shown in a code sample is text, not a block.
+    let sample = concat!(
+        "---\nid: ADR-003\n---\n\n# Old caching\n\n```md\n",
+        "<!-- cli-rag:backlinks -->\n```\n\nTail.\n",
+    );
+    temp.child("notes/adr/ADR-003.md")
+        .write_str(sample)
+        .unwrap();
+
+    let msgs = stale(&temp);
+    assert!(
+        msgs.iter()
+            .any(|m| m.contains("ADR-001.md: backlinks block starting on line 7 has no")),
+        "{:?}",
+        msgs
+    );
+    let out = cli(&temp)
+        .args(["--format", "json", "backlinks", "sync"])
+        .output()
+        .unwrap();
+    let v: Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(v["warnings"].as_array().unwrap().len(), 1);
+    let one = std::fs::read_to_string(temp.child("notes/adr/ADR-001.md").path()).unwrap();
+    assert_eq!(one, broken);
+    let three = std::fs::read_to_string(temp.child("notes/adr/ADR-003.md").path()).unwrap();
+    assert!(three.starts_with(sample), "{}", three);
+    assert!(three.ends_with(
+        "Superseded by:\n- [ADR-002](ADR-002.md): Caching\n<!-- /cli-rag:backlinks -->\n"
+    ));
+
+    cli(&temp)
+        .args(["backlinks", "sync"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Warning: notes/adr/ADR-001.md: backlinks block",
+        ));
+}
@@ -0,0 +1,137 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use std::process::Command;
+
+fn entry<'a>(prov: &'a [serde_json::Value], key: &str) -> &'a serde_json::Value {
+    prov.iter()
+        .find(|e| e["key"] == key)
+        .unwrap_or_else(|| panic!("missing provenance for {}", key))
+}
+
+fn setup() -> (assert_fs::TempDir, assert_fs::fixture::ChildPath) {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("notes").create_dir_all().unwrap();
+    temp.child("alt").create_dir_all().unwrap();
+    temp.child(".cli-rag/templates/ADR.toml")
+        .write_str(
+            "[[schema]]\nname = \"ADR\"\nfile_patterns = [\"ADR-*.md\"]\nrequired = [\"id\"]\n",
+        )
+        .unwrap();
+    let cfg = temp.child(".cli-rag.toml");
+    cfg.write_str(
+        "[config]\nconfig_version = \"0.1\"\n\n[config.scan]\nfilepaths = [\"notes\"]\nindex_path = \"idx/index.json\"\n\n[config.graph]\ndepth = 3\n\n[config.templates]\nimport = [\".cli-rag/templates/ADR.toml\"]\n",
+    )
+    .unwrap();
+    (temp, cfg)
+}
+
+#[test]
+fn explain_json_reports_file_import_and_default_layers() {
+    let (_temp, cfg) = setup();
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .env("CLI_RAG_NO_LUA", "1")
+        .arg("--config")
+        .arg(cfg.path())
+        .arg("config")
+        .arg("explain")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(v["graph"]["depth"], 3);
+    let prov = v["provenance"].as_array().unwrap();
+
+    let depth = entry(prov, "graph.depth");
+    assert_eq!(depth["layer"], "file");
+    assert_eq!(depth["line"], 9);
+    assert!(depth["source"].as_str().unwrap().ends_with(".cli-rag.toml"));
+
+    let index = entry(prov, "scan.indexPath");
+    assert_eq!(index["value"], "idx/index.json");
+    assert_eq!(index["line"], 6);
+
+    let schema = entry(prov, "schemas.ADR.filePatterns");
+    assert_eq!(schema["layer"], "import");
+    assert!(schema["source"].as_str().unwrap().ends_with("ADR.toml"));
+    assert_eq!(schema["line"], 3);
+
+    assert_eq!(
+        entry(prov, "graph.includeBidirectional")["layer"],
+        "default"
+    );
+    assert_eq!(entry(prov, "overlays.enabled")["layer"], "env");
+}
+
+#[test]
+fn explain_attributes_env_and_cli_overrides() {
+    let (temp, cfg) = setup();
+    let run = |cmd: &mut Command| -> serde_json::Value {
+        let out = cmd
+            .arg("--config")
+            .arg(cfg.path())
+            .arg("config")
+            .arg("explain")
+            .arg("scan.filepaths")
+            .arg("--format")
+            .arg("ndjson")
+            .assert()
+            .success()
+            .get_output()
+            .stdout
+            .clone();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 1, "expected one entry, got {}", text);
+        serde_json::from_str(lines[0]).unwrap()
+    };
+
+    let mut cmd = Command::cargo_bin("cli-rag").unwrap();
+    cmd.env("CLI_RAG_FILEPATHS", temp.child("alt").path());
+    let e = run(&mut cmd);
+    assert_eq!(e["layer"], "env");
+    assert_eq!(e["source"], "CLI_RAG_FILEPATHS");
+
+    let mut cmd = Command::cargo_bin("cli-rag").unwrap();
+    cmd.env("CLI_RAG_FILEPATHS", temp.child("alt").path())
+        .arg("--base")
+        .arg(temp.child("notes").path());
+    let e = run(&mut cmd);
+    assert_eq!(e["layer"], "cli");
+    assert_eq!(e["source"], "--base");
+}
+
+#[test]
+fn explain_plain_filters_by_prefix_and_rejects_unknown_keys() {
+    let (_temp, cfg) = setup();
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(cfg.path())
+        .arg("config")
+        .arg("explain")
+        .arg("graph")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.contains("graph.depth = 3  (file: "), "{}", text);
+    assert!(text.contains("graph.includeBidirectional = true  (default)"));
+    assert!(!text.contains("scan.filepaths"));
+
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(cfg.path())
+        .arg("config")
+        .arg("explain")
+        .arg("graph.nope")
+        .assert()
+        .failure();
+}
@@ -0,0 +1,249 @@
+//! The config JSON Schema is written by hand; this keeps it from drifting
+//! away from the serde structs. A recording deserializer walks `Config`
+//! (and every struct it nests, including `SchemaCfg`) and notes each field
+//! serde asks for; every one of them must be a property in the schema.
+//! Structs behind `#[serde(flatten)]` are read through serde's buffered
+//! content instead, so their fields are not seen here.
+
+use cli_rag::config::json_schema::config_json_schema;
+use cli_rag::config::Config;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use serde_json::Value;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+/// Field paths seen so far; `*` stands for a map key and `[]` for a list item.
+/// `skip` holds aliases, which must not be fed next to the field they name.
+#[derive(Default)]
+struct State {
+    seen: Vec<Vec<String>>,
+    skip: BTreeSet<Vec<String>>,
+    last_key: Vec<String>,
+}
+
+type Shared = Rc<RefCell<State>>;
+
+#[derive(Debug)]
+struct Error(String);
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// Produces one sample value at `path`: structs get every field, maps one
+/// `*` entry, lists one item and scalars a zero value.
+struct Recorder {
+    path: Vec<String>,
+    state: Shared,
+}
+
+impl Recorder {
+    fn child(&self, segment: &str) -> Recorder {
+        let mut path = self.path.clone();
+        path.push(segment.to_string());
+        Recorder {
+            path,
+            state: Rc::clone(&self.state),
+        }
+    }
+}
+
+/// Keys and values of a sample map or struct.
+struct Entries {
+    parent: Recorder,
+    keys: Vec<&'static str>,
+    next: usize,
+}
+
+impl<'de> MapAccess<'de> for Entries {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let Some(key) = self.keys.get(self.next) else {
+            return Ok(None);
+        };
+        self.parent.state.borrow_mut().last_key = self.parent.child(key).path;
+        seed.deserialize((*key).into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        let key = self.keys[self.next];
+        self.next += 1;
+        seed.deserialize(self.parent.child(key))
+    }
+}
+
+/// A one-item sample list.
+struct Items(Option<Recorder>);
+
+impl<'de> SeqAccess<'de> for Items {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.0.take().map(|r| seed.deserialize(r)).transpose()
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Recorder {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(Entries {
+            parent: self,
+            keys: Vec::new(),
+            next: 0,
+        })
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_str("")
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_str("")
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_bool(false)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u64(0)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_i64(0)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_f64(0.0)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_seq(Items(Some(self.child("[]"))))
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_map(Entries {
+            parent: self,
+            keys: vec!["*"],
+            next: 0,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let mut keys = Vec::new();
+        let mut state = self.state.borrow_mut();
+        for field in fields {
+            let path = self.child(field).path;
+            if !state.skip.contains(&path) {
+                keys.push(*field);
+            }
+            state.seen.push(path);
+        }
+        drop(state);
+        visitor.visit_map(Entries {
+            parent: self,
+            keys,
+            next: 0,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        i8 i16 i32 u8 u16 u32 f32 char bytes byte_buf unit
+        unit_struct newtype_struct tuple tuple_struct enum identifier ignored_any
+    }
+}
+
+/// The subschema at `path`, following `$ref`s, `properties`, `items`,
+/// `additionalProperties` and the alternatives of a `oneOf`.
+fn resolve<'a>(root: &'a Value, mut node: &'a Value, path: &[String]) -> Option<&'a Value> {
+    if let Some(name) = node["$ref"]
+        .as_str()
+        .and_then(|r| r.strip_prefix("#/definitions/"))
+    {
+        node = &root["definitions"][name];
+    }
+    let Some((segment, rest)) = path.split_first() else {
+        return Some(node);
+    };
+    if let Some(options) = node["oneOf"].as_array() {
+        return options.iter().find_map(|o| resolve(root, o, path));
+    }
+    let next = match segment.as_str() {
+        "[]" => node.get("items")?,
+        "*" => node.get("additionalProperties").filter(|v| v.is_object())?,
+        key => node["properties"].get(key)?,
+    };
+    resolve(root, next, rest)
+}
+
+#[test]
+fn every_config_field_is_in_the_config_json_schema() {
+    let state: Shared = Rc::default();
+    loop {
+        let recorder = Recorder {
+            path: Vec::new(),
+            state: Rc::clone(&state),
+        };
+        match Config::deserialize(recorder) {
+            Ok(_) => break,
+            // An alias clashed with its field: record it, but stop feeding it.
+            Err(e) if e.0.starts_with("duplicate field") => {
+                let mut s = state.borrow_mut();
+                let alias = std::mem::take(&mut s.last_key);
+                assert!(s.skip.insert(alias), "{}", e);
+                s.seen.clear();
+            }
+            Err(e) => panic!("sample config: {}", e),
+        }
+    }
+    let schema = config_json_schema();
+    let paths = &state.borrow().seen;
+    let joined: Vec<String> = paths.iter().map(|p| p.join(".")).collect();
+    for field in [
+        "schema.[].name",
+        "schema.[].validate.staleness.after_days",
+        "view.[].name",
+    ] {
+        assert!(joined.iter().any(|p| p == field), "{:?}", joined);
+    }
+    let missing: Vec<&String> = paths
+        .iter()
+        .zip(&joined)
+        .filter(|(p, _)| resolve(&schema, &schema, p).is_none())
+        .map(|(_, j)| j)
+        .collect();
+    assert!(
+        missing.is_empty(),
+        "fields missing from the schema: {:?}",
+        missing
+    );
+}
@@ -0,0 +1,145 @@
+#![cfg(unix)]
+
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+fn cli(temp: &assert_fs::TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("cli-rag").unwrap();
+    cmd.arg("--config").arg(temp.child(".cli-rag.toml").path());
+    cmd
+}
+
+fn json_out(cmd: &mut Command) -> (Value, String) {
+    let out = cmd.assert().success().get_output().clone();
+    (
+        serde_json::from_slice(&out.stdout).unwrap(),
+        String::from_utf8_lossy(&out.stderr).to_string(),
+    )
+}
+
+/// Spawn `cli-rag daemon` and wait for its socket.
+fn start_daemon(temp: &assert_fs::TempDir) -> Child {
+    let daemon = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .arg("daemon")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let socket = temp.child(".cli-rag/daemon.sock");
+    for _ in 0..100 {
+        if socket.path().exists() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    daemon
+}
+
+fn project() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.create_dir_all().unwrap();
+    base.child("ADR-001.md")
+        .write_str("---\nid: ADR-001\ntags: [api]\nstatus: draft\n---\n\n# Rate limiting\n")
+        .unwrap();
+    base.child("ADR-002.md")
+        .write_str(
+            "---\nid: ADR-002\ntags: []\nstatus: draft\ndepends_on: [ADR-001]\n---\n\n# Quotas\n",
+        )
+        .unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(&format!("bases = [\n  '{}'\n]\n", base.path().display()))
+        .unwrap();
+    temp
+}
+
+#[test]
+fn daemon_serves_search_and_get_with_fallback() {
+    let temp = project();
+    let search = ["search", "--format", "json", "-q", "rate"];
+    let get = ["get", "--id", "ADR-002", "--format", "json"];
+    let (local_search, _) = json_out(cli(&temp).env("CLI_RAG_NO_DAEMON", "1").args(search));
+    let (local_get, _) = json_out(cli(&temp).env("CLI_RAG_NO_DAEMON", "1").args(get));
+
+    let mut daemon = start_daemon(&temp);
+    let socket = temp.child(".cli-rag/daemon.sock");
+    let stream = UnixStream::connect(socket.path()).expect("daemon socket");
+    writeln!(
+        &stream,
+        r#"{{"method": "ping", "config": null, "bases": []}}"#
+    )
+    .unwrap();
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line).unwrap();
+    let reply: Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(reply["code"], "config_mismatch");
+
+    // Without an index, only the local path reports a fallback scan.
+    std::fs::remove_file(temp.child("index/adr-index.json").path()).unwrap();
+    let (served, stderr) = json_out(cli(&temp).args(search));
+    assert_eq!(served, local_search);
+    assert!(!stderr.contains("unified index not found"), "{}", stderr);
+    let (served, _) = json_out(cli(&temp).args(get));
+    assert_eq!(served, local_get);
+    cli(&temp)
+        .args(["get", "--id", "ADR-404", "--format", "json"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("ADR not found: ADR-404"));
+
+    let (_, stderr) = json_out(cli(&temp).env("CLI_RAG_NO_DAEMON", "1").args(search));
+    assert!(stderr.contains("unified index not found"));
+    cli(&temp)
+        .arg("daemon")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("a daemon is already listening"));
+
+    let _ = daemon.kill();
+    let _ = daemon.wait();
+    // A stale socket falls back to local evaluation.
+    let (served, _) = json_out(cli(&temp).args(search));
+    assert_eq!(served, local_search);
+}
+
+#[test]
+fn idle_clients_do_not_block_and_config_edits_stop_the_daemon() {
+    let temp = project();
+    let search = ["search", "--format", "json", "-q", "rate"];
+    let mut daemon = start_daemon(&temp);
+    std::fs::remove_file(temp.child("index/adr-index.json").path()).unwrap();
+
+    // A client that never sends its request does not hold up the others.
+    let _idle = UnixStream::connect(temp.child(".cli-rag/daemon.sock").path()).unwrap();
+    let (_, stderr) = json_out(cli(&temp).args(search));
+    assert!(!stderr.contains("unified index not found"), "{}", stderr);
+
+    // The edited config is no longer the one the daemon serves.
+    let config = temp.child(".cli-rag.toml");
+    let text = std::fs::read_to_string(config.path()).unwrap();
+    config.write_str(&format!("{}# edited\n", text)).unwrap();
+    let (_, stderr) = json_out(cli(&temp).args(search));
+    assert!(stderr.contains("unified index not found"), "{}", stderr);
+    let mut status = None;
+    for _ in 0..50 {
+        status = daemon.try_wait().unwrap();
+        if status.is_some() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    if status.is_none() {
+        let _ = daemon.kill();
+    }
+    assert!(status.is_some_and(|s| s.success()));
+    assert!(!temp.child(".cli-rag/daemon.sock").path().exists());
+}
@@ -0,0 +1,171 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::process::Command;
+
+fn fixture() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.create_dir_all().unwrap();
+    base.child("ADR-001.md")
+        .write_str(
+            "---\nid: ADR-001\n---\n\n# Ops\n\n\
+             [@TODO:due=2026-10-20:rank=high] Renew certs\n\
+             [@TODO:scheduled=2026-10-19:repeat=weekly] Weekly review\n\
+             - [ ] Update runbook\n",
+        )
+        .unwrap();
+    base.child("ADR-002.md")
+        .write_str("+++\nid = \"ADR-002\"\nkanban_status = \"todo\"\n+++\n\n# Ship v2\n")
+        .unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(&format!("bases = [\n  '{}'\n]\n", base.path().display()))
+        .unwrap();
+    temp
+}
+
+fn cli(temp: &assert_fs::TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("cli-rag").unwrap();
+    cmd.arg("--config").arg(temp.child(".cli-rag.toml").path());
+    cmd
+}
+
+fn item_id(temp: &assert_fs::TempDir, kind: &str, text: &str) -> String {
+    let out = cli(temp)
+        .args(["search", "--format", "json", "--kind", kind, "-q", ""])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out).unwrap();
+    v["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| kind == "kanban" || r["text"] == text)
+        .map(|r| r["id"].as_str().unwrap().to_string())
+        .unwrap()
+}
+
+#[test]
+fn todo_done_and_set_rewrite_boxes_in_place() {
+    let temp = fixture();
+    let note = temp.child("notes/ADR-001.md");
+
+    let id = item_id(&temp, "todo", "Renew certs");
+    let out = cli(&temp)
+        .args([
+            "--format",
+            "json",
+            "todo",
+            "done",
+            &id,
+            "--date",
+            "2026-10-18",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(v["line"], 7);
+    assert_eq!(
+        v["after"],
+        "[@TODO:due=2026-10-20:rank=high:done=2026-10-18] Renew certs"
+    );
+    assert_ne!(v["newId"], v["id"]);
+    // The old id no longer resolves; done items cannot be closed twice.
+    cli(&temp)
+        .args(["todo", "done", &id])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not found in"));
+
+    // Repeating items advance instead of closing.
+    let id = item_id(&temp, "todo", "Weekly review");
+    cli(&temp).args(["todo", "done", &id]).assert().success();
+    let id = item_id(&temp, "todo", "Update runbook");
+    cli(&temp).args(["todo", "done", &id]).assert().success();
+    let id = item_id(&temp, "todo", "Renew certs");
+    cli(&temp)
+        .args(["todo", "set", &id, "rank=low", "done=", "owner=ops"])
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with(
+            "ADR-001.md:7: [@TODO:due=2026-10-20:rank=low:owner=ops] Renew certs\n",
+        ));
+    note.assert(predicate::str::ends_with(
+        "[@TODO:due=2026-10-20:rank=low:owner=ops] Renew certs\n\
+         [@TODO:scheduled=2026-10-26:repeat=weekly] Weekly review\n\
+         - [x] Update runbook\n",
+    ));
+    cli(&temp)
+        .args(["todo", "set", &id, "rank"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("expected key=value"));
+}
+
+#[test]
+fn todo_edit_refuses_moved_lines() {
+    let temp = fixture();
+    let note = temp.child("notes/ADR-001.md");
+    let id = item_id(&temp, "todo", "Renew certs");
+    let content = std::fs::read_to_string(note.path()).unwrap();
+    note.write_str(&content.replace("# Ops\n", "# Ops\n\nIntro.\n"))
+        .unwrap();
+    cli(&temp)
+        .args(["todo", "set", &id, "rank=low"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("moved from line 7 to line 9"));
+    assert!(std::fs::read_to_string(note.path())
+        .unwrap()
+        .contains("rank=high"));
+}
+
+#[test]
+fn kanban_move_rewrites_status_and_detects_conflicts() {
+    let temp = fixture();
+    let card = item_id(&temp, "kanban", "");
+    cli(&temp)
+        .args(["kanban", "move", "--id", &card, "--to", "doing"])
+        .assert()
+        .success()
+        .stdout("ADR-002: todo -> doing\n");
+    temp.child("notes/ADR-002.md")
+        .assert(predicate::str::starts_with(
+            "+++\nid = \"ADR-002\"\nkanban_status = \"doing\"\n+++\n",
+        ));
+    // The listed id encoded the old status.
+    cli(&temp)
+        .args(["kanban", "move", "--id", &card, "--to", "done"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("kanban_status is now 'doing'"));
+    cli(&temp)
+        .args(["kanban", "move", "--id", "ADR-002", "--to", "done"])
+        .assert()
+        .success();
+    cli(&temp)
+        .args(["kanban", "move", "--id", "ADR-001", "--to", "done"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not a kanban card"));
+}
+
+#[test]
+fn kanban_move_only_replaces_the_status_line() {
+    let temp = fixture();
+    let card = "+++\r\n# Release card\r\nid = \"ADR-002\"\r\ncreated = 2025-01-01\r\nkanban_status = \"todo\" # lane\r\ntags = [ \"ship\" ]\r\n+++\r\n\r\n# Ship v2\r\n";
+    temp.child("notes/ADR-002.md").write_str(card).unwrap();
+    cli(&temp)
+        .args(["kanban", "move", "--id", "ADR-002", "--to", "doing"])
+        .assert()
+        .success();
+    temp.child("notes/ADR-002.md")
+        .assert(card.replace("\"todo\" # lane", "\"doing\""));
+}
@@ -0,0 +1,138 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::process::Command;
+
+fn fixture() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.child("ADR-010.md")
+        .write_str(concat!(
+            "---\nid: ADR-010\n---\n\n# Overlays\n\n",
+            "## Context\n\nWhy.\n\n",
+            "## Decision Outcome\n\nUse Lua.\n\n### Details\n\nSandboxed.\n\n",
+            "```\n## Not a heading\n```\n\n",
+            "## Consequences\n\nMore hooks.\n",
+        ))
+        .unwrap();
+    base.child("ADR-001.md")
+        .write_str(concat!(
+            "---\nid: ADR-001\n---\n\n# One\n\n",
+            "See [[ADR-010#Decision Outcome]], [[ADR-010#details]] and [[#One]].\n",
+            "Broken: [[ADR-010#Rationale]] and [[#Missing]].\n",
+        ))
+        .unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(&format!(
+            "bases = [\n  '{}'\n]\n\n[[schema]]\nname = \"ADR\"\nfile_patterns = [\"ADR-*.md\"]\nunknown_policy = \"ignore\"\n",
+            base.path().display()
+        ))
+        .unwrap();
+    temp
+}
+
+fn cli(temp: &assert_fs::TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("cli-rag").unwrap();
+    cmd.env("CLI_RAG_NO_DAEMON", "1")
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path());
+    cmd
+}
+
+#[test]
+fn index_records_heading_tree_and_validate_checks_anchors() {
+    let temp = fixture();
+    let out = cli(&temp)
+        .args(["validate", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let report: Value = serde_json::from_slice(&out.stdout).unwrap();
+    let mut anchors: Vec<&str> = report["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|d| d["code"] == "W234")
+        .map(|d| d["msg"].as_str().unwrap())
+        .collect();
+    anchors.sort();
+    assert_eq!(anchors.len(), 2, "{:?}", anchors);
+    assert!(anchors[0].contains("wikilink anchor '#Missing' not found in "));
+    assert!(anchors[0].ends_with("ADR-001.md (line 8)"));
+    assert!(anchors[1].contains("wikilink anchor '#Rationale' not found in "));
+
+    let data = std::fs::read_to_string(temp.child("index/adr-index.json").path()).unwrap();
+    let index: Value = serde_json::from_str(&data).unwrap();
+    let node = index["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|n| n["id"] == "ADR-010")
+        .unwrap();
+    let headings: Vec<(u64, &str, u64)> = node["headings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|h| {
+            (
+                h["level"].as_u64().unwrap(),
+                h["anchor"].as_str().unwrap(),
+                h["line"].as_u64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        headings,
+        vec![
+            (1, "overlays", 5),
+            (2, "context", 7),
+            (2, "decision-outcome", 11),
+            (3, "details", 15),
+            (2, "consequences", 23),
+        ]
+    );
+}
+
+#[test]
+fn get_section_returns_only_that_section() {
+    let temp = fixture();
+    cli(&temp)
+        .args(["get", "--id", "ADR-010", "--section", "decision outcome"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "## Decision Outcome\n\nUse Lua.\n\n### Details\n\nSandboxed.\n\n```\n## Not a heading\n```\n",
+        ))
+        .stdout(predicate::str::contains("More hooks").not())
+        .stdout(predicate::str::contains("Why.").not());
+
+    let out = cli(&temp)
+        .args([
+            "--format",
+            "json",
+            "get",
+            "--id",
+            "ADR-010",
+            "--section",
+            "#details",
+        ])
+        .output()
+        .unwrap();
+    let v: Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(
+        v["content"][0]["text"],
+        "### Details\n\nSandboxed.\n\n```\n## Not a heading\n```\n"
+    );
+    assert_eq!(v["section"]["anchor"], "details");
+    assert_eq!(v["section"]["line"], 15);
+    assert_eq!(v["section"]["endLine"], 22);
+
+    cli(&temp)
+        .args(["get", "--id", "ADR-010", "--section", "Rationale"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "section 'Rationale' not found (headings: Overlays, Context, Decision Outcome, Details, Consequences)",
+        ));
+}
@@ -0,0 +1,121 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+const OVERLAY: &str = r##"return {
+  commands = {
+    standup = function(args, ctx)
+      local hits = ctx.search("", { status = args.status or "draft" })
+      local lines = { "standup for " .. (args[1] or "team") }
+      for _, h in ipairs(hits) do table.insert(lines, "- " .. h.id) end
+      return table.concat(lines, "\n")
+    end,
+    deps = function(args, ctx)
+      local note = ctx.get(args.id)
+      return { id = note.id, deps = #ctx.graph.dependents(args.id), verbose = args.verbose == true,
+        has_body = note.body:find("# One") ~= nil }
+    end,
+  },
+  renderers = {
+    graph = {
+      list = function(g, ctx)
+        local out = {}
+        for _, e in ipairs(g.edges) do table.insert(out, e.from .. " -> " .. e.to) end
+        return "root " .. g.root.id .. "\n" .. table.concat(out, "\n")
+      end,
+    },
+    search = {
+      ids = function(r, ctx)
+        local ids = {}
+        for _, h in ipairs(r.results) do table.insert(ids, h.id) end
+        table.sort(ids)
+        return table.concat(ids, ",")
+      end,
+    },
+  },
+}
+"##;
+
+fn fixture() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.create_dir_all().unwrap();
+    base.child("ADR-001.md")
+        .write_str("---\nid: ADR-001\nstatus: draft\n---\n\n# One\n")
+        .unwrap();
+    base.child("ADR-002.md")
+        .write_str("---\nid: ADR-002\nstatus: accepted\ndepends_on: [ADR-001]\n---\n\n# Two\n")
+        .unwrap();
+    temp.child(".cli-rag.lua").write_str(OVERLAY).unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(&format!("bases = [\n  '{}'\n]\n", base.path().display()))
+        .unwrap();
+    temp
+}
+
+fn cli(temp: &assert_fs::TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("cli-rag").unwrap();
+    cmd.current_dir(temp.path())
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path());
+    cmd
+}
+
+#[test]
+fn run_lists_and_invokes_overlay_commands() {
+    let temp = fixture();
+    cli(&temp)
+        .arg("run")
+        .assert()
+        .success()
+        .stdout("deps\nstandup\n");
+    cli(&temp)
+        .args(["run", "standup", "core"])
+        .assert()
+        .success()
+        .stdout("standup for core\n- ADR-001\n");
+
+    let out = cli(&temp)
+        .args(["run", "deps", "--id", "ADR-001", "--verbose"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(v["id"], "ADR-001");
+    assert_eq!(v["deps"], 1);
+    assert_eq!(v["verbose"], true);
+    assert_eq!(v["has_body"], true);
+
+    cli(&temp)
+        .args(["run", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "unknown overlay command 'nope' (available: deps, standup)",
+        ));
+}
+
+#[test]
+fn graph_and_search_use_overlay_renderers() {
+    let temp = fixture();
+    cli(&temp)
+        .args(["graph", "--id", "ADR-002", "--render", "list"])
+        .assert()
+        .success()
+        .stdout("root ADR-002\nADR-002 -> ADR-001\n");
+    cli(&temp)
+        .args(["search", "-q", "ADR", "--render", "ids"])
+        .assert()
+        .success()
+        .stdout("ADR-001,ADR-002\n");
+    cli(&temp)
+        .args(["search", "-q", "ADR", "--render", "table"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "unknown search renderer 'table' (available: ids)",
+        ));
+}
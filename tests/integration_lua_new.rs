@@ -92,3 +92,46 @@ fn lua_new_overrides_id_and_frontmatter() {
 
     temp.close().unwrap();
 }
+
+#[test]
+fn lua_render_frontmatter_keeps_nested_values() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.create_dir_all().unwrap();
+    let cfg = temp.child(".cli-rag.toml");
+    cfg.write_str(&format!("bases = [\n  '{}'\n]\n", base.path().display()))
+        .unwrap();
+    temp.child(".cli-rag.lua")
+        .write_str(
+            r#"return {
+  render_frontmatter = function(schema, title, ctx)
+    return { priority = 2, weight = 0.5, reviewed = false, tags = {},
+      owner = { team = "core", backups = { "a", "b" } } }
+  end
+}
+"#,
+        )
+        .unwrap();
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(cfg.path())
+        .args([
+            "ai", "new", "start", "--schema", "ADR", "--title", "Nested", "--format", "json",
+        ])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out).unwrap();
+    let fm = &v["seedFrontmatter"];
+    assert_eq!(fm["priority"], 2);
+    assert_eq!(fm["weight"], 0.5);
+    assert_eq!(fm["reviewed"], false);
+    assert_eq!(fm["tags"], serde_json::json!([]));
+    assert_eq!(
+        fm["owner"],
+        serde_json::json!({"team": "core", "backups": ["a", "b"]})
+    );
+}
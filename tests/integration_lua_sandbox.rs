@@ -0,0 +1,126 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::Value;
+use std::process::Command;
+
+fn fixture(overlay_src: &str, lua_cfg: &str) -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.create_dir_all().unwrap();
+    base.child("ADR-001.md")
+        .write_str("---\nid: ADR-001\nstatus: draft\n---\n\n# One\n")
+        .unwrap();
+    temp.child(".cli-rag.lua").write_str(overlay_src).unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(&format!(
+            "[config.scan]\nfilepaths = ['{}']\n\n{}",
+            base.path().display(),
+            lua_cfg
+        ))
+        .unwrap();
+    temp
+}
+
+fn run_json(temp: &assert_fs::TempDir, args: &[&str]) -> Value {
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(args)
+        .output()
+        .unwrap()
+        .stdout;
+    serde_json::from_slice(&out).unwrap()
+}
+
+fn lua_diags(v: &Value) -> Vec<Value> {
+    v["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|d| d["code"].as_str().unwrap_or("").starts_with("LUA_"))
+        .cloned()
+        .collect()
+}
+
+const PROBE: &str = r#"return {
+  validate = function(note, ctx)
+    local msg = "io=" .. type(io) .. " execute=" .. type(os.execute) .. " time=" .. type(os.time)
+    if note.id == "ADR-001" and io == nil then
+      os.execute("true")
+    end
+    return { diagnostics = { { severity = "warning", code = "PROBE", msg = msg } } }
+  end
+}
+"#;
+
+#[test]
+fn overlays_are_sandboxed_unless_capabilities_granted() {
+    let temp = fixture(PROBE, "");
+    let v = run_json(&temp, &["validate", "--format", "json", "--dry-run"]);
+    assert_eq!(v["ok"], false);
+    let diags = lua_diags(&v);
+    assert_eq!(diags.len(), 1, "{:?}", v);
+    assert_eq!(diags[0]["code"], "LUA_RUNTIME");
+    assert_eq!(diags[0]["line"], 5);
+    assert!(diags[0]["path"].as_str().unwrap().ends_with(".cli-rag.lua"));
+    let msg = diags[0]["msg"].as_str().unwrap();
+    assert!(msg.contains("validate failed for"), "{}", msg);
+    assert!(msg.contains("execute"), "{}", msg);
+
+    let temp = fixture(PROBE, "[config.lua]\ncapabilities = [\"io\", \"os\"]\n");
+    let v = run_json(&temp, &["validate", "--format", "json", "--dry-run"]);
+    assert_eq!(v["ok"], true, "{:?}", v);
+    let probe = v["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|d| d["code"] == "PROBE")
+        .unwrap()
+        .clone();
+    assert_eq!(
+        probe["msg"],
+        "LUA[PROBE]: io=table execute=function time=function"
+    );
+}
+
+#[test]
+fn runaway_hooks_hit_the_instruction_limit() {
+    let temp = fixture(
+        "return {\n  validate = function(note, ctx)\n    while true do end\n  end\n}\n",
+        "[config.lua]\ninstruction_limit = 100000\n",
+    );
+    let v = run_json(&temp, &["validate", "--format", "json", "--dry-run"]);
+    let diags = lua_diags(&v);
+    assert_eq!(diags.len(), 1, "{:?}", v);
+    assert_eq!(diags[0]["code"], "LUA_LIMIT");
+    assert!(diags[0]["msg"]
+        .as_str()
+        .unwrap()
+        .contains("instruction limit exceeded"));
+}
+
+#[test]
+fn syntax_errors_are_reported_in_validate_and_info() {
+    let temp = fixture(
+        "return {\n  validate = function(note, ctx)\n    return {\nend\n",
+        "",
+    );
+    let v = run_json(&temp, &["validate", "--format", "json", "--dry-run"]);
+    assert_eq!(v["ok"], false);
+    let diags = lua_diags(&v);
+    assert_eq!(diags.len(), 1, "{:?}", v);
+    assert_eq!(diags[0]["code"], "LUA_SYNTAX");
+    assert!(diags[0]["line"].as_u64().is_some(), "{:?}", diags[0]);
+
+    let info = run_json(&temp, &["--format", "json", "info"]);
+    let load = info["overlays"]["diagnostics"].as_array().unwrap();
+    assert_eq!(load.len(), 1, "{:?}", info["overlays"]);
+    assert_eq!(load[0]["code"], "LUA_SYNTAX");
+    assert!(load[0]["path"].as_str().unwrap().ends_with(".cli-rag.lua"));
+    assert_eq!(
+        info["overlays"]["sandbox"]["capabilities"],
+        serde_json::json!([])
+    );
+}
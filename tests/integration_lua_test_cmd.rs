@@ -0,0 +1,164 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::process::Command;
+
+const OVERLAY: &str = r#"return {
+  validate = function(note, ctx)
+    local out = {}
+    for _, dep in ipairs(note.depends_on) do
+      local target = ctx.graph.get(dep)
+      if note.status == "accepted" and target and target.status == "draft" then
+        table.insert(out, { severity = "error", code = "ACCEPTED_ON_DRAFT", msg = dep })
+      end
+    end
+    if note.frontmatter.review_by and note.frontmatter.review_by < ctx.clock.today_iso() then
+      table.insert(out, { severity = "warning", code = "REVIEW", msg = note.id })
+    end
+    return { diagnostics = out }
+  end,
+  id_generator = function(schema, ctx)
+    return { id = schema .. "-" .. string.format("%03d", ctx.index.next_numeric_id(schema)) }
+  end,
+}
+"#;
+
+const SPEC: &str = r#"describe("validate", function()
+  local draft = { id = "ADR-001", status = "draft" }
+  it("flags accepted notes on drafts", function()
+    local note = fixtures.note({ id = "ADR-002", status = "accepted", depends_on = { "ADR-001" } })
+    local ctx = fixtures.ctx({ notes = { draft, note } })
+    local d = assert.diagnostic(overlay.validate(note, ctx), "ACCEPTED_ON_DRAFT")
+    assert.equal(d.msg, "ADR-001")
+  end)
+  it("uses the pinned clock", function()
+    local note = fixtures.note({ id = "ADR-003", frontmatter = { review_by = "2025-06-01" } })
+    assert.no_diagnostics(overlay.validate(note, fixtures.ctx({ today = "2025-05-01" })))
+    assert.diagnostic(overlay.validate(note, fixtures.ctx({ today = "2025-07-01" })), "REVIEW")
+  end)
+end)
+
+describe("id_generator", function()
+  it("continues the numbering", function()
+    local ctx = fixtures.ctx({ notes = { { id = "ADR-007" } } })
+    assert.same(overlay.id_generator("ADR", ctx), { id = "ADR-008" })
+    assert.equal(ctx.graph.dependents("ADR-007")[1], nil)
+    assert.equal(ctx.util.kebab_case("Hello World"), "hello-world")
+  end)
+  it("is deliberately wrong", function()
+    local ctx = fixtures.ctx({})
+    assert.equal(overlay.id_generator("ADR", ctx).id, "ADR-002")
+  end)
+end)
+"#;
+
+fn fixture() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("notes").create_dir_all().unwrap();
+    temp.child(".cli-rag.lua").write_str(OVERLAY).unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str("[config.scan]\nfilepaths = [\"notes\"]\n")
+        .unwrap();
+    temp.child("spec/overlay_spec.lua").write_str(SPEC).unwrap();
+    temp
+}
+
+fn cli(temp: &assert_fs::TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("cli-rag").unwrap();
+    cmd.current_dir(temp.path())
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path());
+    cmd
+}
+
+#[test]
+fn lua_test_reports_passes_and_failures_as_json() {
+    let temp = fixture();
+    let out = cli(&temp)
+        .args(["--format", "json", "lua", "test"])
+        .assert()
+        .code(2)
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(v["ok"], false);
+    assert_eq!(v["files"], 1);
+    assert_eq!(v["passed"], 3, "{}", v);
+    assert_eq!(v["failed"], 1, "{}", v);
+    let failure = v["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["ok"] == false)
+        .unwrap();
+    assert_eq!(failure["file"], "spec/overlay_spec.lua");
+    assert_eq!(failure["name"], "id_generator > is deliberately wrong");
+    assert_eq!(failure["line"], 25);
+    assert_eq!(failure["msg"], "expected \"ADR-002\", got \"ADR-001\"");
+}
+
+#[test]
+fn lua_test_filter_and_plain_output() {
+    let temp = fixture();
+    cli(&temp)
+        .args(["lua", "test", "spec", "--filter", "validate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "ok    spec/overlay_spec.lua  validate > uses the pinned clock",
+        ))
+        .stdout(predicate::str::contains("2 passed, 0 failed"));
+    cli(&temp)
+        .args(["lua", "test"])
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains(
+            "FAIL  spec/overlay_spec.lua:25  id_generator > is deliberately wrong",
+        ));
+}
+
+#[test]
+fn fixture_ctx_answers_like_real_hooks() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("notes").create_dir_all().unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str("[config.scan]\nfilepaths = [\"notes\"]\n")
+        .unwrap();
+    temp.child("spec/ctx_spec.lua")
+        .write_str(
+            r#"describe("fixtures.ctx", function()
+  local ctx = fixtures.ctx({ notes = {
+    { id = "ADR-001", title = "Rate limiting", status = "draft", tags = { "api" } },
+    { id = "ADR-002", title = "Quotas", depends_on = { "ADR-001" },
+      body = "See [limits](ADR-001.md) and [[Rate limiting]]." },
+  } })
+  it("builds graph edges like the index", function()
+    local kinds = {}
+    for _, n in ipairs(ctx.graph.neighbors("ADR-001")) do
+      table.insert(kinds, n.edge .. ":" .. n.direction .. ":" .. n.id)
+    end
+    table.sort(kinds)
+    assert.same(kinds, { "depends_on:in:ADR-002", "links:in:ADR-002", "mentions:in:ADR-002" })
+    assert.equal(ctx.graph.dependents("ADR-001")[1].id, "ADR-002")
+    assert.equal(#ctx.graph.find({ tag = "api" }), 1)
+  end)
+  it("searches and gets notes", function()
+    local hits = ctx.search("rate", { status = "draft" })
+    assert.equal(#hits, 1)
+    assert.equal(hits[1].id, "ADR-001")
+    assert.equal(#ctx.search("status:draft OR quotas"), 2)
+    assert.matches(ctx.get("ADR-002").body, "%[%[Rate limiting%]%]")
+    assert.equal(ctx.get("ADR-404"), nil)
+  end)
+end)
+"#,
+        )
+        .unwrap();
+    cli(&temp)
+        .args(["lua", "test"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 passed, 0 failed"));
+}
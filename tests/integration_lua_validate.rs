@@ -52,3 +52,193 @@ fn lua_validate_adds_warning() {
 
     temp.close().unwrap();
 }
+
+fn graph_fixture(overlay_src: &str) -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.create_dir_all().unwrap();
+    base.child("ADR-001.md")
+        .write_str("---\nid: ADR-001\nstatus: draft\ntags: [core]\n---\n\n# One\n")
+        .unwrap();
+    base.child("ADR-002.md")
+        .write_str(
+            "---\nid: ADR-002\nstatus: accepted\ntags: [core]\ndepends_on: [ADR-001]\n---\n\n# Two\n\nSee [[ADR-003]].\n",
+        )
+        .unwrap();
+    base.child("ADR-003.md")
+        .write_str("---\nid: ADR-003\nstatus: accepted\n---\n\n# Three\n")
+        .unwrap();
+    temp.child(".cli-rag.lua").write_str(overlay_src).unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(&format!(
+            "bases = [\n  '{}'\n]\n\n[[schema]]\nname = \"ADR\"\nfile_patterns = [\"ADR-*.md\"]\n",
+            base.path().display()
+        ))
+        .unwrap();
+    temp
+}
+
+fn validate_json(temp: &assert_fs::TempDir) -> serde_json::Value {
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["validate", "--format", "json", "--dry-run"])
+        .output()
+        .unwrap()
+        .stdout;
+    serde_json::from_slice(&out).unwrap()
+}
+
+#[test]
+fn lua_validate_reads_graph_for_cross_note_policy() {
+    let temp = graph_fixture(
+        r#"return {
+  validate = function(note, ctx)
+    if note.status ~= "accepted" then return nil end
+    local out = {}
+    for _, dep in ipairs(note.depends_on) do
+      local target = ctx.graph.get(dep)
+      if target and target.status == "draft" then
+        table.insert(out, { severity = "error", code = "ACCEPTED_ON_DRAFT",
+          msg = note.id .. " depends on draft " .. dep })
+      end
+    end
+    local mentions = ctx.graph.neighbors(note.id, "mentions")
+    local dependents = ctx.graph.dependents("ADR-001")
+    table.insert(out, { severity = "warning", code = "GRAPH_PROBE",
+      msg = note.id .. " mentions=" .. #mentions .. " dependents(ADR-001)=" .. #dependents })
+    return { diagnostics = out }
+  end
+}
+"#,
+    );
+    let v = validate_json(&temp);
+    assert_eq!(v["ok"], false);
+    let diags = v["diagnostics"].as_array().unwrap();
+    let msgs: Vec<&str> = diags.iter().filter_map(|d| d["msg"].as_str()).collect();
+    assert!(
+        msgs.iter()
+            .any(|m| m.contains("ADR-002 depends on draft ADR-001")),
+        "{:?}",
+        msgs
+    );
+    assert!(
+        msgs.iter()
+            .any(|m| m.contains("ADR-002 mentions=1 dependents(ADR-001)=1")),
+        "{:?}",
+        msgs
+    );
+    assert!(
+        msgs.iter().any(|m| m.contains("ADR-003 mentions=1 ")),
+        "{:?}",
+        msgs
+    );
+}
+
+#[test]
+fn lua_graph_neighbors_include_markdown_links() {
+    let temp = graph_fixture(
+        r#"return {
+  validate = function(note, ctx)
+    local parts = {}
+    for _, n in ipairs(ctx.graph.neighbors(note.id)) do
+      table.insert(parts, n.edge .. ":" .. n.direction .. ":" .. n.id)
+    end
+    table.sort(parts)
+    return { diagnostics = { { severity = "warning", code = "EDGES",
+      msg = note.id .. " " .. table.concat(parts, ",") } } }
+  end
+}
+"#,
+    );
+    temp.child("notes/ADR-003.md")
+        .write_str(
+            "---\nid: ADR-003\nstatus: accepted\n---\n\n# Three\n\nBuilds on [one](ADR-001.md).\n",
+        )
+        .unwrap();
+    let v = validate_json(&temp);
+    let msgs: Vec<&str> = v["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|d| d["msg"].as_str())
+        .collect();
+    for want in [
+        "ADR-001 depends_on:in:ADR-002,links:in:ADR-003",
+        "ADR-003 links:out:ADR-001,mentions:in:ADR-002",
+    ] {
+        assert!(msgs.iter().any(|m| m.ends_with(want)), "{:?}", msgs);
+    }
+}
+
+#[test]
+fn lua_validate_all_runs_once_over_corpus() {
+    let temp = graph_fixture(
+        r#"return {
+  validate_all = function(notes, ctx)
+    local accepted = ctx.graph.find({ schema = "ADR", status = "accepted" })
+    local core = ctx.graph.find({ tag = "core" })
+    local out = { { severity = "warning", code = "CORPUS",
+      msg = "notes=" .. #notes .. " accepted=" .. #accepted .. " core=" .. #core } }
+    for _, n in ipairs(notes) do
+      if #n.tags == 0 then
+        table.insert(out, { severity = "warning", code = "NO_TAGS", id = n.id, msg = "untagged" })
+      end
+    end
+    return { diagnostics = out }
+  end
+}
+"#,
+    );
+    let v = validate_json(&temp);
+    let diags = v["diagnostics"].as_array().unwrap();
+    let corpus: Vec<_> = diags.iter().filter(|d| d["code"] == "CORPUS").collect();
+    assert_eq!(corpus.len(), 1);
+    assert_eq!(corpus[0]["msg"], "LUA[CORPUS]: notes=3 accepted=2 core=2");
+    let untagged = diags.iter().find(|d| d["code"] == "NO_TAGS").unwrap();
+    assert!(untagged["path"].as_str().unwrap().ends_with("ADR-003.md"));
+}
+
+#[test]
+fn lua_validate_sees_typed_frontmatter_for_yaml_and_toml() {
+    let temp = graph_fixture(
+        r#"return {
+  validate = function(note, ctx)
+    local fm = note.frontmatter
+    if fm.priority == nil then return nil end
+    local parts = { note.id, math.type(fm.priority), tostring(fm.reviewed), fm.due,
+      fm.owner.team, #fm.mixed, type(fm.mixed[2]) }
+    return { diagnostics = { { severity = "warning", code = "FM", msg = table.concat(parts, " ") } } }
+  end
+}
+"#,
+    );
+    let base = temp.child("notes");
+    base.child("ADR-010.md")
+        .write_str(
+            "---\nid: ADR-010\npriority: 3\nreviewed: true\ndue: 2024-05-01\nowner: {team: core}\nmixed: [a, 1, true]\n---\n\n# Ten\n",
+        )
+        .unwrap();
+    base.child("ADR-011.md")
+        .write_str(
+            "+++\nid = \"ADR-011\"\npriority = 4\nreviewed = false\ndue = 2024-06-01\nmixed = [\"a\", 2]\n\n[owner]\nteam = \"infra\"\n+++\n\n# Eleven\n",
+        )
+        .unwrap();
+    let v = validate_json(&temp);
+    let mut msgs: Vec<&str> = v["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|d| d["code"] == "FM")
+        .filter_map(|d| d["msg"].as_str())
+        .collect();
+    msgs.sort();
+    assert_eq!(
+        msgs,
+        vec![
+            "LUA[FM]: ADR-010 integer true 2024-05-01 core 3 number",
+            "LUA[FM]: ADR-011 integer false 2024-06-01 infra 2 number",
+        ]
+    );
+}
@@ -0,0 +1,80 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::{json, Value};
+use std::process::Command;
+
+#[test]
+fn markdown_links_become_edges_references_and_broken_link_warnings() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.child("ADR/ADR-004-toml-config-versioning.md")
+        .write_str("---\nid: ADR-004\n---\n\n# Config versioning\n")
+        .unwrap();
+    base.child("ADR/ADR-001.md")
+        .write_str(concat!(
+            "---\nid: ADR-001\n---\n\n# One\n\n",
+            "See [config](ADR-004-toml-config-versioning.md#decision) and [again][cfg].\n",
+            "External [docs](https://example.com/guide) and [gone](../missing.md).\n",
+            "`[code](nope.md)` and [site](/docs/x.md) and [top](#one).\n",
+            "\n[cfg]: ./ADR-004-toml-config-versioning.md\n",
+        ))
+        .unwrap();
+    base.child("other/ADR-002.md")
+        .write_str(
+            "---\nid: ADR-002\n---\n\n# Two\n\nUp: [four](../ADR/ADR-004-toml-config-versioning.md).\n",
+        )
+        .unwrap();
+    let cfg = temp.child(".cli-rag.toml");
+    cfg.write_str(&format!(
+        "bases = [\n  '{}'\n]\n\n[[schema]]\nname = \"ADR\"\nfile_patterns = [\"ADR-*.md\"]\nunknown_policy = \"ignore\"\n",
+        base.path().display()
+    ))
+    .unwrap();
+
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(cfg.path())
+        .args(["validate", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let report: Value = serde_json::from_slice(&out.stdout).unwrap();
+    let broken: Vec<&str> = report["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|d| d["code"] == "W235")
+        .map(|d| d["msg"].as_str().unwrap())
+        .collect();
+    assert_eq!(broken.len(), 1, "{:?}", broken);
+    assert!(broken[0].ends_with("ADR-001.md: broken link '../missing.md' (line 8)"));
+
+    let data = std::fs::read_to_string(temp.child("index/adr-index.json").path()).unwrap();
+    let index: Value = serde_json::from_str(&data).unwrap();
+    let mut links: Vec<&Value> = index["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|e| e["kind"] == "links")
+        .collect();
+    links.sort_by_key(|e| e["from"].as_str().unwrap());
+    assert_eq!(links.len(), 2, "{:?}", links);
+    assert_eq!(links[0]["from"], "ADR-001");
+    assert_eq!(links[0]["to"], "ADR-004");
+    assert_eq!(links[0]["anchor"], "decision");
+    assert_eq!(links[0]["locations"][0]["line"], 7);
+    assert_eq!(links[1]["from"], "ADR-002");
+    assert_eq!(links[1]["to"], "ADR-004");
+
+    let node = index["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|n| n["id"] == "ADR-001")
+        .unwrap();
+    assert_eq!(
+        node["references"],
+        json!([{"url": "https://example.com/guide", "line": 8}])
+    );
+}
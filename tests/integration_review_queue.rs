@@ -0,0 +1,218 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::Value;
+use std::process::Command;
+
+fn days_from_today(n: i64) -> String {
+    (chrono::Local::now().date_naive() + chrono::Duration::days(n))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+fn setup() -> (assert_fs::TempDir, assert_fs::fixture::ChildPath) {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let notes = temp.child("notes");
+    notes.create_dir_all().unwrap();
+    let cfg = temp.child(".cli-rag.toml");
+    cfg.write_str(
+        r#"[config.scan]
+filepaths = ["notes"]
+
+[[schema]]
+name = "ADR"
+file_patterns = ["ADR-*.md"]
+
+[schema.rules.review_by]
+type = "date"
+date = { review_by = true }
+
+[schema.rules.decided_at]
+type = "date"
+date = { not_before = "created", not_after = "today" }
+
+[schema.rules.expires]
+type = "date"
+severity = "warn"
+date = { not_before = "today", not_after = "today+30d" }
+
+[schema.rules.created]
+type = "date"
+date = { not_after = "today+999999999d" }
+
+[schema.validate.staleness]
+after_days = 60
+statuses = ["draft"]
+field = "updated"
+"#,
+    )
+    .unwrap();
+    notes
+        .child("ADR-001.md")
+        .write_str(&format!(
+            "---\nid: ADR-001\nstatus: accepted\nreview_by: {}\ncreated: 2024-05-01\ndecided_at: 2024-04-01\n---\n\n# One\n",
+            days_from_today(-10)
+        ))
+        .unwrap();
+    notes
+        .child("ADR-002.md")
+        .write_str(&format!(
+            "---\nid: ADR-002\nstatus: draft\nupdated: {}\nexpires: {}\n---\n\n# Two\n",
+            days_from_today(-100),
+            days_from_today(-15)
+        ))
+        .unwrap();
+    notes
+        .child("ADR-004.md")
+        .write_str(&format!(
+            "---\nid: ADR-004\nstatus: accepted\nexpires: {}\n---\n\n# Four\n",
+            days_from_today(45)
+        ))
+        .unwrap();
+    notes
+        .child("ADR-003.md")
+        .write_str(&format!(
+            "---\nid: ADR-003\nstatus: draft\nupdated: {}\nreview_by: {}\n---\n\n# Three\n",
+            days_from_today(-5),
+            days_from_today(5)
+        ))
+        .unwrap();
+    (temp, cfg)
+}
+
+#[test]
+fn validate_reports_date_rule_codes() {
+    let (temp, cfg) = setup();
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("--config")
+        .arg(cfg.path())
+        .args(["validate", "--dry-run", "--format", "json"])
+        .assert()
+        .failure()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out).unwrap();
+    let diags = v["diagnostics"].as_array().unwrap();
+    let codes: Vec<&str> = diags.iter().filter_map(|d| d["code"].as_str()).collect();
+    assert!(codes.contains(&"E227"), "{:?}", diags);
+    assert!(codes.contains(&"W228"), "{:?}", diags);
+    assert!(codes.contains(&"W229"), "{:?}", diags);
+    assert!(codes.contains(&"W226"), "{:?}", diags);
+    let order = diags.iter().find(|d| d["code"] == "E227").unwrap();
+    assert!(order["msg"]
+        .as_str()
+        .unwrap()
+        .contains("'decided_at' (2024-04-01) must not be before 'created' (2024-05-01)"));
+    let msg_of = |code: &str, path: &str| {
+        diags
+            .iter()
+            .filter(|d| d["code"] == code && d["path"].as_str().unwrap_or("").ends_with(path))
+            .map(|d| d["msg"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert!(msg_of("W226", "ADR-002.md")[0].contains("'expires' expired on"));
+    assert!(msg_of("W226", "ADR-004.md")[0].contains("'expires' date"));
+    assert!(msg_of("E226", "ADR-001.md")
+        .iter()
+        .any(|m| m.contains("date bound 'today+999999999d' is out of range")));
+    let date_codes = ["E226", "W226", "E227", "W227", "W228", "W229"];
+    assert!(!diags.iter().any(|d| {
+        d["path"].as_str().unwrap_or("").ends_with("ADR-003.md")
+            && date_codes.contains(&d["code"].as_str().unwrap_or(""))
+    }));
+}
+
+#[test]
+fn review_queue_lists_overdue_notes_most_overdue_first() {
+    let (temp, cfg) = setup();
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("--config")
+        .arg(cfg.path())
+        .args(["--format", "json", "review-queue"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out).unwrap();
+    let items = v["items"].as_array().unwrap();
+    let summary: Vec<(String, String, i64)> = items
+        .iter()
+        .map(|i| {
+            (
+                i["id"].as_str().unwrap().to_string(),
+                i["kind"].as_str().unwrap().to_string(),
+                i["daysOverdue"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("ADR-002".to_string(), "stale".to_string(), 40),
+            ("ADR-002".to_string(), "expired".to_string(), 15),
+            ("ADR-001".to_string(), "review".to_string(), 10),
+        ]
+    );
+
+    let plain = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("--config")
+        .arg(cfg.path())
+        .args(["review-queue", "--schema", "ADR"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let text = String::from_utf8(plain).unwrap();
+    assert_eq!(text.lines().count(), 3, "{}", text);
+    assert!(text.starts_with("40d\tstale\tADR-002\t"), "{}", text);
+}
+
+#[test]
+fn mtime_staleness_still_fires_after_validate_writes_the_index() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let notes = temp.child("notes");
+    notes.create_dir_all().unwrap();
+    let cfg = temp.child(".cli-rag.toml");
+    cfg.write_str(
+        "[config.scan]\nfilepaths = [\"notes\"]\n\n[[schema]]\nname = \"ADR\"\nfile_patterns = [\"ADR-*.md\"]\n\n[schema.validate.staleness]\nafter_days = 1\n",
+    )
+    .unwrap();
+    let note = notes.child("ADR-001.md");
+    note.write_str("---\nid: ADR-001\nstatus: draft\n---\n\n# One\n")
+        .unwrap();
+    let old = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_577_836_800);
+    std::fs::File::options()
+        .write(true)
+        .open(note.path())
+        .unwrap()
+        .set_modified(old)
+        .unwrap();
+    let run = |args: &[&str]| {
+        Command::cargo_bin("cli-rag")
+            .unwrap()
+            .current_dir(temp.path())
+            .env("CLI_RAG_NO_DAEMON", "1")
+            .arg("--config")
+            .arg(cfg.path())
+            .args(args)
+            .output()
+            .unwrap()
+    };
+    let validate = run(&["validate", "--format", "json"]);
+    assert!(String::from_utf8_lossy(&validate.stdout).contains("W229"));
+    assert!(temp.child("index/adr-index.json").path().exists());
+    let out = run(&["--format", "json", "review-queue"]);
+    let v: Value = serde_json::from_slice(&out.stdout).unwrap();
+    let items = v["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1, "{:?}", v);
+    assert_eq!(items[0]["id"], "ADR-001");
+    assert_eq!(items[0]["kind"], "stale");
+}
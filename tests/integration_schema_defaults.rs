@@ -0,0 +1,144 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::Value;
+use std::process::Command;
+
+fn setup() -> (assert_fs::TempDir, assert_fs::fixture::ChildPath) {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("notes").create_dir_all().unwrap();
+    let cfg = temp.child(".cli-rag.toml");
+    cfg.write_str(
+        r#"[config.scan]
+filepaths = ["notes"]
+
+[[schema]]
+name = "ADR"
+file_patterns = ["ADR-*.md"]
+required = ["id", "owner", "created"]
+
+[schema.new.defaults]
+created = "{{now:%Y}}"
+status = "proposed"
+owner = "{{env:CLI_RAG_TEST_OWNER}}"
+review_by = "{{now+90d}}"
+tags = ["adr", "{{schema.name}}"]
+"#,
+    )
+    .unwrap();
+    (temp, cfg)
+}
+
+fn review_by_expected() -> String {
+    (chrono::Local::now() + chrono::Duration::days(90))
+        .format("%Y-%m-%d")
+        .to_string()
+}
+
+#[test]
+fn legacy_new_applies_schema_defaults() {
+    let (temp, cfg) = setup();
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .env("CLI_RAG_TEST_OWNER", "alice")
+        .arg("--config")
+        .arg(cfg.path())
+        .args(["new", "--schema", "ADR", "--title", "Hello"])
+        .assert()
+        .success();
+    let text = std::fs::read_to_string(temp.child("notes/ADR-001.md").path()).unwrap();
+    let fm: serde_yaml::Value = serde_yaml::from_str(text.split("---\n").nth(1).unwrap()).unwrap();
+    assert_eq!(fm["id"], "ADR-001");
+    assert_eq!(fm["status"], "proposed");
+    assert_eq!(fm["owner"], "alice");
+    assert_eq!(fm["created"], chrono::Local::now().format("%Y").to_string());
+    assert_eq!(fm["review_by"], review_by_expected().as_str());
+    assert_eq!(
+        fm["tags"],
+        serde_yaml::from_str::<serde_yaml::Value>("[adr, ADR]").unwrap()
+    );
+    assert!(text.contains("\n# Hello\n"));
+}
+
+#[test]
+fn ai_new_start_seeds_schema_defaults() {
+    let (temp, cfg) = setup();
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .env("CLI_RAG_TEST_OWNER", "bob")
+        .arg("--config")
+        .arg(cfg.path())
+        .args(["ai", "new", "start", "--schema", "ADR", "--title", "Two"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out).unwrap();
+    let seed = &v["seedFrontmatter"];
+    assert_eq!(seed["status"], "proposed");
+    assert_eq!(seed["owner"], "bob");
+    assert_eq!(seed["review_by"], review_by_expected().as_str());
+    assert_eq!(seed["tags"], serde_json::json!(["adr", "ADR"]));
+}
+
+#[test]
+fn validate_fix_fills_missing_required_keys_from_defaults() {
+    let (temp, cfg) = setup();
+    temp.child("notes/ADR-001.md")
+        .write_str("---\nid: ADR-001\nstatus: draft\n---\n\n# One\n")
+        .unwrap();
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .env("CLI_RAG_TEST_OWNER", "carol")
+        .arg("--config")
+        .arg(cfg.path())
+        .args(["validate", "--fix", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(v["ok"], true);
+    let applied = v["fixes"][0]["applied"].as_array().unwrap();
+    assert_eq!(applied.len(), 2);
+    let text = std::fs::read_to_string(temp.child("notes/ADR-001.md").path()).unwrap();
+    assert!(text.contains("\nowner: carol\n"), "{}", text);
+    assert!(!text.contains("review_by"), "{}", text);
+}
+
+#[test]
+fn out_of_range_now_offsets_are_left_as_written() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("notes").create_dir_all().unwrap();
+    let cfg = temp.child(".cli-rag.toml");
+    cfg.write_str(
+        r#"[config.scan]
+filepaths = ["notes"]
+
+[[schema]]
+name = "ADR"
+file_patterns = ["ADR-*.md"]
+
+[schema.new.defaults]
+far = "{{now+99999999999999d}}"
+later = "{{now+999999999d}}"
+"#,
+    )
+    .unwrap();
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("--config")
+        .arg(cfg.path())
+        .args(["new", "--schema", "ADR", "--title", "Far"])
+        .assert()
+        .success();
+    let text = std::fs::read_to_string(temp.child("notes/ADR-001.md").path()).unwrap();
+    let fm: serde_yaml::Value = serde_yaml::from_str(text.split("---\n").nth(1).unwrap()).unwrap();
+    assert_eq!(fm["far"], "{{now+99999999999999d}}");
+    assert_eq!(fm["later"], "{{now+999999999d}}");
+}
@@ -0,0 +1,137 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use std::process::Command;
+
+fn write_config(temp: &assert_fs::TempDir) -> assert_fs::fixture::ChildPath {
+    temp.child("notes").create_dir_all().unwrap();
+    let cfg = temp.child(".cli-rag.toml");
+    cfg.write_str(
+        r#"[config.scan]
+filepaths = ["notes"]
+
+[[schema]]
+name = "ADR"
+file_patterns = ["ADR-*.md"]
+required = ["id", "title"]
+allowed_keys = ["owner"]
+unknown_policy = "error"
+
+[schema.rules.status]
+enum = ["draft", "accepted"]
+
+[schema.rules.priority]
+integer = { min = 1, max = 5 }
+
+[schema.rules.reviewed]
+type = "date"
+
+[schema.rules.released]
+type = "date"
+format = "%d/%m/%Y"
+
+[schema.rules.links]
+type = "array"
+min_items = 1
+regex = "^ADR-\\d+$"
+
+[[schema]]
+name = "IMP"
+file_patterns = ["IMP-*.md"]
+"#,
+    )
+    .unwrap();
+    cfg
+}
+
+#[test]
+fn schema_export_translates_rules_into_json_schema() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cfg = write_config(&temp);
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(cfg.path())
+        .arg("schema")
+        .arg("export")
+        .arg("--name")
+        .arg("ADR")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(v["required"], serde_json::json!(["id", "title"]));
+    assert_eq!(v["additionalProperties"], false);
+    let props = &v["properties"];
+    assert_eq!(props["owner"], serde_json::json!({}));
+    assert_eq!(props["priority"]["type"], "integer");
+    assert_eq!(props["priority"]["minimum"], 1);
+    assert_eq!(props["priority"]["maximum"], 5);
+    assert_eq!(props["reviewed"]["format"], "date");
+    assert_eq!(props["released"]["pattern"], r"^\d{2}/\d{2}/\d{4}$");
+    assert_eq!(props["links"]["type"], "array");
+    assert_eq!(props["links"]["minItems"], 1);
+    assert_eq!(props["links"]["items"]["pattern"], r"^ADR-\d+$");
+    assert_eq!(
+        props["status"]["anyOf"][0]["enum"],
+        serde_json::json!(["draft", "accepted"])
+    );
+}
+
+#[test]
+fn schema_export_writes_config_and_note_schemas_to_dir() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cfg = write_config(&temp);
+    let out_dir = temp.child("schemas");
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(cfg.path())
+        .arg("schema")
+        .arg("export")
+        .arg("--out")
+        .arg(out_dir.path())
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(v["written"].as_array().unwrap().len(), 3);
+
+    let config: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(out_dir.child("cli-rag.config.schema.json").path()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        config["definitions"]["schema"]["required"],
+        serde_json::json!(["name", "file_patterns"])
+    );
+    assert!(config["properties"]["config"]["properties"]["scan"].is_object());
+
+    let imp: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(out_dir.child("IMP.frontmatter.schema.json").path()).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(imp["additionalProperties"], true);
+    assert!(out_dir.child("ADR.frontmatter.schema.json").path().exists());
+}
+
+#[test]
+fn schema_export_rejects_unknown_schema() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let cfg = write_config(&temp);
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(cfg.path())
+        .arg("schema")
+        .arg("export")
+        .arg("--name")
+        .arg("NOPE")
+        .assert()
+        .failure();
+}
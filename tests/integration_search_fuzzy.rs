@@ -0,0 +1,111 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::Value;
+use std::process::Command;
+
+fn fixture(extra_cfg: &str) -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.create_dir_all().unwrap();
+    base.child("ADR-001.md")
+        .write_str(
+            "---\nid: ADR-001\ntags: [security]\n---\n\n# Token authentication\n\n## Café résumé\n",
+        )
+        .unwrap();
+    base.child("ADR-002.md")
+        .write_str("---\nid: ADR-002\ntags: [authentication]\n---\n\n# Rate limiting\n")
+        .unwrap();
+    base.child("ADR-003.md")
+        .write_str(
+            "---\nid: ADR-003\n---\n\n# Logging\n\n```\n# authentication in a code block\n```\n",
+        )
+        .unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(&format!(
+            "[config.scan]\nfilepaths = ['{}']\n{}",
+            base.path().display(),
+            extra_cfg
+        ))
+        .unwrap();
+    temp
+}
+
+fn search(temp: &assert_fs::TempDir, query: &str) -> Vec<Value> {
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["search", "--format", "json", "-q", query])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: Value = serde_json::from_slice(&out).unwrap();
+    v["results"].as_array().unwrap().clone()
+}
+
+fn ids(results: &[Value]) -> Vec<&str> {
+    results.iter().map(|r| r["id"].as_str().unwrap()).collect()
+}
+
+#[test]
+fn search_tolerates_typos_and_reports_match_positions() {
+    let temp = fixture("");
+    // Title boost (2.0) outranks the tag boost (1.5); code blocks are not headings.
+    let res = search(&temp, "authentcation");
+    assert_eq!(ids(&res), vec!["ADR-001", "ADR-002"]);
+    assert_eq!(
+        res[0]["matches"],
+        serde_json::json!([{"field": "title", "positions": [6, 7, 8, 9, 10, 11, 12, 14, 15, 16, 17, 18, 19]}])
+    );
+    assert_eq!(
+        res[1]["matches"],
+        serde_json::json!([{"field": "tags", "index": 0, "positions": [0, 1, 2, 3, 4, 5, 6, 8, 9, 10, 11, 12, 13]}])
+    );
+
+    // Subsequence match on the id; folded match on a heading.
+    let res = search(&temp, "adr2");
+    assert_eq!(ids(&res), vec!["ADR-002"]);
+    assert_eq!(
+        res[0]["matches"][0]["positions"],
+        serde_json::json!([0, 1, 2, 6])
+    );
+    let res = search(&temp, "cafe resume");
+    assert_eq!(ids(&res), vec!["ADR-001"]);
+    assert_eq!(
+        res[0]["matches"],
+        serde_json::json!([{"field": "headings", "text": "Café résumé", "positions": [0, 1, 2, 3, 5, 6, 7, 8, 9, 10]}])
+    );
+    assert!(search(&temp, "zzzz").is_empty());
+}
+
+#[test]
+fn search_boosts_are_configurable() {
+    let temp = fixture("\n[config.search.boosts]\ntitle = 1.0\ntags = 4.0\nheadings = 0\n");
+    assert_eq!(
+        ids(&search(&temp, "authentication")),
+        vec!["ADR-002", "ADR-001"]
+    );
+    assert!(search(&temp, "resume").is_empty());
+}
+
+#[test]
+fn heading_matches_come_from_the_unified_index() {
+    let temp = fixture("");
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .env("CLI_RAG_NO_DAEMON", "1")
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["validate", "--format", "json"])
+        .assert()
+        .success();
+    // Edited after indexing: search answers from the index, not the file.
+    temp.child("notes/ADR-001.md")
+        .write_str("---\nid: ADR-001\n---\n\n# Token authentication\n")
+        .unwrap();
+    let res = search(&temp, "cafe resume");
+    assert_eq!(ids(&res), vec!["ADR-001"]);
+    assert_eq!(res[0]["matches"][0]["field"], "headings");
+}
@@ -0,0 +1,130 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+fn note(base: &assert_fs::fixture::ChildPath, id: &str, fm: &str, title: &str) {
+    base.child(format!("{id}.md"))
+        .write_str(&format!("---\nid: {id}\n{fm}---\n\n# {title}\n"))
+        .unwrap();
+}
+
+fn fixture() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.create_dir_all().unwrap();
+    note(
+        &base,
+        "ADR-004",
+        "status: accepted\ntags: [security]\n",
+        "Auth tokens",
+    );
+    note(
+        &base,
+        "ADR-010",
+        "status: proposed\ntags: [security]\ndepends_on: [ADR-004]\nupdated: 2025-03-01\npriority: 3\n",
+        "Rate limiting for the API",
+    );
+    note(
+        &base,
+        "ADR-011",
+        "status: accepted\ntags: [security, deprecated]\ndepends_on: [ADR-004]\nupdated: 2025-04-01\n",
+        "Old rate limiting",
+    );
+    note(
+        &base,
+        "ADR-012",
+        "status: draft\ntags: [security]\ndepends_on: [ADR-004]\nupdated: 2025-05-01\n",
+        "Rate limiting v2",
+    );
+    note(
+        &base,
+        "IMP-001",
+        "status: accepted\ndepends_on: [ADR-004]\nupdated: 2024-12-01\nurgent: true\n",
+        "Rate limiting rollout",
+    );
+    temp.child(".cli-rag.toml")
+        .write_str(&format!(
+            "bases = [\n  '{}'\n]\n\n[[schema]]\nname = \"ADR\"\nfile_patterns = [\"ADR-*.md\"]\nunknown_policy = \"ignore\"\n\n[[schema]]\nname = \"IMP\"\nfile_patterns = [\"IMP-*.md\"]\nunknown_policy = \"ignore\"\n",
+            base.path().display()
+        ))
+        .unwrap();
+    temp
+}
+
+fn ids(temp: &assert_fs::TempDir, query: &str) -> Vec<String> {
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["search", "--format", "json", "-q", query])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    let mut ids: Vec<String> = v["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["id"].as_str().unwrap().to_string())
+        .collect();
+    ids.sort();
+    ids
+}
+
+#[test]
+fn search_query_combines_field_predicates() {
+    let temp = fixture();
+    assert_eq!(
+        ids(
+            &temp,
+            "schema:ADR status:(accepted OR proposed) tag:security -tag:deprecated depends_on:ADR-004 updated:>2025-01-01 \"rate limiting\""
+        ),
+        vec!["ADR-010"]
+    );
+    assert_eq!(ids(&temp, "rate -schema:ADR"), vec!["IMP-001"]);
+    assert_eq!(ids(&temp, "priority:>=3"), vec!["ADR-010"]);
+    assert_eq!(
+        ids(&temp, "updated:<=2025-04-01 OR status:draft"),
+        vec!["ADR-010", "ADR-011", "ADR-012", "IMP-001"]
+    );
+    assert_eq!(
+        ids(&temp, "id:ADR-01* NOT title:old"),
+        vec!["ADR-010", "ADR-012"]
+    );
+    assert_eq!(ids(&temp, "ADR-004"), vec!["ADR-004"]);
+}
+
+#[test]
+fn number_and_boolean_predicates_survive_the_unified_index() {
+    let temp = fixture();
+    for _ in 0..2 {
+        assert_eq!(ids(&temp, "priority:>2"), vec!["ADR-010"]);
+        assert_eq!(ids(&temp, "urgent:true"), vec!["IMP-001"]);
+        // second round reads the index written by validate
+        Command::cargo_bin("cli-rag")
+            .unwrap()
+            .arg("--config")
+            .arg(temp.child(".cli-rag.toml").path())
+            .arg("validate")
+            .assert()
+            .success();
+    }
+}
+
+#[test]
+fn search_query_errors_point_at_the_token() {
+    let temp = fixture();
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["search", "-q", "status:(accepted OR proposed"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid query at column 8: unclosed '('\n  status:(accepted OR proposed\n         ^",
+        ));
+}
@@ -0,0 +1,172 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use std::process::Command;
+
+fn setup() -> (assert_fs::TempDir, assert_fs::fixture::ChildPath) {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let notes = temp.child("notes");
+    notes.create_dir_all().unwrap();
+    let cfg = temp.child(".cli-rag.toml");
+    cfg.write_str(
+        "[config.scan]\nfilepaths = [\"notes\"]\n\n[[schema]]\nname = \"ADR\"\nfile_patterns = [\"ADR-*.md\"]\nunknown_policy = \"warn\"\n",
+    )
+    .unwrap();
+    notes
+        .child("ADR-001.md")
+        .write_str("---\nid: ADR-001\nstatus: Draft\ntags: solo\nbogus: 1\n---\n\n# One\n")
+        .unwrap();
+    notes
+        .child("ADR-002.md")
+        .write_str(
+            "---\nid: ADR-002\nstatus: accepted\ntags: [a, b, a]\nsupersedes: ADR-001\n---\n\n# Two\n",
+        )
+        .unwrap();
+    notes
+        .child("ADR-003.md")
+        .write_str("+++\nid = \"ADR-003\"\nstatus = \"DRAFT\"\n+++\n\n# Three\n")
+        .unwrap();
+    (temp, cfg)
+}
+
+#[test]
+fn validate_fix_dry_run_reports_diffs_without_writing() {
+    let (temp, cfg) = setup();
+    let before = std::fs::read_to_string(temp.child("notes/ADR-001.md").path()).unwrap();
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("--config")
+        .arg(cfg.path())
+        .arg("validate")
+        .arg("--fix")
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let text = String::from_utf8(out).unwrap();
+    assert!(text.contains("--- a/"), "{}", text);
+    assert!(text.contains("\n-status: Draft\n"), "{}", text);
+    assert!(text.contains("\n+status: draft\n"), "{}", text);
+    assert!(text.contains("Would fix"), "{}", text);
+    let after = std::fs::read_to_string(temp.child("notes/ADR-001.md").path()).unwrap();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn validate_fix_rewrites_front_matter_and_keeps_flavor() {
+    let (temp, cfg) = setup();
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("--config")
+        .arg(cfg.path())
+        .arg("validate")
+        .arg("--fix")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(v["ok"], true);
+    assert_eq!(v["fixes"].as_array().unwrap().len(), 3);
+    let diags = v["diagnostics"].as_array().unwrap();
+    assert!(!diags.iter().any(|d| d["code"] == "W232"));
+    // the unknown key is kept under "warn", so its warning remains
+    assert!(diags.iter().any(|d| d["code"] == "W221"));
+
+    let one = std::fs::read_to_string(temp.child("notes/ADR-001.md").path()).unwrap();
+    let fm: serde_yaml::Value = serde_yaml::from_str(one.split("---\n").nth(1).unwrap()).unwrap();
+    assert_eq!(fm["status"], "draft");
+    assert_eq!(
+        fm["tags"],
+        serde_yaml::from_str::<serde_yaml::Value>("[solo]").unwrap()
+    );
+    assert_eq!(
+        fm["superseded_by"],
+        serde_yaml::from_str::<serde_yaml::Value>("[ADR-002]").unwrap()
+    );
+    // unknown_policy = "warn" keeps unknown keys
+    assert_eq!(fm["bogus"], 1);
+    assert!(one.ends_with("---\n\n# One\n"));
+
+    let two = std::fs::read_to_string(temp.child("notes/ADR-002.md").path()).unwrap();
+    assert!(
+        two.contains("\ntags: [a, b]\nsupersedes: ADR-001\n"),
+        "{}",
+        two
+    );
+    let fm: serde_yaml::Value = serde_yaml::from_str(two.split("---\n").nth(1).unwrap()).unwrap();
+    assert_eq!(
+        fm["tags"],
+        serde_yaml::from_str::<serde_yaml::Value>("[a, b]").unwrap()
+    );
+
+    let three = std::fs::read_to_string(temp.child("notes/ADR-003.md").path()).unwrap();
+    assert_eq!(
+        three,
+        "+++\nid = \"ADR-003\"\nstatus = \"draft\"\n+++\n\n# Three\n"
+    );
+}
+
+#[test]
+fn validate_fix_removes_unknown_keys_only_under_error_policy() {
+    let (temp, cfg) = setup();
+    let text = std::fs::read_to_string(cfg.path()).unwrap();
+    cfg.write_str(&text.replace("\"warn\"", "\"error\""))
+        .unwrap();
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("--config")
+        .arg(cfg.path())
+        .arg("validate")
+        .arg("--fix")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success();
+    let one = std::fs::read_to_string(temp.child("notes/ADR-001.md").path()).unwrap();
+    assert!(!one.contains("bogus"), "{}", one);
+}
+
+#[test]
+fn validate_warns_on_missing_reciprocal_superseded_by() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let notes = temp.child("notes");
+    notes.create_dir_all().unwrap();
+    let cfg = temp.child(".cli-rag.toml");
+    cfg.write_str("[config.scan]\nfilepaths = [\"notes\"]\n")
+        .unwrap();
+    notes
+        .child("ADR-001.md")
+        .write_str("---\nid: ADR-001\nstatus: draft\n---\n\n# One\n")
+        .unwrap();
+    notes
+        .child("ADR-002.md")
+        .write_str("---\nid: ADR-002\nstatus: draft\nsupersedes: ADR-001\n---\n\n# Two\n")
+        .unwrap();
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("--config")
+        .arg(cfg.path())
+        .arg("validate")
+        .arg("--dry-run")
+        .arg("--format")
+        .arg("json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert!(v.get("fixes").is_none());
+    let diags = v["diagnostics"].as_array().unwrap();
+    let w = diags.iter().find(|d| d["code"] == "W232").expect("W232");
+    assert!(w["path"].as_str().unwrap().ends_with("ADR-001.md"));
+}
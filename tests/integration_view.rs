@@ -0,0 +1,127 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+const VIEWS: &str = r#"
+[[view]]
+name = "open-security"
+description = "Open security decisions"
+query = "group:security -status:accepted"
+sort = ["-updated"]
+columns = ["id", "title", "status", "updated"]
+
+[[view]]
+name = "todos"
+kind = ["todo"]
+columns = ["note_id", "text"]
+"#;
+
+fn fixture(views: &str) -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.create_dir_all().unwrap();
+    for (id, status, updated, title) in [
+        ("ADR-001", "proposed", "2025-02-01", "Auth"),
+        ("ADR-002", "accepted", "2025-03-01", "Rate limits"),
+        ("ADR-003", "draft", "2025-04-01", "Key rotation"),
+    ] {
+        base.child(format!("{id}.md"))
+            .write_str(&format!(
+                "---\nid: {id}\nstatus: {status}\ngroups: [security]\nupdated: {updated}\n---\n\n# {title}\n\n- [ ] follow up {id}\n"
+            ))
+            .unwrap();
+    }
+    temp.child(".cli-rag.toml")
+        .write_str(&format!(
+            "bases = [\n  '{}'\n]\n{}",
+            base.path().display(),
+            views
+        ))
+        .unwrap();
+    temp
+}
+
+fn cli(temp: &assert_fs::TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("cli-rag").unwrap();
+    cmd.arg("--config").arg(temp.child(".cli-rag.toml").path());
+    cmd
+}
+
+#[test]
+fn view_renders_saved_searches() {
+    let temp = fixture(VIEWS);
+    cli(&temp)
+        .arg("view")
+        .assert()
+        .success()
+        .stdout("open-security\tOpen security decisions\ntodos\n");
+    cli(&temp)
+        .args(["view", "open-security"])
+        .assert()
+        .success()
+        .stdout(
+            "ID       TITLE         STATUS    UPDATED\n\
+             ADR-003  Key rotation  draft     2025-04-01\n\
+             ADR-001  Auth          proposed  2025-02-01\n",
+        );
+
+    let out = cli(&temp)
+        .args(["view", "todos", "--format", "json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let v: serde_json::Value = serde_json::from_slice(&out).unwrap();
+    assert_eq!(v["view"], "todos");
+    assert_eq!(v["columns"], serde_json::json!(["note_id", "text"]));
+    let rows = v["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 3);
+    assert!(rows
+        .iter()
+        .any(|r| r["note_id"] == "ADR-002" && r["text"] == "follow up ADR-002"));
+
+    cli(&temp)
+        .args(["view", "open-security", "--format", "ndjson"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            r#"{"id":"ADR-003","status":"draft","title":"Key rotation","updated":"2025-04-01"}"#,
+        ));
+    cli(&temp)
+        .args(["view", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "unknown view 'missing' (available: open-security, todos)",
+        ));
+}
+
+#[test]
+fn view_errors_and_completions() {
+    let temp = fixture("\n[[view]]\nname = \"a\"\n\n[[view]]\nname = \"a\"\n");
+    cli(&temp)
+        .arg("view")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Duplicate view name(s) detected: a",
+        ));
+
+    let temp = fixture("\n[[view]]\nname = \"broken\"\nquery = \"status:(a OR\"\n");
+    cli(&temp)
+        .args(["view", "broken"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "view 'broken': invalid query at column 11",
+        ));
+
+    let temp = fixture(VIEWS);
+    cli(&temp)
+        .args(["completions", "zsh"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(open-security todos)"));
+}
@@ -0,0 +1,158 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::Value;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+
+fn note(id: &str, deps: &[&str]) -> String {
+    format!(
+        "---\nid: {}\ntags: []\nstatus: draft\ndepends_on: [{}]\n---\n\n# {}\n",
+        id,
+        deps.join(", "),
+        id
+    )
+}
+
+/// Events up to and including the next `validated` event.
+fn next_pass(rx: &Receiver<Value>) -> Vec<Value> {
+    let mut out = Vec::new();
+    loop {
+        let v = rx
+            .recv_timeout(Duration::from_secs(10))
+            .expect("watch event");
+        // `index_written` trails the previous pass.
+        if out.is_empty() && v["event"] == "index_written" {
+            continue;
+        }
+        let done = v["event"] == "validated";
+        out.push(v);
+        if done {
+            return out;
+        }
+    }
+}
+
+fn names(events: &[Value]) -> Vec<&str> {
+    events
+        .iter()
+        .map(|e| e["event"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn watch_emits_per_note_events_and_diagnostic_diffs() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.create_dir_all().unwrap();
+    base.child("ADR-001.md")
+        .write_str(&note("ADR-001", &["ADR-002"]))
+        .unwrap();
+    base.child("ADR-002.md")
+        .write_str(&note("ADR-002", &[]))
+        .unwrap();
+    let cfg = temp.child(".cli-rag.toml");
+    cfg.write_str(&format!(
+        "bases = [\n  '{}'\n]\n\n[[schema]]\nname = \"ADR\"\nfile_patterns = [\"ADR-*.md\"]\nunknown_policy = \"ignore\"\n",
+        base.path().display()
+    ))
+    .unwrap();
+
+    let mut child = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(cfg.path())
+        .args(["watch", "--debounce-ms", "200", "--json"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("spawn watch");
+    let stdout = child.stdout.take().unwrap();
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if tx
+                .send(serde_json::from_str::<Value>(&line).unwrap())
+                .is_err()
+            {
+                break;
+            }
+        }
+    });
+
+    let initial = next_pass(&rx);
+    assert_eq!(names(&initial), vec!["watch_start", "validated"]);
+    assert_eq!(initial[1]["ok"], true);
+    // Index events follow the initial pass; let the watcher start.
+    std::thread::sleep(Duration::from_millis(700));
+    while rx.try_recv().is_ok() {}
+
+    let adr1 = base.child("ADR-001.md");
+    let adr1_path = adr1.path().display().to_string();
+    adr1.write_str(&note("ADR-001", &["ADR-003"])).unwrap();
+    let pass = next_pass(&rx);
+    assert_eq!(pass[0]["event"], "note_changed");
+    assert_eq!(pass[0]["path"], adr1_path);
+    assert_eq!(pass[0]["id"], "ADR-001");
+    assert!(pass.iter().any(|e| e["event"] == "edge_removed"
+        && e["to"] == "ADR-002"
+        && e["kind"] == "depends_on"));
+    assert!(pass
+        .iter()
+        .any(|e| e["event"] == "edge_added" && e["from"] == "ADR-001" && e["to"] == "ADR-003"));
+    let diag = pass
+        .iter()
+        .find(|e| e["event"] == "diagnostics" && e["path"] == adr1_path)
+        .expect("diagnostics for ADR-001");
+    assert_eq!(diag["added"][0]["severity"], "error");
+    assert_eq!(
+        diag["added"][0]["message"],
+        format!("{}: depends_on 'ADR-003' not found", adr1_path)
+    );
+    let validated = pass.last().unwrap();
+    assert_eq!(validated["ok"], false);
+    assert_eq!(validated["revalidated"], 2);
+
+    base.child("ADR-003.md")
+        .write_str(&note("ADR-003", &[]))
+        .unwrap();
+    let pass = next_pass(&rx);
+    assert_eq!(pass[0]["event"], "note_added");
+    assert_eq!(pass[0]["id"], "ADR-003");
+    let diag = pass
+        .iter()
+        .find(|e| e["event"] == "diagnostics" && e["path"] == adr1_path)
+        .expect("diagnostics for ADR-001");
+    assert_eq!(diag["added"], serde_json::json!([]));
+    assert_eq!(diag["removed"][0]["severity"], "error");
+    assert_eq!(pass.last().unwrap()["ok"], true);
+
+    std::fs::remove_file(base.child("ADR-002.md").path()).unwrap();
+    let pass = next_pass(&rx);
+    assert_eq!(names(&pass)[0], "note_removed");
+    assert_eq!(pass[0]["id"], "ADR-002");
+    // Per-base then unified `index_written` events follow a clean pass.
+    let unified = loop {
+        let v = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        if v["event"] == "index_written"
+            && v["count"] == 2
+            && !v["path"].as_str().unwrap().contains("notes")
+        {
+            break v;
+        }
+    };
+    let index: Value =
+        serde_json::from_str(&std::fs::read_to_string(unified["path"].as_str().unwrap()).unwrap())
+            .unwrap();
+    let ids: Vec<&str> = index["nodes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|n| n["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["ADR-001", "ADR-003"]);
+
+    let _ = child.kill();
+    let _ = child.wait();
+}
@@ -0,0 +1,135 @@
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use serde_json::Value;
+use std::process::Command;
+
+fn fixture() -> assert_fs::TempDir {
+    let temp = assert_fs::TempDir::new().unwrap();
+    let base = temp.child("notes");
+    base.child("ADR-003d-v1.2-locked-CLI-commands.md")
+        .write_str("---\nid: ADR-003\n---\n\n# Locked CLI commands\n")
+        .unwrap();
+    base.child("ADR-010.md")
+        .write_str(
+            "---\nid: ADR-010\naliases: [Lua escape hatch]\n---\n\n# Overlays\n\n## Decision\n",
+        )
+        .unwrap();
+    base.child("ADR-001.md")
+        .write_str(concat!(
+            "---\nid: ADR-001\n---\n\n# One\n\n",
+            "See [[ADR-003d-v1.2-locked-CLI-commands]] and [[ADR-010#Decision|the hatch]].\n",
+            "![[ADR-010]] and [[locked cli commands]] and [[Lua escape hatch]].\n",
+            "Skip `[[ADR-404]]` and [[#One]] and ![[diagram.png]].\n",
+            "```\n[[ADR-405]]\n```\n",
+            "Broken: [[ADR-999|gone]].\n",
+        ))
+        .unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str(&format!(
+            "bases = [\n  '{}'\n]\n\n[[schema]]\nname = \"ADR\"\nfile_patterns = [\"ADR-*.md\"]\nunknown_policy = \"ignore\"\n",
+            base.path().display()
+        ))
+        .unwrap();
+    temp
+}
+
+fn validate(temp: &assert_fs::TempDir) -> Value {
+    let out = Command::cargo_bin("cli-rag")
+        .unwrap()
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["validate", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    serde_json::from_slice(&out.stdout).unwrap()
+}
+
+#[test]
+fn index_resolves_filename_alias_anchor_and_title_links() {
+    let temp = fixture();
+    validate(&temp);
+    let data = std::fs::read_to_string(temp.child("index/adr-index.json").path()).unwrap();
+    let v: Value = serde_json::from_str(&data).unwrap();
+    let mentions: Vec<(&str, u64, &Value)> = v["edges"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|e| e["kind"] == "mentions" && e["from"] == "ADR-001")
+        .map(|e| {
+            (
+                e["to"].as_str().unwrap(),
+                e["locations"][0]["line"].as_u64().unwrap(),
+                e,
+            )
+        })
+        .collect();
+    let targets: Vec<(&str, u64)> = mentions.iter().map(|(t, l, _)| (*t, *l)).collect();
+    assert_eq!(
+        targets,
+        vec![
+            ("ADR-003", 7),
+            ("ADR-010", 7),
+            ("ADR-010", 8),
+            ("ADR-003", 8)
+        ]
+    );
+    let hatch = mentions[1].2;
+    assert_eq!(hatch["anchor"], "Decision");
+    assert_eq!(hatch["alias"], "the hatch");
+    // Same target twice on line 8: the embed comes first and wins.
+    assert_eq!(mentions[2].2["embed"], true);
+}
+
+#[test]
+fn validate_reports_unresolved_wikilinks() {
+    let temp = fixture();
+    let v = validate(&temp);
+    let unresolved: Vec<&Value> = v["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|d| d["code"] == "W233")
+        .collect();
+    assert_eq!(unresolved.len(), 1, "{:?}", unresolved);
+    let msg = unresolved[0]["msg"].as_str().unwrap();
+    assert!(
+        msg.ends_with("unresolved wikilink 'ADR-999' (line 13)"),
+        "{}",
+        msg
+    );
+}
+
+#[test]
+fn lua_fixture_graph_resolves_wikilinks_like_notes() {
+    let temp = assert_fs::TempDir::new().unwrap();
+    temp.child("notes").create_dir_all().unwrap();
+    temp.child(".cli-rag.toml")
+        .write_str("[config.scan]\nfilepaths = [\"notes\"]\n")
+        .unwrap();
+    temp.child("spec/links_spec.lua")
+        .write_str(
+            r#"describe("mentions", function()
+  it("resolves titles and aliases, not code", function()
+    local ctx = fixtures.ctx({ notes = {
+      { id = "ADR-001", body = "[[Overlays|hatch]] `[[ADR-003]]` [[ADR-002#Context]]" },
+      { id = "ADR-002", title = "Overlays" },
+      { id = "ADR-003" },
+    } })
+    local out = ctx.graph.neighbors("ADR-001", "mentions")
+    assert.equal(#out, 1)
+    assert.equal(out[1].id, "ADR-002")
+  end)
+end)
+"#,
+        )
+        .unwrap();
+    Command::cargo_bin("cli-rag")
+        .unwrap()
+        .current_dir(temp.path())
+        .arg("--config")
+        .arg(temp.child(".cli-rag.toml").path())
+        .args(["lua", "test"])
+        .assert()
+        .success();
+}